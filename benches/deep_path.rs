@@ -0,0 +1,56 @@
+//! Compares a deep `&mut`-rooted path with plain indexing.
+//!
+//! Run by `cargo bench --bench deep_path`. Both loops should take
+//! roughly the same time: the path is expected to compile down to the same
+//! bounds checks as the hand-written code.
+
+use smart_access::Cps;
+use std::hint::black_box;
+use std::time::{ Duration, Instant };
+
+const N: usize = 8;
+const ROUNDS: usize = 2_000_000;
+
+type Cube = Vec<Vec<Vec<Vec<u64>>>>;
+
+fn cube() -> Cube {
+    vec![vec![vec![vec![0; N]; N]; N]; N]
+}
+
+fn measure<F: FnMut(usize)>(name: &str, mut f: F) -> Duration {
+    let start = Instant::now();
+
+    for k in 0..ROUNDS { f(black_box(k)); }
+
+    let elapsed = start.elapsed();
+    println!("{:>10}: {:?}", name, elapsed);
+
+    elapsed
+}
+
+fn main() {
+    let mut direct = cube();
+    let mut via_path = cube();
+
+    let plain = measure("indexing", |k| {
+        let (i, j, l, m) = (k % N, k / N % N, k / (N * N) % N, k / (N * N * N) % N);
+
+        if let Some(x) = direct.get_mut(i)
+            .and_then(|x| x.get_mut(j))
+            .and_then(|x| x.get_mut(l))
+            .and_then(|x| x.get_mut(m))
+        {
+            *x += 1;
+        }
+    });
+
+    let path = measure("path", |k| {
+        let (i, j, l, m) = (k % N, k / N % N, k / (N * N) % N, k / (N * N * N) % N);
+
+        via_path.at(i).at(j).at(l).at(m).access(|x| *x += 1);
+    });
+
+    assert!(direct == via_path);
+
+    println!("{:>10}: {:.2}", "ratio", path.as_secs_f64() / plain.as_secs_f64());
+}
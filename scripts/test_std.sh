@@ -4,3 +4,32 @@ cargo test --features "std_hashmap"
 cargo test --no-default-features --features "collections hashbrown batch_ct batch_rt"
 cargo test --no-default-features --features "collections hashbrown detach"
 cargo test --no-default-features --features "collections hashbrown"
+cargo test --features "tokio"
+cargo test --features "test_utils"
+cargo test --features "laws"
+cargo test --features "profile"
+cargo test --features "profile dyn_path"
+cargo test --features "dyn_path"
+cargo test --features "diff"
+cargo test --features "command serde"
+cargo test --features "either"
+cargo test --features "unsafe-cache"
+cargo test --features "unsafe-pair"
+cargo test --features "derive"
+cargo test --features "registry"
+cargo test --features "patch serde_json"
+cargo test --features "timing"
+cargo test --features "plan"
+cargo test --features "tree"
+cargo test --features "bindings"
+cargo test --features "store"
+cargo test --features "versioned"
+cargo test --features "locks"
+cargo test --features "wire"
+cargo test --features "validation"
+cargo test --features "arbitrary dyn_path patch either"
+cargo test --features "batch_bounded"
+cargo test --features "csv"
+cargo test --features "encoding"
+cargo test --features "globals"
+cargo test --features "ecs"
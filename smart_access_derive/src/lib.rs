@@ -0,0 +1,264 @@
+//! Derive macros for the [`smart_access`](https://crates.io/crates/smart_access) crate.
+//!
+//! Use them through the `derive` feature of `smart_access`,
+//! not directly.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{ parse_macro_input, Data, DeriveInput, Fields, Ident, Token };
+use syn::punctuated::Punctuated;
+
+
+/// See the documentation of `smart_access::Fields`.
+#[proc_macro_derive(Fields)]
+pub fn derive_fields(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match fields(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err)   => err.to_compile_error().into(),
+    }
+}
+
+
+/// See the documentation of `smart_access::traversal::AllFields`.
+#[proc_macro_derive(AllFields)]
+pub fn derive_all_fields(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match all_fields(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err)   => err.to_compile_error().into(),
+    }
+}
+
+
+/// See the documentation of `smart_access::AtDelegate`.
+#[proc_macro_derive(AtDelegate, attributes(at_delegate))]
+pub fn derive_at_delegate(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match at_delegate(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err)   => err.to_compile_error().into(),
+    }
+}
+
+
+fn named_fields(input: &DeriveInput) -> syn::Result<Vec<(Ident, syn::Type)>> {
+    let error = || syn::Error::new_spanned(
+        &input.ident, "only structs with named fields are supported"
+    );
+
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => Ok(fields.named.iter()
+                .map(|f| (f.ident.clone().unwrap(), f.ty.clone()))
+                .collect()),
+            _ => Err(error()),
+        },
+        _ => Err(error()),
+    }
+}
+
+
+fn snake_case(name: &str) -> String {
+    let mut result = String::new();
+
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 { result.push('_'); }
+            result.extend(c.to_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+
+fn fields(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = named_fields(input)?;
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let module = Ident::new(&format!("{}_fields", snake_case(&name.to_string())), Span::call_site());
+    let trait_name = Ident::new(&format!("{}Fields", name), Span::call_site());
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let params = input.generics.params.iter();
+    let predicates = where_clause.map(|w| &w.predicates);
+
+    let markers = fields.iter().map(|(field, _)| {
+        let doc = format!("A marker index for the `{}` field of [`{}`](super::{}).", field, name, name);
+
+        quote! {
+            #[doc = #doc]
+            #[allow(non_camel_case_types)]
+            #[derive(Debug, Copy, Clone)]
+            pub struct #field;
+        }
+    });
+
+    let at_impls = fields.iter().map(|(field, ty)| quote! {
+        impl #impl_generics ::smart_access::At<#module::#field> for #name #ty_generics #where_clause {
+            type View = #ty;
+
+            fn access_at<__R, __F>(&mut self, _: #module::#field, f: __F) -> Option<__R> where
+                __F: FnOnce(&mut #ty) -> __R
+            {
+                Some(f(&mut self.#field))
+            }
+        }
+    });
+
+    let signatures = fields.iter().map(|(field, _)| {
+        let doc = format!("Moves to the `{}` field.", field);
+
+        quote! {
+            #[doc = #doc]
+            fn #field(self) -> ::smart_access::AT<__CPS, (__List, #module::#field)>;
+        }
+    });
+
+    let methods = fields.iter().map(|(field, _)| quote! {
+        fn #field(self) -> ::smart_access::AT<__CPS, (__List, #module::#field)> {
+            self.at(#module::#field)
+        }
+    });
+
+    let module_doc = format!("Marker indices for the fields of [`{}`](super::{}).", name, name);
+    let trait_doc = format!("Path-building methods for the fields of [`{}`]({}).", name, name);
+
+    Ok(quote! {
+        #[doc = #module_doc]
+        #vis mod #module {
+            #(#markers)*
+        }
+
+        #(#at_impls)*
+
+        #[doc = #trait_doc]
+        #vis trait #trait_name<__CPS, __List>: Sized {
+            #(#signatures)*
+        }
+
+        // the bound is on the impl: traits of structs sharing a field name don't clash
+        impl<#(#params,)* __CPS, __List> #trait_name<__CPS, __List> for ::smart_access::AT<__CPS, __List> where
+            ::smart_access::AT<__CPS, __List>: ::smart_access::Cps<View=#name #ty_generics>,
+            #predicates
+        {
+            #(#methods)*
+        }
+    })
+}
+
+
+fn all_fields(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => return Err(syn::Error::new_spanned(&input.ident, "only structs are supported")),
+    };
+
+    let ty = match fields.iter().next() {
+        Some(field) => &field.ty,
+        None => return Err(syn::Error::new_spanned(&input.ident, "the struct must have at least one field")),
+    };
+
+    let ty_tokens = quote!(#ty).to_string();
+
+    for field in fields.iter() {
+        let field_ty = &field.ty;
+
+        if quote!(#field_ty).to_string() != ty_tokens {
+            return Err(syn::Error::new_spanned(&field.ty, "all fields must have the same type"));
+        }
+    }
+
+    let members = fields.iter().enumerate().map(|(i, field)| match &field.ident {
+        Some(ident) => quote! { #ident },
+        None => {
+            let index = syn::Index::from(i);
+            quote! { #index }
+        },
+    });
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::smart_access::traversal::Of<::smart_access::traversal::AllFields> for #name #ty_generics #where_clause {
+            type View = #ty;
+
+            fn each_of<__F>(&mut self, _: ::smart_access::traversal::AllFields, mut f: __F) -> bool where
+                __F: FnMut(&mut #ty) -> bool
+            {
+                let _ = #( f(&mut self.#members) )&&*;
+
+                true
+            }
+        }
+    })
+}
+
+
+fn at_delegate(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let error = || syn::Error::new_spanned(
+        &input.ident, "only structs with exactly one field are supported"
+    );
+
+    let field = match &input.data {
+        Data::Struct(data) if data.fields.len() == 1 => data.fields.iter().next().unwrap(),
+        _ => return Err(error()),
+    };
+
+    let member = match &field.ident {
+        Some(ident) => quote! { #ident },
+        None => quote! { 0 },
+    };
+
+    let mut indices = Vec::new();
+
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("at_delegate")) {
+        let list = attr.parse_args_with(Punctuated::<syn::Type, Token![,]>::parse_terminated)?;
+        indices.extend(list);
+    }
+
+    let ty = &field.ty;
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let predicates = where_clause.map(|w| &w.predicates);
+
+    let delegated = indices.iter().map(|index| quote! {
+        impl #impl_generics ::smart_access::At<#index> for #name #ty_generics where
+            #ty: ::smart_access::At<#index>,
+            #predicates
+        {
+            type View = <#ty as ::smart_access::At<#index>>::View;
+
+            fn access_at<__R, __F>(&mut self, i: #index, f: __F) -> Option<__R> where
+                __F: FnOnce(&mut Self::View) -> __R
+            {
+                self.#member.access_at(i, f)
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::smart_access::At<()> for #name #ty_generics #where_clause {
+            type View = #ty;
+
+            fn access_at<__R, __F>(&mut self, _: (), f: __F) -> Option<__R> where
+                __F: FnOnce(&mut #ty) -> __R
+            {
+                Some(f(&mut self.#member))
+            }
+        }
+
+        #(#delegated)*
+    })
+}
@@ -0,0 +1,184 @@
+//! Support for the [`arbitrary`](https://crates.io/crates/arbitrary) crate. __Requires `arbitrary`.__
+//!
+//! `Arbitrary` is implemented for:
+//! * the index types of the crate: [`Idx`](../core_impls/struct.Idx.html),
+//!   [`Clamped`](../core_impls/struct.Clamped.html), 
+//!   [`Rotated`](../core_impls/struct.Rotated.html),
+//!   [`Checked`](../struct.Checked.html), the marker indices
+//!   (e.g. [`SomeP`](../core_impls/struct.SomeP.html)) and (with the
+//!   corresponding features enabled) [`Append`](../collections/struct.Append.html),
+//!   [`TryReserve`](../collections/struct.TryReserve.html),
+//!   [`KeyList`](../traversal/struct.KeyList.html) and
+//!   [path keys](../patch/enum.Key.html)
+//! * [runtime paths](../struct.DynPath.html) and
+//!   [detached paths](../type.DetachedPath.html) over generatable indices
+//!
+//! Thus user-defined `At` implementations and path-handling code can be fuzzed:
+//!
+//! ```
+//! use smart_access::{ Cps, DetachedPath };
+//! use arbitrary::{ Arbitrary, Unstructured };
+//!
+//! type Path2 = DetachedPath<Vec<Vec<u8>>, (((), usize), usize)>;
+//!
+//! let data = [7u8; 64];
+//! let mut u = Unstructured::new(&data);
+//!
+//! let mut foo = vec![ vec![1, 2], vec![3] ];
+//!
+//! for _ in 0..4 {
+//!     let path = Path2::arbitrary(&mut u).unwrap();
+//!
+//!     // the `At` contract: a failed access leaves the data untouched
+//!     let before = foo.clone();
+//!     if foo.attach(path).replace(0).is_none() { assert!(foo == before); }
+//! }
+//! ```
+
+use arbitrary::{ Arbitrary, Result, Unstructured };
+use crate::core_impls::{ Idx, Clamped, Rotated, Normalized, Deref, SomeP, OkP, ErrP, BreakP, AsUtf8 };
+use crate::Checked;
+
+#[cfg(feature="collections")]
+use crate::collections::{ Append, TryReserve };
+
+#[cfg(feature="traversal")]
+use crate::traversal::{ KeyList, Rev };
+
+#[cfg(all(feature="traversal", feature="unsafe-pair"))]
+use crate::traversal::{ Entries };
+
+#[cfg(feature="either")]
+use crate::either_impls::{ LeftP, RightP };
+
+#[cfg(feature="patch")]
+use crate::patch::Key;
+
+#[cfg(feature="dyn_path")]
+use crate::DynPath;
+
+
+macro_rules! arbitrary_marker {
+    ( $($t:ty),* ) => { $(
+        impl<'a> Arbitrary<'a> for $t {
+            fn arbitrary(_: &mut Unstructured<'a>) -> Result<Self> {
+                Ok(Self)
+            }
+
+            fn size_hint(_: usize) -> (usize, Option<usize>) {
+                (0, Some(0))
+            }
+        }
+    )* };
+}
+
+macro_rules! arbitrary_newtype {
+    ( $($t:ident),* ) => { $(
+        impl<'a, I: Arbitrary<'a>> Arbitrary<'a> for $t<I> {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                Ok($t(u.arbitrary()?))
+            }
+
+            fn size_hint(depth: usize) -> (usize, Option<usize>) {
+                I::size_hint(depth)
+            }
+        }
+    )* };
+}
+
+arbitrary_marker!(Normalized, Deref, SomeP, OkP, ErrP, BreakP, AsUtf8, crate::core_impls::Flatten);
+arbitrary_newtype!(Idx, Checked);
+
+impl<'a, T: Arbitrary<'a> + Clone + PartialOrd> Arbitrary<'a> for Clamped<T> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Clamped(u.arbitrary()?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Rotated {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Rotated(u.arbitrary()?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        usize::size_hint(depth)
+    }
+}
+
+#[cfg(feature="collections")]
+arbitrary_newtype!(Append);
+
+#[cfg(feature="collections")]
+impl<'a, I: Arbitrary<'a>> Arbitrary<'a> for TryReserve<I> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(TryReserve { range: u.arbitrary()?, additional: u.arbitrary()? })
+    }
+}
+
+#[cfg(feature="traversal")]
+arbitrary_newtype!(KeyList);
+
+#[cfg(feature="traversal")]
+arbitrary_marker!(Rev, crate::traversal::Flatten);
+
+#[cfg(all(feature="traversal", feature="unsafe-pair"))]
+arbitrary_marker!(Entries);
+
+#[cfg(feature="either")]
+arbitrary_marker!(LeftP, RightP);
+
+#[cfg(feature="patch")]
+impl<'a> Arbitrary<'a> for Key {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=2u8)? {
+            0 => Key::Name(u.arbitrary()?),
+            1 => Key::Index(u.arbitrary()?),
+            _ => Key::Range { start: u.arbitrary()?, end: u.arbitrary()? },
+        })
+    }
+}
+
+/// The path length is arbitrary
+#[cfg(feature="dyn_path")]
+impl<'a, I: Arbitrary<'a>> Arbitrary<'a> for DynPath<I> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.arbitrary_iter()?.collect()
+    }
+}
+
+
+#[test]
+fn test_arbitrary() {
+    extern crate std;
+    use std::vec::Vec;
+
+    let data = (0..=255).collect::<Vec<u8>>();
+    let mut u = Unstructured::new(&data);
+
+    let Idx(_): Idx<u8> = u.arbitrary().unwrap();
+    let Clamped(_): Clamped<i32> = u.arbitrary().unwrap();
+    let SomeP = u.arbitrary().unwrap();
+
+    // arbitrary patches: the rendered path is parsed back
+    // and applying it to an arbitrary value doesn't panic
+    #[cfg(feature="patch")] {
+        use crate::patch::{ parse_path, Patch };
+        use std::string::ToString;
+
+        let mut seed = 1u32;
+        let data = (0..4096).map(|_| { seed = seed.wrapping_mul(1103515245).wrapping_add(12345); (seed >> 16) as u8 }).collect::<Vec<u8>>();
+        let mut u = Unstructured::new(&data);
+
+        while !u.is_empty() {
+            let path: DynPath<Key> = u.arbitrary().unwrap();
+            let mut value: Vec<Option<Vec<u8>>> = u.arbitrary().unwrap();
+
+            assert!(parse_path(&path.to_string()).unwrap() == path);
+
+            let before = value.clone();
+            let patch = Patch { path, value: "7".into() };
+
+            if !patch.apply(&mut value) { assert!(value == before); }
+        }
+    }
+}
@@ -1,11 +1,14 @@
-#[cfg(any(feature="batch_ct", feature="batch_rt"))]
+#[cfg(any(feature="batch_ct", feature="batch_rt", feature="batch_bounded"))]
 use crate::batch::{ CpsBatch };
 
 #[cfg(feature="batch_ct")]
 use crate::batch::{ new_batch_ct };
 
 #[cfg(feature="batch_rt")]
-use crate::batch::{ new_batch_rt, FnBoxRt };
+use crate::batch::{ new_batch_rt, FnBoxRt, new_batch_loop, FnMutBoxRt };
+
+#[cfg(feature="batch_bounded")]
+use crate::batch::{ new_batch_bounded };
 
 #[cfg(feature="detach")]
 mod detach; // detached paths
@@ -14,17 +17,96 @@ mod detach; // detached paths
 use detach::{ DetachedRoot };
 
 #[cfg(feature="detach")]
-pub use detach::{ Attach, DetachedPath };
+pub use detach::{ Attach, DetachedPath, EmptyPath, PathTo };
+
+#[cfg(feature="detach")]
+mod named; // named detach points
+
+#[cfg(feature="detach")]
+use named::{ new_named };
+
+#[cfg(feature="detach")]
+pub use named::{ Named, DetachNamed };
 
 #[cfg(feature="traversal")]
 pub mod traversal;
 
+#[cfg(feature="dyn_path")]
+mod dyn_path; // homogeneous runtime paths
+
+#[cfg(feature="dyn_path")]
+use dyn_path::{ new_dyn_at };
+
+#[cfg(feature="dyn_path")]
+pub use dyn_path::{ DynPath, DynAT };
+
+#[cfg(feature="unsafe-cache")]
+mod cached; // paths resolved once
+
+#[cfg(feature="unsafe-cache")]
+pub use cached::{ CachedAt };
+
+#[cfg(feature="dyn_path")]
+mod rooted; // paths built step by step
+
+#[cfg(feature="dyn_path")]
+pub use rooted::{ RootedPath };
+
+#[cfg(feature="unsafe-pair")]
+mod pair; // accessing two places at once
+
+mod any; // trying several indices
+
+use any::{ new_at_any };
+pub use any::{ AtAny };
+
+mod by; // data-dependent indices
+
+use by::{ new_at_by };
+pub use by::{ AtBy };
+
+mod validated; // gated write-back
+pub use validated::{ Validated };
+
+mod try_at; // typed access errors
+pub use try_at::{ TryAt, Checked };
+
+mod capability; // read-only accessors
+pub use capability::{ ReadAccess, WriteAccess, ReadOnly, ReadWrite };
+
+mod ok_or; // user-defined errors
+use ok_or::{ new_ok_or, new_ok_or_else };
+pub use ok_or::{ OkOr, OkOrElse };
+
+mod num; // numeric helpers
+pub use num::{ CpsNum };
+
+#[cfg(feature="alloc")]
+mod collect; // collection helpers
+
+#[cfg(feature="alloc")]
+pub use collect::{ CpsCollect };
+
+#[cfg(feature="alloc")]
+mod text; // string helpers
+
+#[cfg(feature="alloc")]
+pub use text::{ CpsString };
+
+#[cfg(feature="unsafe-pair")]
+use pair::{ new_zip };
+
+#[cfg(feature="unsafe-pair")]
+pub use pair::{ Pair, Zip };
+
 #[cfg(feature="traversal")]
 use traversal::{ Each, Of };
 
 #[cfg(feature="batch_rt")]
 use alloc::vec::Vec;
 
+use core::fmt;
+
 /// A smart access protocol.
 ///
 /// It is intended to be used through a [`Cps`](trait.Cps.html)-bounded type.
@@ -88,6 +170,36 @@ pub trait Cps: Sized {
         self.access(|x| core::mem::replace(x, new_val))
     }
 
+    /// Equivalent to `self.access(|x| { *x = new_val; }).is_some()`
+    ///
+    /// Unlike [`replace`](#method.replace) the old value is simply dropped.
+    ///
+    /// ```
+    /// # use smart_access::Cps;
+    /// let mut foo = vec![1, 2];
+    ///
+    /// assert!(foo.at(0).set(3));
+    /// assert!(!foo.at(2).set(4));
+    /// assert!(foo == vec![3, 2]);
+    /// ```
+    fn set(self, new_val: Self::View) -> bool where
+        Self::View: Sized
+    {
+        self.access(|x| { *x = new_val; }).is_some()
+    }
+
+    /// Accesses the view at a [fallible index](trait.TryAt.html).
+    ///
+    /// Returns `None` if `self` fails, `Some(Err(..))` if the index fails.
+    ///
+    /// __Not intended for overriding.__
+    fn try_at<Index, R, F>(self, i: Index, f: F) -> Option<Result<R, <Self::View as TryAt<Index>>::Error>> where
+        Self::View: TryAt<Index>,
+        F: FnOnce(&mut <Self::View as TryAt<Index>>::View) -> R
+    {
+        self.access(|v| v.try_access_at(i, f))
+    }
+
     /// Equivalent to `self.access(|_| ())`
     fn touch(self) -> Option<()> where
     {
@@ -101,6 +213,54 @@ pub trait Cps: Sized {
         self.access(|x| x.clone())
     }
 
+    /// Equivalent to `self.get_clone().unwrap_or(default)`
+    ///
+    /// ```
+    /// # use smart_access::Cps;
+    /// let mut foo = Some(1);
+    /// let mut bar: Option<i32> = None;
+    ///
+    /// assert!(foo.at(()).get_or(0) == 1);
+    /// assert!(bar.at(()).get_or(0) == 0);
+    /// assert!(bar.at(()).get_or_default() == 0);
+    /// ```
+    fn get_or(self, default: Self::View) -> Self::View where
+        Self::View: Sized + Clone
+    {
+        self.get_clone().unwrap_or(default)
+    }
+
+    /// Equivalent to `self.get_clone().unwrap_or_default()`
+    fn get_or_default(self) -> Self::View where
+        Self::View: Sized + Clone + Default
+    {
+        self.get_clone().unwrap_or_default()
+    }
+
+    /// Applies `f` to the view and reports whether the view has changed.
+    ///
+    /// Useful for dirty-flag propagation:
+    ///
+    /// ```
+    /// # use smart_access::Cps;
+    /// let mut foo = vec![1, 2];
+    ///
+    /// assert!(foo.at(0).update(|x| { *x = 1; }) == Some(false));
+    /// assert!(foo.at(0).update(|x| { *x = 3; }) == Some(true));
+    /// assert!(foo.at(2).update(|x| { *x = 4; }) == None);
+    /// ```
+    fn update<F>(self, f: F) -> Option<bool> where
+        Self::View: Sized + Clone + PartialEq,
+        F: FnOnce(&mut Self::View)
+    {
+        self.access(|x| {
+            let old = x.clone();
+            f(x);
+
+            *x != old
+        })
+    }
+
     /// &#8220;Moves in the direction&#8221; of the provided index.
     ///
     /// __Not intended for overriding.__
@@ -110,6 +270,198 @@ pub trait Cps: Sized {
         AT { cps: self, list: ((), i) } 
     }
 
+    /// Expands a [tuple of indices](trait.TuplePath.html) into a chain of `.at(..)` calls.
+    ///
+    /// __Not intended for overriding.__
+    ///
+    /// ### Usage example
+    ///
+    /// ```
+    /// # use smart_access::Cps;
+    /// let mut foo = vec![vec![Some(1)]];
+    ///
+    /// assert!(foo.at_path((0, 0, ())).replace(2) == Some(1));
+    /// assert!(foo.at(0).at_path((0, ())).replace(3) == Some(2));
+    /// assert!(foo == vec![vec![Some(3)]]);
+    /// ```
+    fn at_path<P: TuplePath>(self, p: P) -> AT<Self, P::List> where
+        P::List: AtView<Self::View>
+    {
+        AT { cps: self, list: p.into_list() }
+    }
+
+    /// Tries the indices in order and accesses the first one that resolves.
+    ///
+    /// __Not intended for overriding.__
+    ///
+    /// ### Usage example
+    ///
+    /// ```
+    /// # use smart_access::Cps;
+    /// # use std::collections::BTreeMap;
+    /// let mut config = BTreeMap::new();
+    /// config.insert("colour", 1);  // a legacy name
+    ///
+    /// assert!(config.at_any(vec![&"color", &"colour"]).replace(2) == Some(1));
+    /// assert!(config[&"colour"] == 2);
+    /// ```
+    fn at_any<Indices>(self, indices: Indices) -> AtAny<Self, Indices> where
+        Indices: IntoIterator,
+        Self::View: At<Indices::Item>
+    {
+        new_at_any(self, indices)
+    }
+
+    /// Computes the next index from the current view.
+    ///
+    /// The index is chosen and used inside the same access, thus it can
+    /// depend on any part of the view.
+    ///
+    /// __Not intended for overriding.__
+    ///
+    /// ### Usage example
+    ///
+    /// ```
+    /// # use smart_access::Cps;
+    /// let mut tree = vec![ vec![3, 7, 5], vec![] ];
+    ///
+    /// // descends into the child with the greatest value
+    /// let argmax = |xs: &Vec<i32>| (0..xs.len()).max_by_key(|&i| xs[i]).unwrap_or(0);
+    ///
+    /// assert!(tree.at(0).at_by(argmax).access(|x| *x += 1) == Some(()));
+    /// assert!(tree.at(1).at_by(argmax).access(|x| *x += 1) == None);
+    /// assert!(tree == vec![ vec![3, 8, 5], vec![] ]);
+    /// ```
+    fn at_by<I, F>(self, choose: F) -> AtBy<Self, F> where
+        F: FnOnce(&Self::View) -> I,
+        Self::View: At<I>
+    {
+        new_at_by(self, choose)
+    }
+
+    /// Writes back only the values satisfying the predicate.
+    /// See [`Validated`](struct.Validated.html).
+    ///
+    /// __Not intended for overriding.__
+    fn validated<P>(self, pred: P) -> Validated<Self, P> where
+        Self::View: Sized + Clone,
+        P: FnOnce(&Self::View) -> bool
+    {
+        Validated(self, pred)
+    }
+
+    /// Restricts the accessor to reading. See [`ReadOnly`](struct.ReadOnly.html).
+    ///
+    /// __Not intended for overriding.__
+    fn read_only(self) -> ReadOnly<Self> {
+        ReadOnly::new(self)
+    }
+
+    /// Reports access failures as the error. See [`OkOr`](struct.OkOr.html).
+    ///
+    /// __Not intended for overriding.__
+    fn ok_or<E>(self, err: E) -> OkOr<Self, E> {
+        new_ok_or(self, err)
+    }
+
+    /// Reports access failures as the error created by the closure.
+    /// See [`OkOrElse`](struct.OkOrElse.html).
+    ///
+    /// __Not intended for overriding.__
+    fn ok_or_else<E, G>(self, err: G) -> OkOrElse<Self, G> where
+        G: FnOnce() -> E
+    {
+        new_ok_or_else(self, err)
+    }
+
+    #[cfg(feature="unsafe-pair")]
+    /// Combines two `Cps` values into a single one viewing a [pair](struct.Pair.html).
+    ///
+    /// The access fails if either of the values fails.
+    ///
+    /// __Not intended for overriding.__
+    ///
+    /// _Present only on `unsafe-pair`._
+    ///
+    /// ### Usage example
+    ///
+    /// ```
+    /// # use smart_access::Cps;
+    /// # use std::collections::BTreeMap;
+    /// let mut budget = BTreeMap::new();
+    /// budget.insert("food", 100);
+    ///
+    /// let mut reserve = vec![50];
+    ///
+    /// let moved = budget.at(&"food").zip(reserve.at(0)).access(|pair| {
+    ///     let (from, to) = pair.split();
+    ///     *from -= 30;
+    ///     *to += 30;
+    /// });
+    ///
+    /// assert!(moved == Some(()));
+    /// assert!(budget[&"food"] == 70 && reserve == vec![80]);
+    ///
+    /// assert!(budget.at(&"rent").zip(reserve.at(0)).touch() == None);
+    /// ```
+    fn zip<Other: Cps>(self, other: Other) -> Zip<Self, Other> {
+        new_zip(self, other)
+    }
+
+    #[cfg(feature="dyn_path")]
+    /// Attaches a [runtime path](struct.DynPath.html).
+    ///
+    /// __Not intended for overriding.__
+    ///
+    /// _Present only on `dyn_path`._
+    fn at_dyn<I>(self, path: DynPath<I>) -> DynAT<Self, DynPath<I>> where
+        Self::View: At<I, View=Self::View>
+    {
+        new_dyn_at(self, path)
+    }
+
+    #[cfg(feature="dyn_path")]
+    /// Walks a runtime sequence of indices.
+    ///
+    /// A lightweight version of [`at_dyn`](#method.at_dyn) for the case when
+    /// the indices don't need to be stored.
+    ///
+    /// __Not intended for overriding.__
+    ///
+    /// _Present only on `dyn_path`._
+    ///
+    /// ### Usage example
+    ///
+    /// ```
+    /// use smart_access::{ At, Cps };
+    ///
+    /// struct Node { value: i32, children: Vec<Node> }
+    ///
+    /// impl At<usize> for Node {
+    ///     type View = Node;
+    ///
+    ///     fn access_at<R, F>(&mut self, i: usize, f: F) -> Option<R> where
+    ///         F: FnOnce(&mut Node) -> R
+    ///     {
+    ///         self.children.get_mut(i).map(f)
+    ///     }
+    /// }
+    ///
+    /// let leaf = |value| Node { value, children: vec![] };
+    /// let mut tree = Node { value: 0, children: vec![ leaf(1), Node { value: 2, children: vec![ leaf(3) ] } ] };
+    ///
+    /// let path = "1/0".split('/').map(|i| i.parse::<usize>().unwrap());
+    ///
+    /// assert!(tree.at_iter(path).access(|node| node.value) == Some(3));
+    /// assert!(tree.at_iter(vec![0, 0]).touch().is_none());
+    /// ```
+    fn at_iter<Indices>(self, indices: Indices) -> DynAT<Self, Indices> where
+        Indices: IntoIterator,
+        Self::View: At<Indices::Item, View=Self::View>
+    {
+        new_dyn_at(self, indices)
+    }
+
     #[cfg(feature="batch_ct")]
     /// Constructs a [compile-time batch](struct.CpsBatch.html).
     ///
@@ -130,6 +482,26 @@ pub trait Cps: Sized {
         new_batch_rt(self)
     }
 
+    #[cfg(feature="batch_rt")]
+    /// Constructs a [looping runtime batch](struct.CpsBatch.html).
+    ///
+    /// __Not intended for overriding.__
+    ///
+    /// _Present only on `batch_rt`._
+    fn batch_loop<R>(self) -> CpsBatch<Self, Vec<FnMutBoxRt<Self::View, R>>> {
+        new_batch_loop(self)
+    }
+
+    #[cfg(feature="batch_bounded")]
+    /// Constructs a [fixed-capacity runtime batch](struct.CpsBatch.html#fixed-capacity-version).
+    ///
+    /// __Not intended for overriding.__
+    ///
+    /// _Present only on `batch_bounded`._
+    fn batch_bounded<S, const N: usize>(self) -> CpsBatch<Self, heapless::Vec<S, N>> {
+        new_batch_bounded(self)
+    }
+
     #[cfg(feature="detach")]
     /// Attaches a [detached](trait.Attach.html) path.
     ///
@@ -173,6 +545,44 @@ pub trait Cps: Sized {
     {
         AT { cps: self, list: () }
     } 
+
+    #[cfg(feature="detach")]
+    /// Creates a new detach point named by a marker value.
+    ///
+    /// __Not intended for overriding.__
+    ///
+    /// _Present only on `detach`._
+    ///
+    /// Unlike [`.detach()`](struct.AT.html#method.detach), which always uses
+    /// the closest detach point, the 
+    /// [`.detach_named(marker)`](struct.AT.html#method.detach_named) method 
+    /// can split a path at any named detach point:
+    ///
+    /// ```
+    /// # use smart_access::Cps;
+    /// struct Record;
+    /// struct Field;
+    ///
+    /// let mut foo = vec![vec![Some(1)]];
+    /// let mut bar = vec![Some(2)];
+    ///
+    /// let (left, right) = foo.at(0).cut_named(Record)
+    ///     .at(0).cut_named(Field)
+    ///     .at(())
+    ///     .detach_named(Record);
+    ///
+    /// assert!(bar.attach(right).replace(3) == Some(2));
+    /// assert!(bar == vec![Some(3)]);
+    ///
+    /// assert!(left.at(0).at(()).replace(4) == Some(1));
+    /// assert!(foo == vec![vec![Some(4)]]);
+    /// ```
+    ///
+    /// Markers must be distinct within a path.
+    fn cut_named<M>(self, _marker: M) -> AT<Named<Self, M>, ()>
+    {
+        AT { cps: new_named(self), list: () }
+    }
 }
 
 
@@ -267,6 +677,8 @@ impl<T: ?Sized> Cps for &mut T {
 /// # test();
 /// ```
 ///
+/// The same bound can be written as [`impl PathTo<CPS::View, V>`](trait.PathTo.html).
+///
 /// But sometimes an explicit `AT` can be useful (the example below 
 /// is artificial and thus not very illuminating...): 
 ///
@@ -330,6 +742,19 @@ impl<CPS, List> AT<CPS, List> {
     }
     
     
+    /// Override for [`at_path` of `Cps`](trait.Cps.html#method.at_path).
+    ///
+    /// Preserves flat structure.
+    pub fn at_path<P, View: ?Sized>(self, p: P) -> AT<CPS, <List as Concat<P::List>>::Output> where
+        P: TuplePath,
+        List: Concat<P::List>,
+        AT<CPS, List>: Cps<View=View>,
+        P::List: AtView<View>
+    {
+        AT { cps: self.cps, list: self.list.concat(p.into_list()) }
+    }
+    
+    
     /// Override for [`from` of `Each`](traversal/trait.Each.html#method.from).
     ///
     /// Preserves flat structure.
@@ -344,6 +769,29 @@ impl<CPS, List> AT<CPS, List> {
 }
 
 
+impl<CPS, Prev, I> AT<CPS, (Prev, I)> {
+    /// Transforms the last index of the path.
+    ///
+    /// ### Usage example
+    ///
+    /// ```
+    /// use smart_access::Cps;
+    ///
+    /// let mut foo = vec![1, 2, 3];
+    ///
+    /// assert!(foo.at(3).map_index(|i| i - 1).replace(4) == Some(3));
+    /// assert!(foo == vec![1, 2, 4]);
+    /// ```
+    pub fn map_index<J, F>(self, f: F) -> AT<CPS, (Prev, J)> where
+        F: FnOnce(I) -> J
+    {
+        let (prev, index) = self.list;
+
+        AT { cps: self.cps, list: (prev, f(index)) }
+    }
+}
+
+
 
 
 /// `AT` can be broken apart to detach a single path component.
@@ -360,6 +808,81 @@ impl<CPS,Prev,I> From<AT<CPS,(Prev,I)>> for (AT<CPS,Prev>,I)
 }
 
 
+impl<CPS, List> AT<CPS, List> {
+    /// Breaks `AT` apart into the root `Cps` value and the complete index list.
+    ///
+    /// The list is a left-nested tuple: `((((), i1), i2), i3)`.
+    ///
+    /// ### Usage example
+    ///
+    /// ```
+    /// use smart_access::{ Cps, AT };
+    ///
+    /// let mut foo = vec![vec![1, 2], vec![3]];
+    ///
+    /// let (root, (((), i), j)) = foo.at(0).at(1).into_parts();
+    /// let path = AT::from_parts(root, (((), i+1), j-1));
+    ///
+    /// assert!(path.replace(4) == Some(3));
+    /// assert!(foo == vec![vec![1, 2], vec![4]]);
+    /// ```
+    pub fn into_parts(self) -> (CPS, List) {
+        (self.cps, self.list)
+    }
+
+    /// Reassembles `AT` from the parts returned by [`into_parts`](#method.into_parts).
+    pub fn from_parts(cps: CPS, list: List) -> Self {
+        AT { cps, list }
+    }
+
+    /// The number of path components. See [`PathLen`](trait.PathLen.html).
+    pub fn path_len(&self) -> usize where
+        List: PathLen
+    {
+        List::LEN
+    }
+
+    /// Walks the path components. See [`VisitPath`](trait.VisitPath.html).
+    pub fn visit_path<F>(&self, mut f: F) where
+        List: VisitPath,
+        F: FnMut(usize, &dyn fmt::Debug)
+    {
+        self.list.visit(&mut f)
+    }
+}
+
+
+impl<T: ?Sized, List: Clone> AT<&mut T, List> {
+    /// A copy of the accessor borrowing the same root.
+    ///
+    /// `Cps` methods consume the accessor. A path rooted in a mutable
+    /// reference can be reused instead of being rebuilt: the copy reborrows
+    /// the root (for the lifetime of `&mut self`) and clones the indices.
+    ///
+    /// Note that `&mut AT<..>` is a `Cps` value itself (with the view being
+    /// the `AT` value), thus the path is reused by calling `reborrow`.
+    ///
+    /// ### Usage example
+    ///
+    /// ```
+    /// use smart_access::Cps;
+    ///
+    /// let mut counters = vec![vec![0, 0], vec![0]];
+    /// let mut second = counters.at(0).at(1);
+    ///
+    /// for i in 1..=3 {
+    ///     assert!(second.reborrow().access(|x| { *x += i; *x }) == Some(i * (i + 1) / 2));
+    /// }
+    ///
+    /// assert!(second.get_clone() == Some(6));
+    /// assert!(counters == vec![vec![0, 6], vec![0]]);
+    /// ```
+    pub fn reborrow(&mut self) -> AT<&mut T, List> {
+        AT { cps: &mut *self.cps, list: self.list.clone() }
+    }
+}
+
+
 #[cfg(feature="detach")]
 impl<CPS: Cps, List> AT<CPS, List> {
 
@@ -395,6 +918,19 @@ impl<CPS: Cps, List> AT<CPS, List> {
     pub fn detach(self) -> (CPS, DetachedPath<CPS::View, List>) {
         (self.cps, AT { cps: DetachedRoot::new(), list: self.list })
     }
+
+/// Detaches the path starting from the [detach point](trait.Cps.html#method.cut_named)
+/// named by the marker.
+///
+/// The detached path includes all the components after the named point
+/// (other detach points are flattened).
+///
+/// _Present only on `detach`._
+    pub fn detach_named<M, Pos>(self, _marker: M) -> (<Self as DetachNamed<M, Pos>>::Left, <Self as DetachNamed<M, Pos>>::Path) where
+        Self: DetachNamed<M, Pos>
+    {
+        DetachNamed::detach_named(self)
+    }
 }
 
 
@@ -483,6 +1019,27 @@ pub fn detached_at<View: ?Sized, I>(i: I) -> DetachedPath<View, ((), I)> where
 }
 
 
+/// Creates an empty detached path starting at `Root`. __Requires `detach` feature.__
+///
+/// Unlike [`detached_at`](fn.detached_at.html) the root type is fixed
+/// upfront, so the first index is inferred from it:
+///
+/// ```
+/// use smart_access::{ Cps, detached };
+///
+/// let path = detached::<Vec<Vec<i32>>>().at(1).at(0);
+/// let mut foo = vec![vec![1], vec![2]];
+///
+/// assert!(foo.attach(path).replace(3) == Some(2));
+/// ```
+///
+/// _Present only on `detach`._
+#[cfg(feature="detach")]
+pub fn detached<Root: ?Sized>() -> EmptyPath<Root> {
+    AT::new()
+}
+
+
 
 
 /// A trait which may be needed alongside [`Attach`](trait.Attach.html) bounds.
@@ -504,18 +1061,33 @@ pub trait AtView<View: ?Sized>: Sized {
     fn give_access<CPS, R, F>(self, cps: CPS, f: F) -> Option<R> where
         CPS: Cps<View=View>,
         F: FnOnce(&mut Self::View) -> R;
+
+    /// Walks the path from the root. Returns `true` iff every step succeeds.
+    ///
+    /// The result of the access is passed by the caller through a side-effect
+    /// of `f`: in such a way no intermediate `Option<Option<R>>` values are built.
+    fn walk<F>(self, root: &mut View, f: F) -> bool where
+        F: FnOnce(&mut Self::View) -> bool;
 }
 
 
 impl<View: ?Sized> AtView<View> for () {
     type View = View;
     
+    #[inline]
     fn give_access<CPS, R, F>(self, cps: CPS, f: F) -> Option<R> where
         CPS: Cps<View=View>,
         F: FnOnce(&mut Self::View) -> R
     {
         cps.access(f)
     }
+
+    #[inline]
+    fn walk<F>(self, root: &mut View, f: F) -> bool where
+        F: FnOnce(&mut View) -> bool
+    {
+        f(root)
+    }
 }
 
 impl<View: ?Sized, Prev, Index> AtView<View> for (Prev, Index) where
@@ -524,15 +1096,172 @@ impl<View: ?Sized, Prev, Index> AtView<View> for (Prev, Index) where
 {
     type View = <Prev::View as At<Index>>::View;
     
+    #[inline]
     fn give_access<CPS, R, F>(self, cps: CPS, f: F) -> Option<R> where
         CPS: Cps<View=View>,
         F: FnOnce(&mut Self::View) -> R
+    {
+        let mut result = None;
+
+        let done = cps.access(|root| self.walk(root, |v| { result = Some(f(v)); true }));
+
+        if done == Some(true) { result } else { None }
+    }
+
+    #[inline]
+    fn walk<F>(self, root: &mut View, f: F) -> bool where
+        F: FnOnce(&mut Self::View) -> bool
     {
         let (prev, index) = self;
 
-        prev.give_access(cps, |v| { v.access_at(index, f) }).flatten()
+        prev.walk(root, |v| v.access_at(index, f) == Some(true))
+    }
+}
+
+
+
+
+
+
+/// A helper trait rendering a path list as a chain of `.at(..)` calls.
+///
+/// Implemented for path lists with `Debug` indices.
+pub trait RenderPath {
+    fn render<W: fmt::Write>(&self, w: &mut W) -> fmt::Result;
+}
+
+impl RenderPath for () {
+    fn render<W: fmt::Write>(&self, _: &mut W) -> fmt::Result { Ok(()) }
+}
+
+impl<Prev: RenderPath, Index: fmt::Debug> RenderPath for (Prev, Index) {
+    fn render<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        self.0.render(w)?;
+
+        write!(w, ".at({:?})", self.1)
+    }
+}
+
+
+// type-level concatenation of path lists
+//
+// Is public only to be used in bounds (e.g. of `DetachNamed` impls).
+pub trait Concat<Tail> {
+    type Output;
+
+    fn concat(self, tail: Tail) -> Self::Output;
+}
+
+impl<List> Concat<()> for List {
+    type Output = List;
+
+    fn concat(self, _: ()) -> List { self }
+}
+
+impl<List, Prev, I> Concat<(Prev, I)> for List where
+    List: Concat<Prev>
+{
+    type Output = (List::Output, I);
+
+    fn concat(self, tail: (Prev, I)) -> Self::Output {
+        (self.concat(tail.0), tail.1)
     }
 }
 
 
+/// A tuple of indices convertible into a path list.
+///
+/// Implemented for tuples of up to 8 indices: `(i, j, k)` becomes `((((), i), j), k)`.
+/// Used by [`at_path`](trait.Cps.html#method.at_path).
+pub trait TuplePath {
+    type List;
+
+    fn into_list(self) -> Self::List;
+}
+
+macro_rules! tuple_path {
+    ( $($t:ident $v:ident),+ ) => {
+        impl<$($t),+> TuplePath for ($($t,)+) {
+            type List = crate::path!($($t),+);
+
+            fn into_list(self) -> Self::List {
+                let ($($v,)+) = self;
+                let list = ();
+                $( let list = (list, $v); )+
+
+                list
+            }
+        }
+    };
+}
+
+tuple_path!(I1 i1);
+tuple_path!(I1 i1, I2 i2);
+tuple_path!(I1 i1, I2 i2, I3 i3);
+tuple_path!(I1 i1, I2 i2, I3 i3, I4 i4);
+tuple_path!(I1 i1, I2 i2, I3 i3, I4 i4, I5 i5);
+tuple_path!(I1 i1, I2 i2, I3 i3, I4 i4, I5 i5, I6 i6);
+tuple_path!(I1 i1, I2 i2, I3 i3, I4 i4, I5 i5, I6 i6, I7 i7);
+tuple_path!(I1 i1, I2 i2, I3 i3, I4 i4, I5 i5, I6 i6, I7 i7, I8 i8);
+
 
+/// A path list length known at compile time.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::{ Cps, PathLen };
+///
+/// fn len_of<List: PathLen>(_: &List) -> usize { List::LEN }
+///
+/// let mut foo = vec![vec![1]];
+/// let (_, list) = foo.at(0).at(0).into_parts();
+///
+/// assert!(len_of(&list) == 2);
+/// assert!(foo.at(0).path_len() == 1);
+/// ```
+pub trait PathLen {
+    const LEN: usize;
+}
+
+impl PathLen for () {
+    const LEN: usize = 0;
+}
+
+impl<Prev: PathLen, Index> PathLen for (Prev, Index) {
+    const LEN: usize = Prev::LEN + 1;
+}
+
+
+/// A helper trait walking the components of a path list.
+///
+/// Implemented for path lists with `Debug` indices. The components are
+/// visited from the root along with their positions.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::Cps;
+///
+/// let mut foo = vec![vec![1]];
+/// let mut components = vec![];
+///
+/// foo.at(0).at(0).visit_path(|i, index| components.push(format!("{}:{:?}", i, index)));
+///
+/// assert!(components == vec!["0:0", "1:0"]);
+/// ```
+pub trait VisitPath: PathLen {
+    fn visit<F: FnMut(usize, &dyn fmt::Debug)>(&self, f: &mut F);
+}
+
+impl VisitPath for () {
+    fn visit<F: FnMut(usize, &dyn fmt::Debug)>(&self, _: &mut F) {}
+}
+
+impl<Prev: VisitPath, Index: fmt::Debug> VisitPath for (Prev, Index) {
+    fn visit<F: FnMut(usize, &dyn fmt::Debug)>(&self, f: &mut F) {
+        self.0.visit(f);
+
+        f(Prev::LEN, &self.1)
+    }
+}
@@ -0,0 +1,69 @@
+use super::{ At, Cps };
+
+
+/// A `Cps` value trying several indices in turn.
+///
+/// Created by the [`at_any`](trait.Cps.html#method.at_any) method.
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct AtAny<CPS, Indices> {
+    cps: CPS,
+    indices: Indices,
+}
+
+pub fn new_at_any<CPS, Indices>(cps: CPS, indices: Indices) -> AtAny<CPS, Indices> {
+    AtAny { cps, indices }
+}
+
+/// `access` returns `None` if none of the indices resolves
+impl<CPS: Cps, Indices, V: ?Sized> Cps for AtAny<CPS, Indices> where
+    Indices: IntoIterator,
+    CPS::View: At<Indices::Item, View=V>
+{
+    type View = V;
+
+    fn access<R, F>(self, f: F) -> Option<R> where
+        F: FnOnce(&mut V) -> R
+    {
+        let indices = self.indices;
+
+        self.cps.access(|v| {
+            let mut f = Some(f);
+
+            for i in indices {
+                let result = v.access_at(i, |x| (f.take().unwrap())(x));
+
+                if result.is_some() { return result; }
+
+                // an index can run the closure and still fail (e.g. a failed write-back)
+                if f.is_none() { break; }
+            }
+
+            None
+        }).flatten()
+    }
+}
+
+
+#[test]#[cfg(feature="collections")]
+fn test_at_any() {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    let mut foo = vec![1, 2, 3];
+
+    assert!(foo.at_any(vec![5, 1, 0]).replace(4) == Some(2));
+    assert!(foo == vec![1, 4, 3]);
+
+    assert!(foo.at_any(vec![3, 4]).replace(5).is_none());
+    assert!(foo.at_any(Vec::<usize>::new()).touch().is_none());
+    assert!(foo == vec![1, 4, 3]);
+
+    let rejecting = || crate::core_impls::Proxy {
+        to: |x: &Vec<i32>| Some(x.len()),
+        from: |_: usize| None,
+    };
+
+    assert!(foo.at_any(vec![rejecting(), rejecting()]).replace(0).is_none());
+    assert!(foo == vec![1, 4, 3]);
+}
@@ -0,0 +1,48 @@
+use super::{ At, Cps };
+
+
+/// A `Cps` value choosing the index from the current view.
+///
+/// Created by the [`at_by`](trait.Cps.html#method.at_by) method.
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct AtBy<CPS, F> {
+    cps: CPS,
+    choose: F,
+}
+
+pub fn new_at_by<CPS, F>(cps: CPS, choose: F) -> AtBy<CPS, F> {
+    AtBy { cps, choose }
+}
+
+/// `access` returns `None` if the chosen index doesn't resolve
+impl<CPS: Cps, F, I, V: ?Sized> Cps for AtBy<CPS, F> where
+    F: FnOnce(&CPS::View) -> I,
+    CPS::View: At<I, View=V>
+{
+    type View = V;
+
+    fn access<R, G>(self, f: G) -> Option<R> where
+        G: FnOnce(&mut V) -> R
+    {
+        let choose = self.choose;
+
+        self.cps.access(|v| {
+            let i = choose(v);
+
+            v.access_at(i, f)
+        }).flatten()
+    }
+}
+
+
+#[test]#[cfg(feature="collections")]
+fn test_at_by() {
+    use alloc::vec;
+
+    let mut foo = vec![vec![1, 2], vec![3, 4]];
+
+    assert!(foo.at_by(|xs| xs.len() - 1).at_by(|xs| xs[0] - 2).replace(5) == Some(4));
+    assert!(foo.at(0).at_by(|xs| xs[1]).touch().is_none());
+    assert!(foo == vec![vec![1, 2], vec![3, 5]]);
+}
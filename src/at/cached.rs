@@ -0,0 +1,121 @@
+use super::{ Cps, Attach };
+
+
+/// An accessor resolving a path only once. __Requires `unsafe-cache`.__
+///
+/// On the first successful access the [detached path](trait.Attach.html)
+/// is walked and a raw pointer to the resolved place is remembered.
+/// Subsequent accesses use the pointer directly. If the path fails
+/// then nothing is cached and the next access tries again.
+///
+/// The root is exclusively borrowed for the whole lifetime of
+/// a `CachedAt`, thus the only way to modify the structure is through
+/// the accessed view itself (which doesn't invalidate the pointer to it)
+/// or by the [`restructure`](#method.restructure) method (which drops the cache).
+///
+/// ### Safety
+///
+/// Every step of the path must give access to a place __stored inside__
+/// the parent (like the `Vec` and map accessors do) and not to
+/// a temporary value constructed by the `At` implementation
+/// (like the `iter_mut` accessors or proxies do).
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::{ Cps, CachedAt, detached_at };
+/// use std::collections::BTreeMap;
+///
+/// let mut world = BTreeMap::new();
+/// world.insert("player", vec![0, 0]);
+///
+/// let path = detached_at(&"player").at(1);
+/// let mut y = unsafe { CachedAt::new(&mut world, path) };
+///
+/// for _ in 0..3 {
+///     y.access(|y| { *y += 1; });  // walks the path only once
+/// }
+///
+/// assert!(world[&"player"] == vec![0, 3]);
+/// ```
+pub struct CachedAt<'a, Root: ?Sized, Path, V: ?Sized> {
+    root: &'a mut Root,
+    path: Path,
+    cached: Option<*mut V>,
+}
+
+impl<'a, Root: ?Sized, Path, V: ?Sized> CachedAt<'a, Root, Path, V> where
+    Path: Attach<Root, View=V> + Clone
+{
+    /// Creates an accessor. The path is not resolved until the first access.
+    ///
+    /// ### Safety
+    ///
+    /// See the [type-level documentation](struct.CachedAt.html).
+    pub unsafe fn new(root: &'a mut Root, path: Path) -> Self {
+        CachedAt { root, path, cached: None }
+    }
+
+    /// Accesses the place resolving the path if needed.
+    pub fn access<R, F>(&mut self, f: F) -> Option<R> where
+        F: FnOnce(&mut V) -> R
+    {
+        let ptr = match self.cached {
+            Some(ptr) => ptr,
+            None => {
+                let ptr = (&mut *self.root).attach(self.path.clone())
+                    .access(|v| v as *mut V)?;
+
+                self.cached = Some(ptr);
+                ptr
+            }
+        };
+
+        // Safety: the root is exclusively borrowed by `self` and
+        // the place is not a temporary (guaranteed by the caller of `new`)
+        Some(f(unsafe { &mut *ptr }))
+    }
+
+    /// Checks if the path has been resolved.
+    pub fn is_cached(&self) -> bool {
+        self.cached.is_some()
+    }
+
+    /// Forgets the resolved place. The next access walks the path again.
+    pub fn invalidate(&mut self) {
+        self.cached = None;
+    }
+
+    /// Gives access to the whole root. The cache is invalidated.
+    pub fn restructure<R, F>(&mut self, f: F) -> R where
+        F: FnOnce(&mut Root) -> R
+    {
+        self.cached = None;
+
+        f(self.root)
+    }
+
+    /// Releases the root.
+    pub fn into_root(self) -> &'a mut Root {
+        self.root
+    }
+}
+
+
+#[test]#[cfg(feature="collections")]
+fn test_cached() {
+    use crate::detached_at;
+    use alloc::vec;
+
+    let mut foo = vec![vec![1], vec![]];
+    let mut cached = unsafe { CachedAt::new(&mut foo, detached_at(1).at(0)) };
+
+    assert!(cached.access(|x| *x).is_none());
+    assert!(!cached.is_cached());
+
+    cached.restructure(|v| v[1].push(2));
+    assert!(cached.access(|x| core::mem::replace(x, 3)) == Some(2));
+    assert!(cached.is_cached());
+
+    assert!(cached.into_root() == &vec![vec![1], vec![3]]);
+}
@@ -0,0 +1,174 @@
+use super::{ At, AT, Cps };
+
+
+/// A capability of reading a view.
+///
+/// Implemented for all `Cps` values and for [`ReadOnly`](struct.ReadOnly.html).
+pub trait ReadAccess {
+    type View: ?Sized;
+
+    /// Reads the view. Returns `None` if the view can't be accessed.
+    fn read<R, F>(self, f: F) -> Option<R> where
+        F: FnOnce(&Self::View) -> R;
+}
+
+/// A capability of mutating a view.
+///
+/// Implemented for all `Cps` values (including [`ReadWrite`](struct.ReadWrite.html))
+/// but __not__ for [`ReadOnly`](struct.ReadOnly.html).
+pub trait WriteAccess: ReadAccess {
+    /// Mutates the view. Returns `None` if the view can't be accessed.
+    fn write<R, F>(self, f: F) -> Option<R> where
+        F: FnOnce(&mut Self::View) -> R;
+}
+
+impl<T: Cps> ReadAccess for T {
+    type View = T::View;
+
+    fn read<R, F>(self, f: F) -> Option<R> where
+        F: FnOnce(&T::View) -> R
+    {
+        self.access(|v| f(v))
+    }
+}
+
+impl<T: Cps> WriteAccess for T {
+    fn write<R, F>(self, f: F) -> Option<R> where
+        F: FnOnce(&mut T::View) -> R
+    {
+        self.access(f)
+    }
+}
+
+
+/// An accessor which statically cannot mutate the view.
+///
+/// Unlike `Cps` values it gives access only through a shared reference
+/// but it still can be navigated by [`at`](#method.at). The wrapped `Cps`
+/// value can't be extracted.
+///
+/// Can be created either directly or by the
+/// [`read_only`](trait.Cps.html#method.read_only) method.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::{ Cps, ReadAccess, WriteAccess, ReadOnly, ReadWrite };
+///
+/// fn total<A: ReadAccess<View=Vec<i32>>>(a: A) -> Option<i32> {
+///     a.read(|xs| xs.iter().sum())
+/// }
+///
+/// fn reset<A: WriteAccess<View=Vec<i32>>>(a: A) -> Option<()> {
+///     a.write(|xs| xs.clear())
+/// }
+///
+/// let mut foo = vec![vec![1, 2], vec![3]];
+///
+/// assert!(total(foo.at(0).read_only()) == Some(3));
+/// assert!(foo.read_only().at(1).at(0).get_clone() == Some(3));
+///
+/// assert!(reset(ReadWrite::new(foo.at(0))) == Some(()));
+/// assert!(total(ReadWrite::new(foo.at(0))) == Some(0));
+/// ```
+///
+/// A read-only accessor can't be mutated:
+///
+/// ``` compile_fail
+/// use smart_access::{ Cps, WriteAccess };
+///
+/// let mut foo = vec![1, 2];
+/// foo.read_only().at(0).write(|x| { *x = 3; });
+/// ```
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct ReadOnly<CPS>(CPS);
+
+impl<CPS: Cps> ReadOnly<CPS> {
+    /// Restricts the accessor to reading.
+    pub fn new(cps: CPS) -> Self {
+        ReadOnly(cps)
+    }
+
+    /// Navigates further, keeping the restriction.
+    pub fn at<Index>(self, i: Index) -> ReadOnly<AT<CPS, ((), Index)>> where
+        CPS::View: At<Index>
+    {
+        ReadOnly(self.0.at(i))
+    }
+
+    /// Clones the view.
+    pub fn get_clone(self) -> Option<CPS::View> where
+        CPS::View: Clone
+    {
+        self.0.get_clone()
+    }
+}
+
+impl<CPS: Cps> ReadAccess for ReadOnly<CPS> {
+    type View = CPS::View;
+
+    fn read<R, F>(self, f: F) -> Option<R> where
+        F: FnOnce(&CPS::View) -> R
+    {
+        self.0.access(|v| f(v))
+    }
+}
+
+
+/// An accessor explicitly granting the right to mutate the view.
+///
+/// A `Cps` value itself has the same rights: the wrapper only documents
+/// the intent in signatures. It can be downgraded by
+/// [`read_only`](trait.Cps.html#method.read_only).
+///
+/// See [`ReadOnly`](struct.ReadOnly.html) for an example.
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct ReadWrite<CPS>(CPS);
+
+impl<CPS: Cps> ReadWrite<CPS> {
+    /// Wraps an accessor.
+    pub fn new(cps: CPS) -> Self {
+        ReadWrite(cps)
+    }
+
+    /// Navigates further, keeping the wrapper.
+    pub fn at<Index>(self, i: Index) -> ReadWrite<AT<CPS, ((), Index)>> where
+        CPS::View: At<Index>
+    {
+        ReadWrite(self.0.at(i))
+    }
+
+    /// Unwraps the accessor.
+    pub fn into_inner(self) -> CPS {
+        self.0
+    }
+}
+
+/// `access` returns `Some` / `None` according to the wrapped value
+impl<CPS: Cps> Cps for ReadWrite<CPS> {
+    type View = CPS::View;
+
+    fn access<R, F>(self, f: F) -> Option<R> where
+        F: FnOnce(&mut CPS::View) -> R
+    {
+        self.0.access(f)
+    }
+}
+
+
+#[test]
+fn test_capabilities() {
+    let mut foo = [1, 2];
+
+    assert!(foo[..].read_only().read(|xs| xs.len()) == Some(2));
+    assert!(foo[..].read_only().at(1).read(|x| *x) == Some(2));
+
+    let read_write = ReadWrite::new(&mut foo[..]).at(0);
+    assert!(read_write.read_only().get_clone() == Some(1));
+
+    assert!(ReadWrite::new(&mut foo[..]).at(0).write(|x| { *x = 3; }) == Some(()));
+    assert!(foo[..].read_only().at(5).read(|x| *x).is_none());
+    assert!(foo == [3, 2]);
+}
@@ -0,0 +1,91 @@
+use super::Cps;
+use alloc::vec::Vec;
+
+
+/// Convenience methods for collection views.
+///
+/// Implemented for all `Cps` values. The methods are thin wrappers
+/// around [`access`](trait.Cps.html#tymethod.access) and return `None`
+/// if the access fails. __Requires `alloc`.__
+///
+/// The methods are prefixed with `view_` because `&mut Vec<T>` is a `Cps`
+/// value too: the inherent `Vec` methods always take precedence in method
+/// calls, thus e.g. `v.len()` is `Vec::len` and `v.view_len()` is
+/// `CpsCollect::view_len` regardless of the trait being in scope.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::{ Cps, CpsCollect };
+///
+/// let mut table = vec![vec![1], vec![]];
+///
+/// assert!(table.at(0).view_push(2) == Some(()));
+/// assert!(table.at(1).view_extend(3..5) == Some(()));
+/// assert!(table.at(1).view_len() == Some(2));
+/// assert!(table.at(0).view_clear() == Some(()));
+/// assert!(table.at(2).view_push(0) == None);
+///
+/// assert!(table == vec![vec![], vec![3, 4]]);
+/// ```
+pub trait CpsCollect: Cps {
+    /// Appends an item to the vector.
+    fn view_push<T>(self, item: T) -> Option<()> where
+        Self: Cps<View=Vec<T>>
+    {
+        self.access(|v| Vec::push(v, item))
+    }
+
+    /// Extends the view with the contents of an iterator.
+    ///
+    /// Works for any view implementing `Extend` (e.g. `String` or `BTreeSet`).
+    fn view_extend<I>(self, iter: I) -> Option<()> where
+        I: IntoIterator,
+        Self::View: Extend<I::Item>
+    {
+        self.access(|v| Extend::extend(v, iter))
+    }
+
+    /// Removes all items of the vector.
+    fn view_clear<T>(self) -> Option<()> where
+        Self: Cps<View=Vec<T>>
+    {
+        self.access(|v| Vec::clear(v))
+    }
+
+    /// The number of items in the vector.
+    fn view_len<T>(self) -> Option<usize> where
+        Self: Cps<View=Vec<T>>
+    {
+        self.access(|v| Vec::len(v))
+    }
+
+    /// Checks if the vector is empty.
+    fn view_is_empty<T>(self) -> Option<bool> where
+        Self: Cps<View=Vec<T>>
+    {
+        self.access(|v| Vec::is_empty(v))
+    }
+}
+
+impl<T: Cps> CpsCollect for T {}
+
+
+#[cfg(feature="collections")]
+#[test]
+fn test_cps_collect() {
+    use alloc::string::String;
+    use alloc::vec;
+
+    let mut foo = vec![String::from("a")];
+
+    assert!(foo.at(0).view_extend(['b', 'c']) == Some(()));
+    assert!(foo.view_push(String::new()) == Some(()));
+    assert!(foo.view_len() == Some(2));
+    assert!(foo.len() == 2);
+    assert!(foo.at(2).view_extend(Some('d')).is_none());
+    assert!(foo == vec!["abc", ""]);
+
+    assert!(foo.view_clear() == Some(()));
+    assert!(foo.view_is_empty() == Some(true));
+}
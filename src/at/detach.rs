@@ -1,9 +1,17 @@
 use super::*;
 use core::marker::PhantomData;
+use core::hash::{ Hash, Hasher };
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct DetachedRoot<V: ?Sized>(PhantomData<*const V>);
 
+// a derived impl would require `V: Clone`
+impl<V: ?Sized> Clone for DetachedRoot<V> {
+    fn clone(&self) -> Self {
+        DetachedRoot::new()
+    }
+}
+
 impl<V: ?Sized> DetachedRoot<V> {
     pub fn new() -> Self {
         DetachedRoot(PhantomData)
@@ -34,6 +42,64 @@ impl<V: ?Sized> Cps for DetachedRoot<V> {
 /// passed to generically typed arguments of the function).
 pub type DetachedPath<View, List> = AT<DetachedRoot<View>, List>;
 
+/// Detached paths are equal if their indices are equal.
+impl<View: ?Sized, List: PartialEq> PartialEq for DetachedPath<View, List> {
+    fn eq(&self, other: &Self) -> bool {
+        self.list == other.list
+    }
+}
+
+impl<View: ?Sized, List: Eq> Eq for DetachedPath<View, List> {}
+
+impl<View: ?Sized, List: Hash> Hash for DetachedPath<View, List> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.list.hash(state)
+    }
+}
+
+/// Generates the indices. __Requires `arbitrary`.__
+#[cfg(feature="arbitrary")]
+impl<'a, View: ?Sized, List: arbitrary::Arbitrary<'a>> arbitrary::Arbitrary<'a> for DetachedPath<View, List> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(AT { cps: DetachedRoot::new(), list: u.arbitrary()? })
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        List::size_hint(depth)
+    }
+}
+
+
+/// An empty detached path. __Requires `detach` feature.__
+///
+/// A starting point for [field paths](derive.Fields.html):
+/// `EmptyPath::<Config>::new().server().port()`.
+///
+/// Can also be extended by ordinary [`at`](struct.AT.html#method.at) calls:
+///
+/// ```
+/// use smart_access::{ Cps, EmptyPath };
+///
+/// let path = EmptyPath::<Vec<Vec<i32>>>::new().at(1).at(0);
+/// let mut foo = vec![vec![1], vec![2]];
+///
+/// assert!(foo.attach(path).replace(3) == Some(2));
+/// ```
+pub type EmptyPath<Root> = DetachedPath<Root, ()>;
+
+impl<Root: ?Sized> AT<DetachedRoot<Root>, ()> {
+    /// Creates an empty detached path.
+    pub fn new() -> Self {
+        AT { cps: DetachedRoot::new(), list: () }
+    }
+}
+
+impl<Root: ?Sized> Default for AT<DetachedRoot<Root>, ()> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 
 /// A detached path. __Requires `detach` feature.__
 ///
@@ -62,3 +128,28 @@ impl<ToView: ?Sized, List> Attach<ToView> for DetachedPath<ToView, List> where
 }
 
 
+
+
+/// A shorthand for `Attach<Root, View=View>`. __Requires `detach` feature.__
+///
+/// Implemented for every detached path leading from `Root` to `View`.
+/// Allows to name &#8220;a way to reach a `View` inside a `Root`&#8221;
+/// with a single bound:
+///
+/// ```
+/// use smart_access::{ Cps, PathTo, detached_at };
+///
+/// fn replace_at<CPS: Cps, V>(cps: CPS, path: impl PathTo<CPS::View, V>, x: V) -> Option<V> {
+///     cps.attach(path).replace(x)
+/// }
+///
+/// let mut foo = vec![vec![1, 2]];
+///
+/// assert!(replace_at(&mut foo, detached_at(0).at(1), 3) == Some(2));
+/// assert!(foo == vec![vec![1, 3]]);
+/// ```
+pub trait PathTo<Root: ?Sized, View: ?Sized>: Attach<Root, View=View> {}
+
+impl<Root: ?Sized, View: ?Sized, P> PathTo<Root, View> for P where
+    P: Attach<Root, View=View>
+{}
@@ -0,0 +1,193 @@
+use super::{ At, Cps };
+use alloc::vec::Vec;
+use alloc::vec;
+use core::iter::FromIterator;
+
+
+/// A homogeneous runtime path. __Requires `dyn_path`.__
+///
+/// All the indices of a `DynPath<I>` have the same type `I` and are
+/// stored in a `Vec`. A `DynPath<I>` can be attached by the
+/// [`at_dyn`](trait.Cps.html#method.at_dyn) method to any `Cps`-bounded value
+/// with `View: At<I, View=View>`. The path is walked at runtime.
+///
+/// Compared to a chain of `.at(..)` calls it trades a little speed for
+/// dramatically smaller types: the type of a path doesn't depend on its length.
+///
+/// ### Usage example
+///
+/// Usually the index type is an enum covering all the ways to go
+/// one level deeper into a self-similar datatype:
+///
+/// ```
+/// use smart_access::{ At, Cps, DynPath };
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Json { Num(f64), Arr(Vec<Json>), Obj(Vec<(String, Json)>) }
+///
+/// #[derive(Debug, Clone)]
+/// enum Key { Idx(usize), Field(&'static str) }
+///
+/// impl At<Key> for Json {
+///     type View = Json;
+///
+///     fn access_at<R, F>(&mut self, key: Key, f: F) -> Option<R> where
+///         F: FnOnce(&mut Json) -> R
+///     {
+///         match (self, key) {
+///             (Json::Arr(xs), Key::Idx(i)) => xs.get_mut(i).map(f),
+///             (Json::Obj(fields), Key::Field(name)) => fields.iter_mut()
+///                 .find(|(k, _)| k == name)
+///                 .map(|(_, v)| f(v)),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let mut json = Json::Obj(vec![
+///     ("users".into(), Json::Arr(vec![
+///         Json::Obj(vec![ ("age".into(), Json::Num(42.)) ]),
+///     ])),
+/// ]);
+///
+/// let path = DynPath::new().at(Key::Field("users")).at(Key::Idx(0)).at(Key::Field("age"));
+/// assert!(path.len() == 3);
+///
+/// assert!(json.at_dyn(path.clone()).replace(Json::Num(43.)) == Some(Json::Num(42.)));
+/// assert!(json.at_dyn(path).at(Key::Idx(0)).touch() == None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature="serde", serde(transparent))]
+pub struct DynPath<I> {
+    list: Vec<I>,
+}
+
+impl<I> DynPath<I> {
+    /// Creates an empty path.
+    pub fn new() -> Self {
+        DynPath { list: Vec::new() }
+    }
+
+    /// Appends an index to the path (a builder-style version of [`push`](#method.push)).
+    pub fn at(mut self, i: I) -> Self {
+        self.list.push(i);
+
+        self
+    }
+
+    /// Appends an index to the path.
+    pub fn push(&mut self, i: I) {
+        self.list.push(i);
+    }
+
+    /// Removes the last index of the path.
+    pub fn pop(&mut self) -> Option<I> {
+        self.list.pop()
+    }
+
+    /// The number of indices in the path.
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Checks if the path is empty.
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// The indices of the path.
+    pub fn indices(&self) -> &[I] {
+        &self.list
+    }
+
+    /// Converts the path into a vector of indices.
+    pub fn into_vec(self) -> Vec<I> {
+        self.list
+    }
+}
+
+impl<I> Default for DynPath<I> {
+    fn default() -> Self {
+        DynPath::new()
+    }
+}
+
+impl<I> From<Vec<I>> for DynPath<I> {
+    fn from(list: Vec<I>) -> Self {
+        DynPath { list }
+    }
+}
+
+impl<I> FromIterator<I> for DynPath<I> {
+    fn from_iter<It: IntoIterator<Item=I>>(it: It) -> Self {
+        DynPath { list: it.into_iter().collect() }
+    }
+}
+
+impl<I> Extend<I> for DynPath<I> {
+    fn extend<It: IntoIterator<Item=I>>(&mut self, it: It) {
+        self.list.extend(it)
+    }
+}
+
+
+impl<I> IntoIterator for DynPath<I> {
+    type Item = I;
+    type IntoIter = vec::IntoIter<I>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.into_iter()
+    }
+}
+
+impl<'a, I> IntoIterator for &'a DynPath<I> {
+    type Item = &'a I;
+    type IntoIter = core::slice::Iter<'a, I>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.iter()
+    }
+}
+
+
+/// A `Cps` value walking a runtime path. __Requires `dyn_path`.__
+///
+/// Created by the [`at_dyn`](trait.Cps.html#method.at_dyn) method.
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct DynAT<CPS, Path> {
+    cps: CPS,
+    path: Path,
+}
+
+pub fn new_dyn_at<CPS, Path>(cps: CPS, path: Path) -> DynAT<CPS, Path> {
+    DynAT { cps, path }
+}
+
+/// `access` returns `None` if any step of the path fails
+impl<CPS: Cps, Path> Cps for DynAT<CPS, Path> where
+    Path: IntoIterator,
+    CPS::View: At<Path::Item, View=CPS::View>
+{
+    type View = CPS::View;
+
+    fn access<R, F>(self, f: F) -> Option<R> where
+        F: FnOnce(&mut Self::View) -> R
+    {
+        let rest = self.path.into_iter();
+
+        self.cps.access(|v| walk(v, rest, f)).flatten()
+    }
+}
+
+fn walk<V, It, R, F>(v: &mut V, mut rest: It, f: F) -> Option<R> where
+    V: At<It::Item, View=V> + ?Sized,
+    It: Iterator,
+    F: FnOnce(&mut V) -> R
+{
+    match rest.next() {
+        None    => Some(f(v)),
+        Some(i) => v.access_at(i, |w| walk(w, rest, f)).flatten(),
+    }
+}
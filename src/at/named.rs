@@ -0,0 +1,117 @@
+use super::{ AT, Cps, Concat };
+use super::detach::{ DetachedRoot, DetachedPath };
+use core::marker::PhantomData;
+
+
+/// A named detach point. __Requires `detach` feature.__
+///
+/// Created by the [`cut_named`](trait.Cps.html#method.cut_named) method.
+/// Forwards `access` to the wrapped `Cps` value.
+#[must_use]
+#[derive(Debug)]
+pub struct Named<CPS, M> {
+    cps: CPS,
+    marker: PhantomData<M>,
+}
+
+// a derived impl would require `M: Clone`
+impl<CPS: Clone, M> Clone for Named<CPS, M> {
+    fn clone(&self) -> Self {
+        Named { cps: self.cps.clone(), marker: PhantomData }
+    }
+}
+
+pub fn new_named<CPS, M>(cps: CPS) -> Named<CPS, M> {
+    Named { cps, marker: PhantomData }
+}
+
+/// `access` returns `Some` / `None` according to the wrapped `Cps` value
+impl<CPS: Cps, M> Cps for Named<CPS, M> {
+    type View = CPS::View;
+
+    fn access<R, F>(self, f: F) -> Option<R> where
+        F: FnOnce(&mut Self::View) -> R
+    {
+        self.cps.access(f)
+    }
+}
+
+
+// type-level positions of a named detach point
+pub struct Here;
+pub struct There<P>(PhantomData<P>);
+pub struct Skip<P>(PhantomData<P>);
+
+
+/// A helper trait for [`detach_named`](struct.AT.html#method.detach_named).
+///
+/// The `Pos` parameter is inferred: it encodes the position
+/// of the detach point named by `M`.
+pub trait DetachNamed<M, Pos> {
+    type Left;
+    type Path;
+
+    fn detach_named(self) -> (Self::Left, Self::Path);
+}
+
+impl<CPS: Cps, M, List> DetachNamed<M, Here> for AT<Named<CPS, M>, List> {
+    type Left = CPS;
+    type Path = DetachedPath<CPS::View, List>;
+
+    fn detach_named(self) -> (CPS, Self::Path) {
+        (self.cps.cps, AT { cps: DetachedRoot::new(), list: self.list })
+    }
+}
+
+impl<CPS, M, List, P, Root: ?Sized, Inner> DetachNamed<M, There<P>> for AT<CPS, List> where
+    CPS: DetachNamed<M, P, Path=DetachedPath<Root, Inner>>,
+    Inner: Concat<List>
+{
+    type Left = CPS::Left;
+    type Path = DetachedPath<Root, Inner::Output>;
+
+    fn detach_named(self) -> (Self::Left, Self::Path) {
+        let (left, inner) = self.cps.detach_named();
+
+        (left, AT { cps: DetachedRoot::new(), list: inner.list.concat(self.list) })
+    }
+}
+
+impl<CPS, M, Other, P> DetachNamed<M, Skip<P>> for Named<CPS, Other> where
+    CPS: DetachNamed<M, P>
+{
+    type Left = CPS::Left;
+    type Path = CPS::Path;
+
+    fn detach_named(self) -> (Self::Left, Self::Path) {
+        self.cps.detach_named()
+    }
+}
+
+
+#[cfg(feature="collections")]
+#[test]
+fn test_named_cuts() {
+    use alloc::vec;
+
+    struct Outer;
+    struct Inner;
+
+    let mut foo = vec![vec![vec![1]]];
+    let mut bar = vec![vec![vec![2]]];
+
+    let (left, path) = foo.at(0).cut_named(Outer).at(0).cut_named(Inner).at(0).detach_named(Outer);
+    assert!(bar[0].attach(path).replace(3) == Some(2));
+    assert!(left.at(0).at(0).replace(4) == Some(1));
+
+    let (left, path) = foo.at(0).cut_named(Outer).at(0).cut().at(0).cut_named(Inner).detach_named(Outer);
+    assert!(bar[0].attach(path).replace(5) == Some(3));
+    assert!(left.touch().is_some());
+
+    let (left, path) = foo.at(0).cut_named(Outer).at(0).cut_named(Inner).at(0).detach_named(Inner);
+    assert!(bar[0][0].attach(path).replace(6) == Some(5));
+    assert!(left.at(0).replace(7) == Some(4));
+
+    assert!(foo == vec![vec![vec![7]]]);
+    assert!(bar == vec![vec![vec![6]]]);
+}
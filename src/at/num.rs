@@ -0,0 +1,84 @@
+use super::Cps;
+use core::ops::{ Add, Sub };
+
+
+/// Read-modify-write helpers for numeric views.
+///
+/// Implemented for all `Cps` values. Every method returns the old value
+/// (or `None` if the access fails). The arithmetic follows the usual
+/// rules, e.g. overflows panic in debug builds.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::{ Cps, CpsNum };
+///
+/// let mut counters = vec![10, 20];
+///
+/// assert!(counters.at(0).fetch_add(5) == Some(10));
+/// assert!(counters.at(1).fetch_sub(5) == Some(20));
+/// assert!(counters.at(0).min_assign(12) == Some(15));
+/// assert!(counters.at(1).max_assign(30) == Some(15));
+///
+/// assert!(counters.at(0).compare_and_swap(12, 0) == Some(12));  // swapped
+/// assert!(counters.at(0).compare_and_swap(12, 1) == Some(0));   // not swapped
+/// assert!(counters.at(2).fetch_add(1) == None);
+///
+/// assert!(counters == vec![0, 30]);
+/// ```
+pub trait CpsNum: Cps {
+    /// Adds the value.
+    fn fetch_add(self, x: Self::View) -> Option<Self::View> where
+        Self::View: Copy + Add<Output=Self::View>
+    {
+        self.access(|v| { let old = *v; *v = old + x; old })
+    }
+
+    /// Subtracts the value.
+    fn fetch_sub(self, x: Self::View) -> Option<Self::View> where
+        Self::View: Copy + Sub<Output=Self::View>
+    {
+        self.access(|v| { let old = *v; *v = old - x; old })
+    }
+
+    /// Replaces the view with the value if the value is less.
+    fn min_assign(self, x: Self::View) -> Option<Self::View> where
+        Self::View: Copy + PartialOrd
+    {
+        self.access(|v| { let old = *v; if x < old { *v = x; } old })
+    }
+
+    /// Replaces the view with the value if the value is greater.
+    fn max_assign(self, x: Self::View) -> Option<Self::View> where
+        Self::View: Copy + PartialOrd
+    {
+        self.access(|v| { let old = *v; if x > old { *v = x; } old })
+    }
+
+    /// Replaces the view with `new` if it equals `expected`.
+    ///
+    /// The replacement has happened iff the returned value equals `expected`.
+    fn compare_and_swap(self, expected: Self::View, new: Self::View) -> Option<Self::View> where
+        Self::View: Copy + PartialEq
+    {
+        self.access(|v| { let old = *v; if old == expected { *v = new; } old })
+    }
+}
+
+impl<T: Cps> CpsNum for T {}
+
+
+#[test]
+fn test_cps_num() {
+    let mut foo = [1.5, f64::NAN];
+
+    assert!(foo[..].at(0).fetch_add(1.0) == Some(1.5));
+    assert!(foo[..].at(0).max_assign(f64::NAN) == Some(2.5));
+    assert!(foo[..].at(1).min_assign(0.0).map(f64::is_nan) == Some(true));
+    assert!(foo[..].at(1).compare_and_swap(f64::NAN, 0.0).map(f64::is_nan) == Some(true));
+    assert!(foo[0] == 2.5 && foo[1].is_nan());
+
+    let mut bar = None::<u8>;
+    assert!(bar.at(()).fetch_sub(1).is_none());
+    assert!(bar.at((0,)).fetch_sub(0) == Some(0));
+}
@@ -0,0 +1,119 @@
+use super::{ At, AT, Cps };
+
+
+/// An accessor reporting failures as a user-defined error.
+///
+/// Created by the [`ok_or`](trait.Cps.html#method.ok_or) method.
+/// Its methods mirror the methods of `Cps` but return `Result` instead of
+/// `Option`, thus they compose with `?` in functions having their own
+/// error types.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::Cps;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum ConfigError { Missing(&'static str) }
+///
+/// fn bump_port(config: &mut Vec<Vec<u16>>) -> Result<u16, ConfigError> {
+///     let old = config.at(0).at(1).ok_or(ConfigError::Missing("port")).replace(8080)?;
+///     let retries = config.at(1).ok_or_else(|| ConfigError::Missing("retries")).at(0).get_clone()?;
+///
+///     Ok(old + retries)
+/// }
+///
+/// assert!(bump_port(&mut vec![vec![1, 80], vec![3]]) == Ok(83));
+/// assert!(bump_port(&mut vec![vec![1]]) == Err(ConfigError::Missing("port")));
+/// assert!(bump_port(&mut vec![vec![1, 80]]) == Err(ConfigError::Missing("retries")));
+/// ```
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct OkOr<CPS, E> {
+    cps: CPS,
+    err: E,
+}
+
+/// A lazy version of [`OkOr`](struct.OkOr.html): the error is created only on failure.
+///
+/// Created by the [`ok_or_else`](trait.Cps.html#method.ok_or_else) method.
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct OkOrElse<CPS, G> {
+    cps: CPS,
+    err: G,
+}
+
+pub fn new_ok_or<CPS, E>(cps: CPS, err: E) -> OkOr<CPS, E> {
+    OkOr { cps, err }
+}
+
+pub fn new_ok_or_else<CPS, G>(cps: CPS, err: G) -> OkOrElse<CPS, G> {
+    OkOrElse { cps, err }
+}
+
+
+macro_rules! ok_or_methods {
+    ( [$($gen:tt)*] $name:ident<$cps:ident, $err:ident>, $error:ty, $make:expr ) => {
+        impl<$($gen)*> $name<$cps, $err> {
+            /// Accesses the view. Returns the error if the access fails.
+            pub fn access<R, F>(self, f: F) -> Result<R, $error> where
+                F: FnOnce(&mut $cps::View) -> R
+            {
+                let (cps, err, make) = (self.cps, self.err, $make);
+
+                cps.access(f).ok_or_else(|| make(err))
+            }
+
+            /// Navigates further, keeping the error.
+            pub fn at<Index>(self, i: Index) -> $name<AT<$cps, ((), Index)>, $err> where
+                $cps::View: At<Index>
+            {
+                $name { cps: self.cps.at(i), err: self.err }
+            }
+
+            /// Replaces the view. Returns the old one or the error.
+            pub fn replace(self, new_val: $cps::View) -> Result<$cps::View, $error> where
+                $cps::View: Sized
+            {
+                self.access(|x| core::mem::replace(x, new_val))
+            }
+
+            /// Clones the view. Returns the error if the access fails.
+            pub fn get_clone(self) -> Result<$cps::View, $error> where
+                $cps::View: Clone
+            {
+                self.access(|x| x.clone())
+            }
+
+            /// Checks that the access succeeds.
+            pub fn touch(self) -> Result<(), $error> {
+                self.access(|_| ())
+            }
+
+            /// Unwraps the accessor.
+            pub fn into_inner(self) -> $cps {
+                self.cps
+            }
+        }
+    };
+}
+
+ok_or_methods!([CPS: Cps, E] OkOr<CPS, E>, E, |e| e);
+ok_or_methods!([CPS: Cps, E, G: FnOnce() -> E] OkOrElse<CPS, G>, E, |g: G| g());
+
+
+#[test]
+fn test_ok_or() {
+    let mut foo = [1, 2];
+
+    assert!(foo[..].at(1).ok_or("missing").replace(3) == Ok(2));
+    assert!(foo[..].at(2).ok_or("missing").touch() == Err("missing"));
+    assert!(foo[..].ok_or(0).at(5).get_clone() == Err(0));
+
+    let mut calls = 0;
+    assert!(foo[..].at(0).ok_or_else(|| { calls += 1; "missing" }).get_clone() == Ok(1));
+    assert!(calls == 0);
+    assert!(foo[..].at(3).ok_or_else(|| { calls += 1; "missing" }).access(|x| *x) == Err("missing"));
+    assert!(calls == 1);
+}
@@ -0,0 +1,142 @@
+use super::{ At, Cps };
+
+#[cfg(feature="alloc")]
+use alloc::vec::Vec;
+
+
+/// A pair of mutable references to two distinct places. __Requires `unsafe-pair`.__
+///
+/// Used as the view of [`zip`](trait.Cps.html#method.zip)
+/// and of [traversal zipping](traversal/trait.Each.html#method.zip).
+/// The pair is unsized, thus it can't be moved out from
+/// behind a reference. The components are accessed by
+/// the [`fst`](#method.fst), [`snd`](#method.snd) and
+/// [`split`](#method.split) methods.
+///
+/// The pair is implemented by casting a slice of raw pointers.
+/// The safety relies on the pair being unable to escape the closure
+/// it is passed to.
+#[repr(transparent)]
+pub struct Pair<A: ?Sized, B: ?Sized> {
+    ptrs: [(*mut A, *mut B)],
+}
+
+impl<A: ?Sized, B: ?Sized> Pair<A, B> {
+    pub(crate) fn with_mut<R, F>(a: &mut A, b: &mut B, f: F) -> R where
+        F: FnOnce(&mut Pair<A, B>) -> R
+    {
+        let mut ptrs = [(a as *mut A, b as *mut B)];
+        let slice = &mut ptrs[..] as *mut [(*mut A, *mut B)];
+
+        // Safety: the layout is the same due to repr(transparent),
+        // the pointers are valid for the whole call of `f`
+        // and the pair can't escape `f` (it is unsized and has
+        // no public constructors)
+        f(unsafe { &mut *(slice as *mut Pair<A, B>) })
+    }
+
+    /// The first component.
+    pub fn fst(&mut self) -> &mut A {
+        unsafe { &mut *self.ptrs[0].0 }
+    }
+
+    /// The second component.
+    pub fn snd(&mut self) -> &mut B {
+        unsafe { &mut *self.ptrs[0].1 }
+    }
+
+    /// Both components at once.
+    pub fn split(&mut self) -> (&mut A, &mut B) {
+        let (a, b) = self.ptrs[0];
+
+        unsafe { (&mut *a, &mut *b) }
+    }
+}
+
+
+/// A `Cps` value accessing two places at once.
+///
+/// Created by the [`zip`](trait.Cps.html#method.zip) method.
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct Zip<L, R> {
+    left: L,
+    right: R,
+}
+
+pub fn new_zip<L, R>(left: L, right: R) -> Zip<L, R> {
+    Zip { left, right }
+}
+
+/// `access` returns `None` if either of the sides fails
+impl<L: Cps, R: Cps> Cps for Zip<L, R> {
+    type View = Pair<L::View, R::View>;
+
+    fn access<T, F>(self, f: F) -> Option<T> where
+        F: FnOnce(&mut Self::View) -> T
+    {
+        let right = self.right;
+
+        self.left.access(|a| right.access(|b| Pair::with_mut(a, b, f))).flatten()
+    }
+}
+
+
+/// `access_at` returns `None` if the lengths differ or the index is out of bounds
+///
+/// Thus two parallel columns can be edited as a single row:
+///
+/// ```
+/// use smart_access::Cps;
+///
+/// let mut positions = vec![0, 10];
+/// let mut velocities = vec![1, -1];
+///
+/// let columns = (&mut positions[..]).zip(&mut velocities[..]);
+///
+/// assert!(columns.at(1).access(|row| { let (x, v) = row.split(); *x += *v; }) == Some(()));
+/// assert!(positions == vec![0, 9]);
+/// ```
+impl<A, B> At<usize> for Pair<[A], [B]> {
+    type View = Pair<A, B>;
+
+    fn access_at<R, F>(&mut self, i: usize, f: F) -> Option<R> where
+        F: FnOnce(&mut Pair<A, B>) -> R
+    {
+        let (a, b) = self.split();
+        if a.len() != b.len() { return None; }
+
+        Some(Pair::with_mut(a.get_mut(i)?, b.get_mut(i)?, f))
+    }
+}
+
+/// `access_at` returns `None` if the lengths differ or the index is out of bounds
+#[cfg(feature="alloc")]
+impl<A, B> At<usize> for Pair<Vec<A>, Vec<B>> {
+    type View = Pair<A, B>;
+
+    fn access_at<R, F>(&mut self, i: usize, f: F) -> Option<R> where
+        F: FnOnce(&mut Pair<A, B>) -> R
+    {
+        let (a, b) = self.split();
+
+        Pair::with_mut(&mut a[..], &mut b[..], |p| p.access_at(i, f))
+    }
+}
+
+#[test]
+fn test_zip() {
+    let mut foo = [1, 2];
+    let mut bar = Some(3);
+
+    let (x, y) = foo.split_at_mut(1);
+
+    assert!(x.at(0).zip(y.at(0)).access(|p| {
+        let (a, b) = p.split();
+        core::mem::swap(a, b);
+    }) == Some(()));
+    assert!(foo == [2, 1]);
+
+    assert!(foo[..].at(5).zip(&mut bar).access(|p| { *p.snd() = None; }).is_none());
+    assert!(bar.at(()).zip(&mut foo[..]).access(|p| p.split().1.len()) == Some(2));
+}
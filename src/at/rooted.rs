@@ -0,0 +1,229 @@
+use super::{ At, Cps, DynPath, DynAT, new_dyn_at };
+#[cfg(feature="unsafe-cache")]
+use alloc::vec::Vec;
+
+
+/// A root with a runtime path built step by step. __Requires `dyn_path`.__
+///
+/// Holds a mutable reference to the root and a [runtime path](struct.DynPath.html)
+/// which can be changed by the [`descend`](#method.descend) and
+/// [`ascend`](#method.ascend) methods. Changing the path doesn't touch
+/// the root: the indices are checked only by an access.
+///
+/// A path created by [`new`](#method.new) keeps no references into the
+/// structure between accesses, thus every access walks the path from
+/// the root (cloning the indices). A path created by [`new_cached`](#method.new_cached)
+/// (__requires `unsafe-cache`__) remembers the resolved places and walks only
+/// the indices appended since the last access.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::{ At, Cps, RootedPath };
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Node { value: i32, children: Vec<Node> }
+///
+/// impl At<usize> for Node {
+///     type View = Node;
+///
+///     fn access_at<R, F>(&mut self, i: usize, f: F) -> Option<R> where
+///         F: FnOnce(&mut Node) -> R
+///     {
+///         self.children.get_mut(i).map(f)
+///     }
+/// }
+///
+/// let leaf = |value| Node { value, children: vec![] };
+/// let mut tree = Node { value: 1, children: vec![ leaf(2), leaf(3) ] };
+///
+/// let mut path = RootedPath::new(&mut tree);
+///
+/// path.descend(1);
+/// assert!(path.access(|node| { node.value = 4; }) == Some(()));
+///
+/// path.descend(0);  // a leaf has no children
+/// assert!(path.access(|_| ()) == None);
+/// assert!(path.depth() == 2);
+///
+/// assert!(path.ascend() == Some(0));
+/// assert!(path.ascend() == Some(1));
+/// path.descend(0);
+///
+/// assert!(path.into_cps().access(|node| node.value) == Some(2));
+/// assert!(tree.children[1].value == 4);
+/// ```
+#[derive(Debug)]
+pub struct RootedPath<'a, T: ?Sized, I> {
+    root: &'a mut T,
+    path: DynPath<I>,
+    #[cfg(feature="unsafe-cache")]
+    cache: Option<Vec<*mut T>>,
+}
+
+impl<'a, T: ?Sized, I> RootedPath<'a, T, I> where
+    T: At<I, View=T>,
+    I: Clone
+{
+    /// Creates an empty path.
+    pub fn new(root: &'a mut T) -> Self {
+        RootedPath {
+            root,
+            path: DynPath::new(),
+            #[cfg(feature="unsafe-cache")]
+            cache: None,
+        }
+    }
+
+    /// Creates an empty path reusing the resolved places. __Requires `unsafe-cache`.__
+    ///
+    /// Every access remembers a raw pointer to each place on the path.
+    /// The next access starts from the deepest remembered place, thus only
+    /// the indices appended since the last access are walked. A failed
+    /// index is walked again by the next access.
+    ///
+    /// ### Safety
+    ///
+    /// The same as for [`CachedAt`](struct.CachedAt.html): every step must
+    /// give access to a place __stored inside__ the parent and not to
+    /// a temporary value constructed by the `At` implementation.
+    #[cfg(feature="unsafe-cache")]
+    pub unsafe fn new_cached(root: &'a mut T) -> Self {
+        RootedPath { root, path: DynPath::new(), cache: Some(Vec::new()) }
+    }
+
+    /// Appends an index. The root is not accessed.
+    pub fn descend(&mut self, i: I) {
+        self.path.push(i);
+    }
+
+    /// Removes the last index.
+    pub fn ascend(&mut self) -> Option<I> {
+        let i = self.path.pop();
+
+        #[cfg(feature="unsafe-cache")] {
+            if let Some(cache) = &mut self.cache { cache.truncate(self.path.len()); }
+        }
+
+        i
+    }
+
+    /// The number of indices.
+    pub fn depth(&self) -> usize {
+        self.path.len()
+    }
+
+    /// The indices from the root.
+    pub fn path(&self) -> &DynPath<I> {
+        &self.path
+    }
+
+    /// Walks the path from the root and accesses the resulting place.
+    ///
+    /// Returns `None` if the path doesn't resolve.
+    pub fn access<R, F>(&mut self, f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        #[cfg(feature="unsafe-cache")] {
+            if let Some(cache) = &mut self.cache {
+                let mut place = match cache.last() {
+                    Some(&place) => place,
+                    None => &mut *self.root as *mut T,
+                };
+
+                for i in self.path.indices()[cache.len()..].iter().cloned() {
+                    // Safety: the root is exclusively borrowed by `self`, the places
+                    // on the path are not temporaries (guaranteed by the caller
+                    // of `new_cached`) and the accessing closure can't reach them
+                    place = unsafe { &mut *place }.access_at(i, |v| v as *mut T)?;
+                    cache.push(place);
+                }
+
+                return Some(f(unsafe { &mut *place }));
+            }
+        }
+
+        (&mut *self.root).at_iter(self.path.indices().iter().cloned()).access(f)
+    }
+
+    /// Converts the path into a `Cps` value.
+    pub fn into_cps(self) -> DynAT<&'a mut T, DynPath<I>> {
+        new_dyn_at(self.root, self.path)
+    }
+}
+
+
+#[test]
+fn test_rooted_path() {
+    use alloc::vec::Vec;
+
+    // a tree growing on access
+    #[derive(Default)]
+    struct Node { children: Vec<Node> }
+
+    impl At<usize> for Node {
+        type View = Node;
+
+        fn access_at<R, F>(&mut self, i: usize, f: F) -> Option<R> where
+            F: FnOnce(&mut Node) -> R
+        {
+            if i == self.children.len() { self.children.push(Node::default()); }
+
+            self.children.get_mut(i).map(f)
+        }
+    }
+
+    let mut tree = Node::default();
+    let mut path = RootedPath::new(&mut tree);
+
+    path.descend(0);
+    path.descend(1);
+    assert!(path.path().indices() == [0, 1]);
+
+    path.ascend();
+    path.descend(0);
+    assert!(path.access(|node| node.children.len()) == Some(0));
+    assert!(tree.children.len() == 1 && tree.children[0].children.len() == 1);
+}
+
+#[test]#[cfg(feature="unsafe-cache")]
+fn test_cached_rooted_path() {
+    use alloc::vec;
+
+    // a tree counting the accesses to the children
+    #[derive(Default)]
+    struct Node { visits: usize, children: Vec<Node> }
+
+    impl At<usize> for Node {
+        type View = Node;
+
+        fn access_at<R, F>(&mut self, i: usize, f: F) -> Option<R> where
+            F: FnOnce(&mut Node) -> R
+        {
+            self.visits += 1;
+
+            self.children.get_mut(i).map(f)
+        }
+    }
+
+    let leaf = || Node::default();
+    let mut tree = Node { visits: 0, children: vec![ Node { visits: 0, children: vec![leaf(), leaf()] } ] };
+    let mut path = unsafe { RootedPath::new_cached(&mut tree) };
+
+    path.descend(0);
+    path.descend(1);
+    assert!(path.access(|node| node.visits) == Some(0));
+    assert!(path.access(|node| { node.visits = 5; }) == Some(()));
+
+    path.descend(0);
+    assert!(path.access(|_| ()).is_none());
+
+    path.ascend();
+    path.ascend();
+    path.descend(0);
+    assert!(path.access(|node| node.children.len()) == Some(0));
+
+    assert!(tree.visits == 1);
+    assert!(tree.children[0].visits == 2);
+    assert!(tree.children[0].children[1].visits == 6);  // 5 and a failed step
+}
@@ -0,0 +1,78 @@
+use super::Cps;
+use alloc::string::String;
+use core::ops::RangeBounds;
+
+
+/// Convenience methods for `String` views.
+///
+/// Implemented for all `Cps` values. The methods are thin wrappers
+/// around [`access`](trait.Cps.html#tymethod.access) and return `None`
+/// if the access fails. The methods panic in the same cases as their
+/// `String` counterparts (e.g. if a position is not on a char boundary).
+/// __Requires `alloc`.__
+///
+/// The methods are prefixed with `view_` because `&mut String` is a `Cps`
+/// value too: the inherent `String` methods always take precedence in method
+/// calls, thus e.g. `s.truncate(1)` is `String::truncate` and
+/// `s.view_truncate(1)` is `CpsString::view_truncate` regardless of the trait
+/// being in scope.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::{ Cps, CpsString };
+///
+/// let mut names = vec![String::from("Alice"), String::from("Bob")];
+///
+/// assert!(names.at(0).view_push_str(" Smith") == Some(()));
+/// assert!(names.at(1).view_insert_str(0, "Mr. ") == Some(()));
+/// assert!(names.at(1).view_replace_range(4.., "Robert") == Some(()));
+/// assert!(names.at(0).view_truncate(3) == Some(()));
+/// assert!(names.at(2).view_push_str("!") == None);
+///
+/// assert!(names == vec!["Ali", "Mr. Robert"]);
+/// ```
+pub trait CpsString: Cps<View=String> {
+    /// Appends a string slice.
+    fn view_push_str(self, s: &str) -> Option<()> {
+        self.access(|v| String::push_str(v, s))
+    }
+
+    /// Shortens the string to the length (in bytes).
+    ///
+    /// Does nothing if the length is greater than the current one.
+    fn view_truncate(self, len: usize) -> Option<()> {
+        self.access(|v| String::truncate(v, len))
+    }
+
+    /// Inserts a string slice at the byte position.
+    fn view_insert_str(self, i: usize, s: &str) -> Option<()> {
+        self.access(|v| String::insert_str(v, i, s))
+    }
+
+    /// Replaces the byte range with a string slice.
+    fn view_replace_range<R: RangeBounds<usize>>(self, range: R, s: &str) -> Option<()> {
+        self.access(|v| String::replace_range(v, range, s))
+    }
+}
+
+impl<T: Cps<View=String>> CpsString for T {}
+
+
+#[test]
+fn test_cps_string() {
+    let mut foo = Some(String::from("hello"));
+
+    assert!(foo.at(()).view_replace_range(..1, "J") == Some(()));
+    assert!(foo.at(()).view_truncate(10) == Some(()));
+    assert!(foo.at(()).view_insert_str(5, ", world") == Some(()));
+    assert!(foo.as_deref() == Some("Jello, world"));
+
+    foo = None;
+    assert!(foo.at(()).view_push_str("!").is_none());
+
+    let mut bar = String::from("ab");
+    assert!(bar.view_push_str("cd") == Some(()));
+    bar.truncate(3);
+    assert!(bar == "abc");
+}
@@ -35,12 +35,111 @@
 //! 
 //! assert!(foo == vec![vec![6, 3], vec![6, 5]]);
 //! ```
+//!
+//! With the `unsafe-pair` feature enabled maps can also be traversed along with
+//! their keys: see [`Entries`](struct.Entries.html).
+//! Nested containers can be traversed down to the innermost items:
+//! see [`Flatten`](struct.Flatten.html). A given list of indices can be traversed
+//! by [`KeyList`](struct.KeyList.html), a key range of a map by
+//! [`KeyRange`](struct.KeyRange.html), and double-ended containers can be
+//! traversed from the back by [`Rev`](struct.Rev.html). Slices can be
+//! traversed by blocks: see [`ChunksOf`](struct.ChunksOf.html),
+//! [`RChunksOf`](struct.RChunksOf.html) and the typed
+//! [`ArrayChunks`](struct.ArrayChunks.html). With the `unsafe-pair` feature
+//! enabled two parallel columns (zipped into a [`Pair`](../struct.Pair.html))
+//! can be traversed row-wise by [`Rows`](struct.Rows.html).
+//!
+//! The number of visited items can be bounded by [`take`](trait.Each.html#method.take)
+//! and [`skip`](trait.Each.html#method.skip). Two traversals can be
+//! combined by [`chain`](trait.Each.html#method.chain), and (with the `unsafe-pair`
+//! feature enabled) the items can be paired with the items of a mutating
+//! iterator by [`zip`](trait.Each.html#method.zip).
 
 use crate::AT;
 
 mod internal;
 use internal::OfView;
 
+#[cfg(feature="unsafe-pair")]
+mod entries; // key-value traversals of maps
+
+#[cfg(feature="unsafe-pair")]
+pub use entries::{ Entries, KeyValue };
+
+mod flatten; // traversals of nested containers
+pub use flatten::{ Container, Flatten };
+
+mod key_list; // traversals of explicitly listed indices
+pub use key_list::{ KeyList };
+
+mod rev; // reverse-order traversals
+pub use rev::{ Rev };
+
+#[cfg(feature="collections")]
+mod key_range; // traversals of key ranges of maps
+
+#[cfg(feature="collections")]
+pub use key_range::{ KeyRange };
+
+mod all_fields; // traversals of homogeneous struct fields
+pub use all_fields::{ AllFields };
+
+/// Derives a traversal of all fields of a struct. __Requires `derive`.__
+///
+/// All fields must have the same type (compared syntactically).
+/// Both named and tuple structs are supported. The derive generates
+/// `Of<AllFields, View=FieldType>` visiting the fields in the order of declaration:
+///
+/// ```
+/// use smart_access::Cps;
+/// use smart_access::traversal::{ Each, AllFields };
+///
+/// #[derive(AllFields)]
+/// struct Point { x: f64, y: f64, z: f64 }
+///
+/// #[derive(AllFields)]
+/// struct Seasons<T>(T, T, T, T);
+///
+/// let mut p = Point { x: 1.0, y: -2.0, z: 3.0 };
+/// p.of(AllFields).each(|c| { *c *= 2.0; true });
+/// assert!((p.x, p.y, p.z) == (2.0, -4.0, 6.0));
+///
+/// let mut stats = vec![ Seasons(1, 2, 3, 4) ];
+/// let mut total = 0;
+/// stats.at(0).access(|s| s.of(AllFields).each(|x| { total += *x; true }));
+/// assert!(total == 10);
+/// ```
+///
+/// Fields of different types are rejected:
+///
+/// ``` compile_fail
+/// use smart_access::traversal::AllFields;
+///
+/// #[derive(AllFields)]
+/// struct Mixed { a: u8, b: u16 }
+/// ```
+#[cfg(feature="derive")]
+pub use smart_access_derive::AllFields;
+
+mod chunks; // traversals of fixed-size blocks
+pub use chunks::{ ChunksOf, RChunksOf, ArrayChunks };
+
+#[cfg(feature="unsafe-pair")]
+mod rows; // traversals of parallel columns
+
+#[cfg(feature="unsafe-pair")]
+pub use rows::{ Rows };
+
+mod adapters; // adapters of Each-bound values
+use adapters::{ new_take, new_skip, new_chain };
+pub use adapters::{ Take, Skip, Chain };
+
+#[cfg(feature="unsafe-pair")]
+use adapters::{ new_zip };
+
+#[cfg(feature="unsafe-pair")]
+pub use adapters::{ Zip };
+
 
 
 /// An analogue of the [`At`](../trait.At.html) trait.
@@ -91,6 +190,82 @@ pub trait Each: Sized {
     {
         AT { cps: self, list: ((), i) } 
     }
+
+    /// Visits at most `n` items.
+    ///
+    /// The items of nested traversals are counted at the innermost level.
+    fn take(self, n: usize) -> Take<Self> {
+        new_take(self, n)
+    }
+
+    /// Skips the first `n` items.
+    ///
+    /// The items of nested traversals are counted at the innermost level.
+    fn skip(self, n: usize) -> Skip<Self> {
+        new_skip(self, n)
+    }
+
+    /// Visits the items of `self` then the items of `other`.
+    fn chain<Other>(self, other: Other) -> Chain<Self, Other> where
+        Other: Each<View=Self::View>
+    {
+        new_chain(self, other)
+    }
+
+    #[cfg(feature="unsafe-pair")]
+    /// Pairs the items with the items of an iterator (e.g. `vec.iter_mut()`).
+    ///
+    /// The view is a [`Pair`](../struct.Pair.html) of mutable references.
+    /// The traversal stops when either of the sides is exhausted.
+    ///
+    /// The other side is an iterator and not a traversal: a traversal gives
+    /// access to its items only inside its own closure, thus two traversals
+    /// can't be advanced in lockstep.
+    ///
+    /// _Present only on `unsafe-pair`._
+    fn zip<'a, I, B>(self, other: I) -> Zip<Self, I::IntoIter> where
+        I: IntoIterator<Item=&'a mut B>,
+        B: ?Sized + 'a
+    {
+        new_zip(self, other.into_iter())
+    }
+
+    /// The index of the first visited item satisfying the predicate.
+    ///
+    /// The items are not mutated. The items of nested traversals
+    /// are counted at the innermost level.
+    ///
+    /// ```
+    /// use smart_access::Cps;
+    /// use smart_access::traversal::{ Each, Rev };
+    ///
+    /// let mut foo = vec![3, 8, 1, 9];
+    ///
+    /// assert!(foo.iter_mut().of(()).position(|x| *x > 5) == Some(1));
+    /// assert!(foo.of(Rev).position(|x| *x > 5) == Some(0));
+    /// assert!(foo.iter_mut().of(()).position(|x| *x > 10) == None);
+    ///
+    /// // a positional follow-up
+    /// let i = foo.iter_mut().of(()).position(|x| *x == 1).unwrap();
+    /// foo.at(i).replace(0);
+    /// assert!(foo == vec![3, 8, 0, 9]);
+    /// ```
+    fn position<P>(self, mut pred: P) -> Option<usize> where
+        P: FnMut(&Self::View) -> bool
+    {
+        let mut index = 0;
+        let mut found = None;
+
+        self.each(|v| {
+            if found.is_some() { return false; }
+            if pred(v) { found = Some(index); return false; }
+
+            index += 1;
+            true
+        });
+
+        found
+    }
 }
 
 
@@ -118,3 +293,13 @@ impl<T: ?Sized> Each for &mut T {
 }
 
 
+
+
+#[test]
+fn test_position() {
+    let mut foo = [1, 2, 3, 4];
+
+    assert!(foo[..].of(ChunksOf(2)).position(|xs| xs[0] == 3) == Some(1));
+    assert!(foo[..].of(ChunksOf(2)).of(Rev).position(|x| *x == 3) == Some(3));
+    assert!(foo[..].of(ChunksOf(2)).of(Rev).skip(1).position(|x| *x == 2).is_none());
+}
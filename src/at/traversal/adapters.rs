@@ -0,0 +1,280 @@
+use super::Each;
+
+#[cfg(feature="unsafe-pair")]
+use crate::at::Pair;
+
+
+/// A traversal visiting at most `n` items of another one.
+///
+/// Created by [`Each::take`](trait.Each.html#method.take).
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::traversal::Each;
+/// use std::collections::BTreeMap;
+///
+/// let mut scores = BTreeMap::new();
+/// for (i, name) in ["a", "b", "c", "d"].iter().enumerate() { scores.insert(*name, i); }
+///
+/// // the first two values starting from "b"
+/// scores.range_mut("b"..).map(|(_, v)| v).of(()).take(2).each(|x| { *x *= 10; true });
+///
+/// assert!(scores.values().copied().collect::<Vec<_>>() == vec![0, 10, 20, 3]);
+/// ```
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct Take<E> {
+    inner: E,
+    n: usize,
+}
+
+impl<E: Each> Each for Take<E> {
+    type View = E::View;
+
+    fn each<F>(self, mut f: F) -> bool where
+        F: FnMut(&mut Self::View) -> bool
+    {
+        let mut remaining = self.n;
+
+        // a nested traversal can't be stopped from within,
+        // thus the excess items are only passed through
+        self.inner.each(|v| {
+            if remaining == 0 { return false; }
+            remaining -= 1;
+
+            f(v) && remaining > 0
+        })
+    }
+}
+
+
+/// A traversal skipping the first `n` items of another one.
+///
+/// Created by [`Each::skip`](trait.Each.html#method.skip).
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::traversal::{ Each, Rev };
+///
+/// let mut foo = vec![vec![1, 2], vec![3, 4]];
+///
+/// // nested traversals are counted by their innermost items: 2, 1, 4, 3
+/// foo.iter_mut().of(()).of(Rev).skip(1).take(2).each(|x| { *x = 0; true });
+///
+/// assert!(foo == vec![vec![0, 2], vec![3, 0]]);
+/// ```
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct Skip<E> {
+    inner: E,
+    n: usize,
+}
+
+impl<E: Each> Each for Skip<E> {
+    type View = E::View;
+
+    fn each<F>(self, mut f: F) -> bool where
+        F: FnMut(&mut Self::View) -> bool
+    {
+        let mut to_skip = self.n;
+
+        self.inner.each(|v| {
+            if to_skip > 0 { to_skip -= 1; return true; }
+
+            f(v)
+        })
+    }
+}
+
+
+/// A traversal visiting the items of one traversal then the items of another one.
+///
+/// Created by [`Each::chain`](trait.Each.html#method.chain).
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::traversal::Each;
+/// use std::collections::BTreeMap;
+///
+/// let mut limits = BTreeMap::new();
+/// limits.insert("cpu", 4);
+/// limits.insert("memory", 8);
+///
+/// let mut fallback = vec![1, 2];
+///
+/// limits.values_mut().of(())
+///     .chain(fallback.iter_mut().of(()))
+///     .each(|x| { *x *= 2; true });
+///
+/// assert!(limits["cpu"] == 8 && limits["memory"] == 16);
+/// assert!(fallback == vec![2, 4]);
+/// ```
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct Chain<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> Each for Chain<A, B> where
+    A: Each,
+    B: Each<View=A::View>
+{
+    type View = A::View;
+
+    fn each<F>(self, mut f: F) -> bool where
+        F: FnMut(&mut Self::View) -> bool
+    {
+        let mut stopped = false;
+
+        let result = self.first.each(|v| {
+            if stopped { return false; }
+            stopped = !f(v);
+
+            !stopped
+        });
+
+        if stopped { return result; }
+
+        self.second.each(f)
+    }
+}
+
+
+/// A traversal visiting the items of another one paired with the items of an iterator.
+/// __Requires `unsafe-pair`.__
+///
+/// Created by [`Each::zip`](trait.Each.html#method.zip). The view is
+/// a [`Pair`](../struct.Pair.html). The traversal stops when
+/// the iterator is exhausted.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::traversal::{ Each, Rev };
+///
+/// let mut foo = vec![1, 2, 3];
+/// let mut bar = vec![10, 20, 30];
+///
+/// foo.iter_mut().of(()).zip(&mut bar).each(|p| {
+///     let (a, b) = p.split();
+///     *a += *b;
+///     true
+/// });
+///
+/// assert!(foo == vec![11, 22, 33]);
+///
+/// // pairs the last item with the first one and so on
+/// foo.of(Rev).zip(bar.iter_mut().take(2)).each(|p| { *p.snd() = *p.fst(); true });
+///
+/// assert!(bar == vec![33, 22, 30]);
+/// ```
+#[cfg(feature="unsafe-pair")]
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct Zip<E, I> {
+    inner: E,
+    iter: I,
+}
+
+#[cfg(feature="unsafe-pair")]
+impl<'a, E, I, B> Each for Zip<E, I> where
+    E: Each,
+    I: Iterator<Item=&'a mut B>,
+    B: ?Sized + 'a
+{
+    type View = Pair<E::View, B>;
+
+    fn each<F>(self, mut f: F) -> bool where
+        F: FnMut(&mut Self::View) -> bool
+    {
+        let mut iter = self.iter;
+        let mut exhausted = false;
+
+        self.inner.each(|a| {
+            if exhausted { return false; }
+
+            match iter.next() {
+                Some(b) => Pair::with_mut(a, b, &mut f),
+                None    => { exhausted = true; false }
+            }
+        })
+    }
+}
+
+
+// Helpers for the Each trait.
+pub fn new_take<E: Each>(inner: E, n: usize) -> Take<E> {
+    Take { inner, n }
+}
+
+pub fn new_skip<E: Each>(inner: E, n: usize) -> Skip<E> {
+    Skip { inner, n }
+}
+
+pub fn new_chain<A: Each, B: Each>(first: A, second: B) -> Chain<A, B> {
+    Chain { first, second }
+}
+
+#[cfg(feature="unsafe-pair")]
+pub fn new_zip<E: Each, I: Iterator>(inner: E, iter: I) -> Zip<E, I> {
+    Zip { inner, iter }
+}
+
+
+#[test]
+fn test_take_skip() {
+    let mut foo = [1, 2, 3, 4, 5];
+    let mut visited = 0;
+
+    foo.iter_mut().of(()).skip(1).take(3).each(|x| { *x = 0; visited += 1; true });
+    assert!(foo == [1, 0, 0, 0, 5]);
+    assert!(visited == 3);
+
+    foo.iter_mut().of(()).take(0).each(|x| { *x = 7; true });
+    foo.iter_mut().of(()).skip(5).each(|x| { *x = 7; true });
+    assert!(foo == [1, 0, 0, 0, 5]);
+
+    // stopping early
+    foo.iter_mut().of(()).take(4).each(|x| { *x += 1; *x < 2 });
+    assert!(foo == [2, 0, 0, 0, 5]);
+}
+
+#[test]
+fn test_chain() {
+    let mut foo = [1, 2];
+    let mut bar = [3, 4];
+    let mut visited = [0; 4];
+    let mut n = 0;
+
+    foo.iter_mut().of(()).chain(bar.iter_mut().of(())).each(|x| { visited[n] = *x; n += 1; true });
+    assert!(visited == [1, 2, 3, 4]);
+
+    // stopping in the first traversal
+    foo.iter_mut().of(()).chain(bar.iter_mut().of(())).each(|x| { *x *= 10; *x < 10 });
+    assert!(foo == [10, 2] && bar == [3, 4]);
+
+    // combined with other adapters
+    foo.iter_mut().of(()).chain(bar.iter_mut().of(())).skip(1).take(2).each(|x| { *x = 0; true });
+    assert!(foo == [10, 0] && bar == [0, 4]);
+}
+
+#[test]#[cfg(feature="unsafe-pair")]
+fn test_zip() {
+    let mut foo = [1, 2, 3];
+    let mut bar = [4, 5];
+
+    // stops at the shorter side
+    foo.iter_mut().of(()).zip(bar.iter_mut()).each(|p| { let (a, b) = p.split(); core::mem::swap(a, b); true });
+    assert!(foo == [4, 5, 3] && bar == [1, 2]);
+
+    foo.iter_mut().of(()).take(1).zip(bar.iter_mut()).each(|p| { *p.snd() = 0; true });
+    assert!(bar == [0, 2]);
+
+    // stopping early
+    foo.iter_mut().of(()).zip(bar.iter_mut().rev()).each(|p| { *p.fst() += *p.snd(); false });
+    assert!(foo == [6, 5, 3]);
+}
@@ -0,0 +1,31 @@
+/// A traversal of all fields of a struct whose fields share one type.
+///
+/// Implemented by the [`AllFields`](derive.AllFields.html) derive
+/// (__requires `derive`__). The fields are visited in the order of declaration.
+///
+/// A manual implementation is straightforward:
+///
+/// ```
+/// use smart_access::traversal::{ Each, Of, AllFields };
+///
+/// struct Rgb { r: u8, g: u8, b: u8 }
+///
+/// impl Of<AllFields> for Rgb {
+///     type View = u8;
+///
+///     fn each_of<F>(&mut self, _: AllFields, mut f: F) -> bool where
+///         F: FnMut(&mut u8) -> bool
+///     {
+///         let _ = f(&mut self.r) && f(&mut self.g) && f(&mut self.b);
+///
+///         true
+///     }
+/// }
+///
+/// let mut color = Rgb { r: 10, g: 200, b: 250 };
+/// color.of(AllFields).each(|c| { *c = c.saturating_add(20); true });
+///
+/// assert!((color.r, color.g, color.b) == (30, 220, 255));
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct AllFields;
@@ -0,0 +1,211 @@
+use super::Of;
+use core::convert::TryFrom;
+
+#[cfg(feature="alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature="iter_mut")]
+use crate::iter_mut::Slice;
+
+
+/// A traversal of consecutive blocks of `n` items starting from the front.
+///
+/// Mirrors `slice::chunks_mut`: the last block can be shorter.
+/// Nothing is visited if `n` is zero.
+///
+/// Implemented for slices, for `Vec` (with the `alloc` feature enabled)
+/// and for [`Slice`](../iter_mut/struct.Slice.html) (with the `iter_mut` feature enabled).
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::traversal::{ Each, ChunksOf };
+///
+/// // a row-major 2x3 matrix
+/// let mut matrix = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+///
+/// // normalizes every row to the sum of 1
+/// matrix.of(ChunksOf(3)).each(|row| {
+///     let sum: f64 = row.iter().sum();
+///     for x in row.iter_mut() { *x /= sum; }
+///     true
+/// });
+///
+/// assert!(matrix[..3] == [1.0/6.0, 2.0/6.0, 3.0/6.0]);
+/// assert!(matrix[3..] == [4.0/15.0, 5.0/15.0, 6.0/15.0]);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct ChunksOf(pub usize);
+
+/// A traversal of consecutive blocks of `n` items starting from the back.
+///
+/// Mirrors `slice::rchunks_mut`: the blocks are visited from the back
+/// and the last visited block (the front one) can be shorter.
+/// Nothing is visited if `n` is zero.
+///
+/// Implemented for the same types as [`ChunksOf`](struct.ChunksOf.html).
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::traversal::{ Each, RChunksOf };
+///
+/// // stereo frames: the most recent ones are at the end
+/// let mut samples = vec![0, 1, 2, 3, 4, 5, 6];
+/// let mut frames = vec![];
+///
+/// samples.of(RChunksOf(2)).each(|frame| { frames.push(frame.to_vec()); frames.len() < 2 });
+///
+/// assert!(frames == vec![vec![5, 6], vec![3, 4]]);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct RChunksOf(pub usize);
+
+/// A traversal of consecutive blocks of exactly `N` items viewed as arrays.
+///
+/// Mirrors `slice::chunks_exact_mut`: the trailing items which don't form
+/// a full block are not visited. Nothing is visited if `N` is zero.
+///
+/// Implemented for slices and for `Vec` (with the `alloc` feature enabled).
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::traversal::{ Each, ArrayChunks };
+///
+/// // two RGBA pixels and a stray byte
+/// let mut pixels = vec![10, 20, 30, 255, 40, 50, 60, 128, 7];
+///
+/// // premultiplies the alpha
+/// pixels.of(ArrayChunks::<4>).each(|[r, g, b, a]| {
+///     let alpha = *a as u16;
+///     let premultiply = |c: &mut u8| *c = (*c as u16 * alpha / 255) as u8;
+///
+///     premultiply(r); premultiply(g); premultiply(b);
+///     true
+/// });
+///
+/// assert!(pixels == vec![10, 20, 30, 255, 20, 25, 30, 128, 7]);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct ArrayChunks<const N: usize>;
+
+
+impl<T> Of<ChunksOf> for [T] {
+    type View = [T];
+
+    fn each_of<F>(&mut self, i: ChunksOf, f: F) -> bool where
+        F: FnMut(&mut [T]) -> bool
+    {
+        if i.0 > 0 { self.chunks_mut(i.0).all(f); }
+
+        true
+    }
+}
+
+impl<T> Of<RChunksOf> for [T] {
+    type View = [T];
+
+    fn each_of<F>(&mut self, i: RChunksOf, f: F) -> bool where
+        F: FnMut(&mut [T]) -> bool
+    {
+        if i.0 > 0 { self.rchunks_mut(i.0).all(f); }
+
+        true
+    }
+}
+
+impl<T, const N: usize> Of<ArrayChunks<N>> for [T] {
+    type View = [T; N];
+
+    fn each_of<F>(&mut self, _: ArrayChunks<N>, mut f: F) -> bool where
+        F: FnMut(&mut [T; N]) -> bool
+    {
+        if N > 0 {
+            self.chunks_exact_mut(N).all(|chunk| f(<&mut [T; N]>::try_from(chunk).unwrap()));
+        }
+
+        true
+    }
+}
+
+#[cfg(feature="alloc")]
+impl<T> Of<ChunksOf> for Vec<T> {
+    type View = [T];
+
+    fn each_of<F>(&mut self, i: ChunksOf, f: F) -> bool where
+        F: FnMut(&mut [T]) -> bool
+    {
+        self[..].each_of(i, f)
+    }
+}
+
+#[cfg(feature="alloc")]
+impl<T> Of<RChunksOf> for Vec<T> {
+    type View = [T];
+
+    fn each_of<F>(&mut self, i: RChunksOf, f: F) -> bool where
+        F: FnMut(&mut [T]) -> bool
+    {
+        self[..].each_of(i, f)
+    }
+}
+
+#[cfg(feature="alloc")]
+impl<T, const N: usize> Of<ArrayChunks<N>> for Vec<T> {
+    type View = [T; N];
+
+    fn each_of<F>(&mut self, i: ArrayChunks<N>, f: F) -> bool where
+        F: FnMut(&mut [T; N]) -> bool
+    {
+        self[..].each_of(i, f)
+    }
+}
+
+#[cfg(feature="iter_mut")]
+impl<T> Of<ChunksOf> for Slice<T> {
+    type View = Slice<T>;
+
+    fn each_of<F>(&mut self, i: ChunksOf, mut f: F) -> bool where
+        F: FnMut(&mut Slice<T>) -> bool
+    {
+        self.as_mut().each_of(i, |chunk| f(Slice::new_mut(chunk)))
+    }
+}
+
+#[cfg(feature="iter_mut")]
+impl<T> Of<RChunksOf> for Slice<T> {
+    type View = Slice<T>;
+
+    fn each_of<F>(&mut self, i: RChunksOf, mut f: F) -> bool where
+        F: FnMut(&mut Slice<T>) -> bool
+    {
+        self.as_mut().each_of(i, |chunk| f(Slice::new_mut(chunk)))
+    }
+}
+
+
+#[test]
+fn test_chunks() {
+    use super::Each;
+
+    let mut foo = [1, 2, 3, 4, 5];
+
+    foo[..].of(ChunksOf(2)).each(|xs| { xs.reverse(); true });
+    assert!(foo == [2, 1, 4, 3, 5]);
+
+    foo[..].of(RChunksOf(2)).each(|xs| { xs[0] = 0; xs.len() == 2 });
+    assert!(foo == [0, 0, 4, 0, 5]);
+
+    let mut visited = false;
+    foo[..].of(ChunksOf(0)).each(|_| { visited = true; true });
+    foo[..].of(RChunksOf(0)).each(|_| { visited = true; true });
+    foo[..].of(ArrayChunks::<0>).each(|_| { visited = true; true });
+    assert!(!visited);
+
+    foo[..].of(ArrayChunks::<2>).each(|[x, y]| { core::mem::swap(x, y); *y == 0 });
+    assert!(foo == [0, 0, 0, 4, 5]);
+
+    foo[1..].of(ArrayChunks::<2>).each(|pair| { *pair = [1, 2]; true });
+    assert!(foo == [0, 1, 2, 1, 2]);
+}
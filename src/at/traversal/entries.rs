@@ -0,0 +1,144 @@
+#[cfg(any(feature="collections", feature="hashbrown"))]
+use super::Of;
+
+#[cfg(feature="collections")]
+use alloc::collections::BTreeMap;
+
+#[cfg(feature="hashbrown")]
+use core::hash::Hash;
+
+
+/// A traversal of map entries. __Requires `unsafe-pair`.__
+///
+/// The view of each entry is a [`KeyValue`](struct.KeyValue.html): the key
+/// can be read and the value can be mutated. Implemented for `BTreeMap`
+/// (with the `collections` feature enabled) and `HashMap` (with the
+/// `hashbrown` feature enabled).
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::traversal::{ Each, Entries };
+/// use std::collections::BTreeMap;
+///
+/// let mut fields = BTreeMap::new();
+/// fields.insert("first_name", "john".to_string());
+/// fields.insert("last_name", "doe".to_string());
+/// fields.insert("city", "york".to_string());
+///
+/// fields.of(Entries).each(|kv| {
+///     let (key, value) = kv.split();
+///
+///     if key.ends_with("_name") { *value = value.to_uppercase(); }
+///
+///     true
+/// });
+///
+/// assert!(fields["first_name"] == "JOHN");
+/// assert!(fields["last_name"] == "DOE");
+/// assert!(fields["city"] == "york");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Entries;
+
+
+/// A map entry: a shared reference to a key and a mutable reference to a value.
+/// __Requires `unsafe-pair`.__
+///
+/// The entry is unsized, thus it can't be moved out from
+/// behind a reference. The components are accessed by
+/// the [`key`](#method.key), [`value`](#method.value) and
+/// [`split`](#method.split) methods.
+///
+/// Like [`Pair`](../struct.Pair.html) the entry is implemented by casting
+/// a slice of raw pointers.
+#[repr(transparent)]
+pub struct KeyValue<K: ?Sized, V: ?Sized> {
+    ptrs: [(*const K, *mut V)],
+}
+
+impl<K: ?Sized, V: ?Sized> KeyValue<K, V> {
+    #[cfg(any(feature="collections", feature="hashbrown"))]
+    fn with_mut<R, F>(k: &K, v: &mut V, f: F) -> R where
+        F: FnOnce(&mut KeyValue<K, V>) -> R
+    {
+        let mut ptrs = [(k as *const K, v as *mut V)];
+        let slice = &mut ptrs[..] as *mut [(*const K, *mut V)];
+
+        // Safety: the layout is the same due to repr(transparent),
+        // the pointers are valid for the whole call of `f`
+        // and the entry can't escape `f` (it is unsized and has
+        // no public constructors)
+        f(unsafe { &mut *(slice as *mut KeyValue<K, V>) })
+    }
+
+    /// The key.
+    pub fn key(&self) -> &K {
+        unsafe { &*self.ptrs[0].0 }
+    }
+
+    /// The value.
+    pub fn value(&mut self) -> &mut V {
+        unsafe { &mut *self.ptrs[0].1 }
+    }
+
+    /// Both components at once.
+    pub fn split(&mut self) -> (&K, &mut V) {
+        let (k, v) = self.ptrs[0];
+
+        unsafe { (&*k, &mut *v) }
+    }
+}
+
+
+#[cfg(feature="collections")]
+impl<K, V> Of<Entries> for BTreeMap<K, V> {
+    type View = KeyValue<K, V>;
+
+    fn each_of<F>(&mut self, _: Entries, mut f: F) -> bool where
+        F: FnMut(&mut Self::View) -> bool
+    {
+        for (k, v) in self.iter_mut() {
+            if !KeyValue::with_mut(k, v, &mut f) { break }
+        }
+
+        true
+    }
+}
+
+#[cfg(feature="hashbrown")]
+impl<K: Eq + Hash, V> Of<Entries> for hashbrown::HashMap<K, V> {
+    type View = KeyValue<K, V>;
+
+    fn each_of<F>(&mut self, _: Entries, mut f: F) -> bool where
+        F: FnMut(&mut Self::View) -> bool
+    {
+        for (k, v) in self.iter_mut() {
+            if !KeyValue::with_mut(k, v, &mut f) { break }
+        }
+
+        true
+    }
+}
+
+
+#[test]#[cfg(feature="hashbrown")]
+fn test_entries() {
+    use super::Each;
+
+    let mut foo = hashbrown::HashMap::new();
+    foo.insert(1, 10);
+    foo.insert(2, 20);
+
+    let mut visited = 0;
+
+    foo.of(Entries).each(|kv| {
+        *kv.value() += *kv.key();
+        visited += 1;
+
+        false
+    });
+
+    assert!(visited == 1);
+    assert!(foo[&1] + foo[&2] == 31 || foo[&1] + foo[&2] == 32);
+}
@@ -0,0 +1,139 @@
+use super::Of;
+
+#[cfg(feature="alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature="alloc")]
+use alloc::collections::BTreeMap;
+
+#[cfg(feature="hashbrown")]
+use core::hash::Hash;
+
+
+/// A container with mutably traversable items.
+///
+/// Used by the [`Flatten`](struct.Flatten.html) traversal. Implemented for
+/// slices, `Option`, `Vec` and map values (with the `alloc` and the `hashbrown`
+/// features enabled respectively).
+pub trait Container {
+    type Item;
+
+    /// Applies `f` to the items until it returns `false`.
+    ///
+    /// Returns `false` if the traversal has been stopped.
+    fn each_item<F>(&mut self, f: F) -> bool where
+        F: FnMut(&mut Self::Item) -> bool;
+}
+
+impl<T> Container for [T] {
+    type Item = T;
+
+    fn each_item<F>(&mut self, f: F) -> bool where
+        F: FnMut(&mut T) -> bool
+    {
+        self.iter_mut().all(f)
+    }
+}
+
+impl<T> Container for Option<T> {
+    type Item = T;
+
+    fn each_item<F>(&mut self, f: F) -> bool where
+        F: FnMut(&mut T) -> bool
+    {
+        self.iter_mut().all(f)
+    }
+}
+
+#[cfg(feature="alloc")]
+impl<T> Container for Vec<T> {
+    type Item = T;
+
+    fn each_item<F>(&mut self, f: F) -> bool where
+        F: FnMut(&mut T) -> bool
+    {
+        self.iter_mut().all(f)
+    }
+}
+
+#[cfg(feature="alloc")]
+impl<K, V> Container for BTreeMap<K, V> {
+    type Item = V;
+
+    fn each_item<F>(&mut self, f: F) -> bool where
+        F: FnMut(&mut V) -> bool
+    {
+        self.values_mut().all(f)
+    }
+}
+
+#[cfg(feature="hashbrown")]
+impl<K: Eq + Hash, V> Container for hashbrown::HashMap<K, V> {
+    type Item = V;
+
+    fn each_item<F>(&mut self, f: F) -> bool where
+        F: FnMut(&mut V) -> bool
+    {
+        self.values_mut().all(f)
+    }
+}
+
+
+/// A traversal of the items of the items of a [container](trait.Container.html).
+///
+/// Mirrors `Iterator::flatten`.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::traversal::{ Each, Flatten };
+/// use std::collections::BTreeMap;
+///
+/// let mut groups = BTreeMap::new();
+/// groups.insert("a", vec![1, 2]);
+/// groups.insert("b", vec![3]);
+///
+/// groups.of(Flatten).each(|x| { *x *= 10; true });
+///
+/// assert!(groups["a"] == vec![10, 20]);
+/// assert!(groups["b"] == vec![30]);
+///
+/// let mut matrix = vec![vec![1, 2], vec![3]];
+///
+/// // stops after the first element exceeding 2
+/// matrix.of(Flatten).each(|x| { *x += 1; *x <= 2 });
+/// assert!(matrix == vec![vec![2, 3], vec![3]]);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Flatten;
+
+impl<C: Container + ?Sized> Of<Flatten> for C where
+    C::Item: Container
+{
+    type View = <C::Item as Container>::Item;
+
+    fn each_of<F>(&mut self, _: Flatten, mut f: F) -> bool where
+        F: FnMut(&mut Self::View) -> bool
+    {
+        self.each_item(|inner| inner.each_item(&mut f));
+
+        true
+    }
+}
+
+
+#[test]
+fn test_flatten() {
+    use super::Each;
+
+    let mut foo = [Some(1), None, Some(2)];
+    let mut visited = 0;
+
+    foo[..].of(Flatten).each(|x| { *x += 1; visited += 1; true });
+
+    assert!(visited == 2);
+    assert!(foo == [Some(2), None, Some(3)]);
+
+    foo[..].of(Flatten).each(|x| { *x = 0; false });
+    assert!(foo == [Some(0), None, Some(3)]);
+}
@@ -0,0 +1,64 @@
+use super::Of;
+use crate::at::At;
+
+
+/// A traversal of the values at a list of indices.
+///
+/// The indices are visited in order. The missing ones are skipped.
+/// Works for any container implementing [`At`](../trait.At.html)
+/// for the indices, e.g. for maps with a list of keys.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::traversal::{ Each, KeyList };
+/// use std::collections::BTreeMap;
+///
+/// let mut settings = BTreeMap::new();
+/// settings.insert("width", 100);
+/// settings.insert("height", 50);
+/// settings.insert("depth", 10);
+///
+/// let mut visited = vec![];
+///
+/// settings.of(KeyList(["height", "missing", "width"].iter())).each(|x| {
+///     visited.push(*x);
+///     *x *= 2;
+///     true
+/// });
+///
+/// assert!(visited == vec![50, 100]);
+/// assert!(settings["width"] == 200);
+/// assert!(settings["height"] == 100);
+/// assert!(settings["depth"] == 10);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeyList<I>(pub I);
+
+impl<T: ?Sized, I> Of<KeyList<I>> for T where
+    I: IntoIterator + Clone,
+    T: At<I::Item>
+{
+    type View = T::View;
+
+    fn each_of<F>(&mut self, keys: KeyList<I>, mut f: F) -> bool where
+        F: FnMut(&mut Self::View) -> bool
+    {
+        for key in keys.0 {
+            if self.access_at(key, &mut f) == Some(false) { break }
+        }
+
+        true
+    }
+}
+
+
+#[test]
+fn test_key_list() {
+    use super::Each;
+
+    let mut foo = [1, 2, 3, 4];
+
+    foo[..].of(KeyList([3, 7, 0, 1])).each(|x| { *x += 10; *x != 11 });
+    assert!(foo == [11, 2, 3, 14]);
+}
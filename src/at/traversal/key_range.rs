@@ -0,0 +1,83 @@
+use super::Of;
+use core::ops::{ Bound, RangeBounds };
+use alloc::collections::BTreeMap;
+
+
+/// A traversal of the values of a `BTreeMap` with the keys in a range.
+///
+/// A shorthand for `map.range_mut(..).map(|(_, v)| v).of(())`, usable
+/// as a part of a path. The values are visited in the order of keys.
+///
+/// __Requires `collections`.__
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::traversal::{ Each, KeyRange };
+/// use std::collections::BTreeMap;
+///
+/// let mut readings = BTreeMap::new();
+/// for hour in 0..24 { readings.insert(hour, vec![hour]); }
+///
+/// // the working hours
+/// readings.of(KeyRange(9..18)).each(|xs| { xs.push(0); true });
+///
+/// assert!(readings[&8] == vec![8]);
+/// assert!(readings[&9] == vec![9, 0]);
+/// assert!(readings[&17] == vec![17, 0]);
+/// assert!(readings[&18] == vec![18]);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct KeyRange<R>(pub R);
+
+/// An empty or decreasing range visits nothing
+impl<K, V, R> Of<KeyRange<R>> for BTreeMap<K, V> where
+    K: Ord,
+    R: RangeBounds<K> + Clone
+{
+    type View = V;
+
+    fn each_of<F>(&mut self, i: KeyRange<R>, f: F) -> bool where
+        F: FnMut(&mut V) -> bool
+    {
+        if is_empty(&i.0) { return true; }
+
+        self.range_mut(i.0).map(|(_, v)| v).all(f);
+
+        true
+    }
+}
+
+// The ranges rejected by `BTreeMap::range_mut` (which panics on them).
+fn is_empty<K: Ord, R: RangeBounds<K>>(range: &R) -> bool {
+    use Bound::*;
+
+    match (range.start_bound(), range.end_bound()) {
+        (Included(a), Included(b)) => a > b,
+        (Included(a), Excluded(b)) |
+        (Excluded(a), Included(b)) |
+        (Excluded(a), Excluded(b)) => a >= b,
+        _ => false,
+    }
+}
+
+
+#[test]
+fn test_key_range() {
+    use super::Each;
+
+    let mut foo = BTreeMap::new();
+    for i in 0..5 { foo.insert(i, i); }
+
+    foo.of(KeyRange(1..=3)).each(|x| { *x *= 10; *x < 20 });
+    foo.of(KeyRange(4..)).each(|x| { *x = 0; true });
+    foo.of(KeyRange(7..9)).each(|x| { *x = 0; true });
+
+    // empty and decreasing ranges
+    #[allow(clippy::reversed_empty_ranges)]
+    foo.of(KeyRange(3..1)).each(|x| { *x = 0; true });
+    foo.of(KeyRange((Bound::Excluded(2), Bound::Excluded(2)))).each(|x| { *x = 0; true });
+    foo.of(KeyRange(2..2)).each(|x| { *x = 0; true });
+
+    assert!(foo.values().copied().eq([0, 10, 20, 3, 0].iter().copied()));
+}
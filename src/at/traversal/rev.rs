@@ -0,0 +1,93 @@
+use super::Of;
+
+#[cfg(feature="alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature="alloc")]
+use alloc::collections::{ BTreeMap, VecDeque };
+
+
+/// A traversal of the items in the reverse order.
+///
+/// Implemented for slices and (with the `alloc` feature enabled)
+/// for `Vec`, `VecDeque` and `BTreeMap` (values in the reverse order of keys).
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::traversal::{ Each, Rev };
+///
+/// // every element becomes the sum of itself and the elements after it
+/// let mut foo = vec![1, 2, 3];
+/// let mut sum = 0;
+///
+/// foo.of(Rev).each(|x| { sum += *x; *x = sum; true });
+///
+/// assert!(foo == vec![6, 5, 3]);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Rev;
+
+impl<T> Of<Rev> for [T] {
+    type View = T;
+
+    fn each_of<F>(&mut self, _: Rev, f: F) -> bool where
+        F: FnMut(&mut T) -> bool
+    {
+        self.iter_mut().rev().all(f);
+
+        true
+    }
+}
+
+#[cfg(feature="alloc")]
+impl<T> Of<Rev> for Vec<T> {
+    type View = T;
+
+    fn each_of<F>(&mut self, _: Rev, f: F) -> bool where
+        F: FnMut(&mut T) -> bool
+    {
+        self.iter_mut().rev().all(f);
+
+        true
+    }
+}
+
+#[cfg(feature="alloc")]
+impl<T> Of<Rev> for VecDeque<T> {
+    type View = T;
+
+    fn each_of<F>(&mut self, _: Rev, f: F) -> bool where
+        F: FnMut(&mut T) -> bool
+    {
+        self.iter_mut().rev().all(f);
+
+        true
+    }
+}
+
+#[cfg(feature="alloc")]
+impl<K, V> Of<Rev> for BTreeMap<K, V> {
+    type View = V;
+
+    fn each_of<F>(&mut self, _: Rev, f: F) -> bool where
+        F: FnMut(&mut V) -> bool
+    {
+        self.values_mut().rev().all(f);
+
+        true
+    }
+}
+
+
+#[test]
+fn test_rev() {
+    use super::Each;
+
+    let mut foo = [1, 2, 3];
+    let mut visited = 0;
+
+    foo[..].of(Rev).each(|x| { *x *= 10; visited += 1; visited < 2 });
+
+    assert!(foo == [1, 20, 30]);
+}
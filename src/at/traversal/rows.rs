@@ -0,0 +1,82 @@
+use super::Of;
+use crate::at::Pair;
+
+#[cfg(feature="alloc")]
+use alloc::vec::Vec;
+
+
+/// A traversal of the rows of two parallel columns. __Requires `unsafe-pair`.__
+///
+/// Implemented for a [`Pair`](../struct.Pair.html) of slices and (with
+/// the `alloc` feature enabled) for a `Pair` of vectors. The view of a row
+/// is a `Pair` of the items at the same position. Nothing is visited if
+/// the lengths of the columns differ.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::Cps;
+/// use smart_access::traversal::{ Each, Rows };
+///
+/// // a struct-of-arrays layout of particles
+/// let mut positions = vec![0.0, 10.0, 20.0];
+/// let mut velocities = vec![1.0, -1.0, 0.5];
+///
+/// // `Each` has its own `zip` thus the qualified syntax is needed
+/// Cps::zip(&mut positions, &mut velocities).access(|columns| {
+///     columns.of(Rows).each(|row| {
+///         let (x, v) = row.split();
+///         *x += *v;
+///         true
+///     })
+/// });
+///
+/// assert!(positions == vec![1.0, 9.0, 20.5]);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Rows;
+
+impl<A, B> Of<Rows> for Pair<[A], [B]> {
+    type View = Pair<A, B>;
+
+    fn each_of<F>(&mut self, _: Rows, mut f: F) -> bool where
+        F: FnMut(&mut Pair<A, B>) -> bool
+    {
+        let (a, b) = self.split();
+        if a.len() != b.len() { return true; }
+
+        a.iter_mut().zip(b.iter_mut()).all(|(x, y)| Pair::with_mut(x, y, &mut f));
+
+        true
+    }
+}
+
+#[cfg(feature="alloc")]
+impl<A, B> Of<Rows> for Pair<Vec<A>, Vec<B>> {
+    type View = Pair<A, B>;
+
+    fn each_of<F>(&mut self, i: Rows, f: F) -> bool where
+        F: FnMut(&mut Pair<A, B>) -> bool
+    {
+        let (a, b) = self.split();
+
+        Pair::with_mut(&mut a[..], &mut b[..], |p| p.each_of(i, f))
+    }
+}
+
+
+#[test]
+fn test_rows() {
+    use super::Each;
+    use crate::Cps;
+
+    let mut foo = [1, 2, 3];
+    let mut bar = ['a', 'b', 'c'];
+    let mut baz = [0; 2];
+
+    Cps::zip(&mut foo[..], &mut bar[..]).access(|p| p.of(Rows).each(|row| { *row.fst() *= 10; *row.snd() != 'b' }));
+    assert!(foo == [10, 20, 3]);
+
+    Cps::zip(&mut foo[..], &mut baz[..]).access(|p| p.of(Rows).each(|row| { *row.snd() = 1; true }));
+    assert!(baz == [0, 0]);
+}
@@ -0,0 +1,98 @@
+use super::At;
+
+
+/// A smart access protocol reporting the reason of a failure.
+///
+/// Unlike [`At`](trait.At.html), `try_access_at` returns a typed error
+/// instead of `None`. The contract is the same: if an error is returned
+/// then `self` must stay unchanged.
+///
+/// `TryAt` is bridged to `At` through the [`Checked`](struct.Checked.html)
+/// index (errors become `None`). The error itself can be obtained by the
+/// [`try_at`](trait.Cps.html#method.try_at) method.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::{ Cps, TryAt, Checked };
+///
+/// #[derive(Debug, PartialEq)]
+/// enum SlotError { OutOfBounds, Locked }
+///
+/// struct Slots { values: Vec<i32>, locked: Vec<bool> }
+///
+/// impl TryAt<usize> for Slots {
+///     type View = i32;
+///     type Error = SlotError;
+///
+///     fn try_access_at<R, F>(&mut self, i: usize, f: F) -> Result<R, SlotError> where
+///         F: FnOnce(&mut i32) -> R
+///     {
+///         match self.locked.get(i) {
+///             None       => Err(SlotError::OutOfBounds),
+///             Some(true) => Err(SlotError::Locked),
+///             _          => Ok(f(&mut self.values[i])),
+///         }
+///     }
+/// }
+///
+/// let mut slots = Slots { values: vec![1, 2], locked: vec![false, true] };
+///
+/// assert!(slots.try_at(0, |x| { *x += 1; *x }) == Some(Ok(2)));
+/// assert!(slots.try_at(1, |x| *x) == Some(Err(SlotError::Locked)));
+/// assert!(slots.try_at(2, |x| *x) == Some(Err(SlotError::OutOfBounds)));
+///
+/// // the `At` bridge
+/// assert!(slots.at(Checked(0)).replace(5) == Some(2));
+/// assert!(slots.at(Checked(1)).replace(5) == None);
+/// ```
+pub trait TryAt<Index> {
+    type View: ?Sized;
+    type Error;
+
+    /// Accesses data at a specified index or reports why it's impossible.
+    fn try_access_at<R, F>(&mut self, i: Index, f: F) -> Result<R, Self::Error> where
+        F: FnOnce(&mut Self::View) -> R;
+}
+
+
+/// An index bridging [`TryAt`](trait.TryAt.html) to `At`.
+#[derive(Debug, Copy, Clone)]
+pub struct Checked<I>(pub I);
+
+/// `access_at` returns `None` if `try_access_at` returns an error
+impl<T: TryAt<I> + ?Sized, I> At<Checked<I>> for T {
+    type View = T::View;
+
+    fn access_at<R, F>(&mut self, i: Checked<I>, f: F) -> Option<R> where
+        F: FnOnce(&mut Self::View) -> R
+    {
+        self.try_access_at(i.0, f).ok()
+    }
+}
+
+
+#[test]
+fn test_try_at() {
+    use crate::Cps;
+
+    struct Even(i32);
+
+    impl TryAt<()> for Even {
+        type View = i32;
+        type Error = i32;
+
+        fn try_access_at<R, F>(&mut self, _: (), f: F) -> Result<R, i32> where
+            F: FnOnce(&mut i32) -> R
+        {
+            if self.0 % 2 == 0 { Ok(f(&mut self.0)) } else { Err(self.0) }
+        }
+    }
+
+    let mut foo = [Even(2), Even(3)];
+
+    assert!(foo[..].at(0).try_at((), |x| { *x += 2; *x }) == Some(Ok(4)));
+    assert!(foo[..].at(1).try_at((), |x| *x) == Some(Err(3)));
+    assert!(foo[..].at(2).try_at((), |x| *x).is_none());
+    assert!(foo[..].at(1).at(Checked(())).touch().is_none());
+}
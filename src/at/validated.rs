@@ -0,0 +1,69 @@
+use super::Cps;
+
+
+/// A `Cps` value writing back only the values satisfying a predicate.
+///
+/// The closure passed to `access` runs on a copy of the view.
+/// If the predicate holds for the modified copy then the copy is
+/// written back, otherwise the data is left untouched and
+/// `access` returns `None`.
+///
+/// Can be created either directly or by the
+/// [`validated`](trait.Cps.html#method.validated) method.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::{ Cps, Validated };
+///
+/// let mut balances = vec![10, 20];
+///
+/// let withdraw = |balances: &mut Vec<i32>, i: usize, amount: i32| {
+///     Validated(balances.at(i), |x: &i32| *x >= 0).access(|x| { *x -= amount; })
+/// };
+///
+/// assert!(withdraw(&mut balances, 0, 5) == Some(()));
+/// assert!(withdraw(&mut balances, 1, 25) == None);
+/// assert!(balances == vec![5, 20]);
+/// ```
+#[must_use]
+#[derive(Debug, Clone)]
+pub struct Validated<CPS, P>(pub CPS, pub P);
+
+/// `access` returns `None` if the predicate fails
+impl<CPS: Cps, P> Cps for Validated<CPS, P> where
+    CPS::View: Sized + Clone,
+    P: FnOnce(&CPS::View) -> bool
+{
+    type View = CPS::View;
+
+    fn access<R, F>(self, f: F) -> Option<R> where
+        F: FnOnce(&mut Self::View) -> R
+    {
+        let pred = self.1;
+
+        self.0.access(|v| {
+            let mut copy = v.clone();
+            let result = f(&mut copy);
+
+            if !pred(&copy) { return None; }
+
+            *v = copy;
+
+            Some(result)
+        }).flatten()
+    }
+}
+
+
+#[test]
+fn test_validated() {
+    let mut foo = Some(1);
+
+    assert!(foo.at(()).validated(|x| *x < 3).replace(2) == Some(1));
+    assert!(foo.at(()).validated(|x| *x < 3).replace(3).is_none());
+    assert!(foo == Some(2));
+
+    let mut bar: Option<i32> = None;
+    assert!(bar.at(()).validated(|_| true).replace(1).is_none());
+}
@@ -1,6 +1,12 @@
+#[cfg(any(feature="batch_ct", feature="batch_rt"))]
 mod run_batch;  // a helper for compile-time batch execution
+
+#[cfg(any(feature="batch_ct", feature="batch_rt"))]
 use run_batch::RunBatch;
 
+#[cfg(feature="batch_ct")]
+use run_batch::Prepend;
+
 use crate::at::Cps;
 
 #[cfg(feature="batch_rt")]
@@ -9,6 +15,9 @@ use alloc::vec::Vec;
 #[cfg(feature="batch_rt")]
 use alloc::boxed::Box;
 
+#[cfg(all(feature="batch_ct", feature="detach"))]
+use crate::Attach;
+
 
 /// A builder for complex mutations. __Requires `batch_ct` or `batch_rt`.__
 ///
@@ -18,8 +27,8 @@ use alloc::boxed::Box;
 ///
 /// Created by method `.batch_ct()` of any [`Cps`](trait.Cps.html)-bounded value.
 ///
-/// Efficient but can't be combined with loops (and is difficult to use in 
-/// presence of conditional branches).
+/// Efficient but can't be combined with loops. A step chosen at runtime
+/// from two functions can be added by [`add_if`](#method.add_if-1).
 ///
 /// ### Example
 ///
@@ -93,6 +102,47 @@ use alloc::boxed::Box;
 /// ```
 ///
 /// Runtime batches are abstracted by the trait [`BatchRt`](trait.BatchRt.html).
+///
+///
+/// ## Fixed-capacity version
+///
+/// Created by method `.batch_bounded()`. A runtime batch storing at most `N`
+/// functions of the same type `S` inline, thus it needs no heap. The type
+/// can be a closure (e.g. created in a loop) or a function pointer.
+///
+/// __Requires `batch_bounded`.__
+///
+/// ### Example
+///
+/// ```
+/// # #[cfg(feature="batch_bounded")] fn main() {
+/// use smart_access::Cps;
+///
+/// let mut foo = 0;
+///
+/// let mut batch = (&mut foo).batch_bounded::<_, 16>();
+///
+/// for i in 1..=10 {
+///     batch = batch.add(move |v: &mut i32, prev: Option<i32>| { *v += i; prev.unwrap_or(0) + 1 });
+/// }
+///
+/// assert!(!batch.is_full());
+/// assert!(batch.run() == Some(10));
+/// assert!(foo == (1..=10).sum::<i32>());
+///
+/// // function pointers allow different functions in the same batch
+/// type Step = fn(&mut i32, Option<()>);
+///
+/// let result = foo.batch_bounded::<Step, 2>()
+///     .add(|v, _| { *v = -*v; })
+///     .add(|v, _| { *v *= 2; })
+///     .run();
+///
+/// assert!(result == Some(()));
+/// assert!(foo == -110);
+/// # }
+/// # #[cfg(not(feature="batch_bounded"))] fn main() {}
+/// ```
 #[must_use]
 pub struct CpsBatch<CPS, L> {
     cps: CPS,
@@ -102,6 +152,9 @@ pub struct CpsBatch<CPS, L> {
 #[cfg(feature="batch_rt")]
 pub type FnBoxRt<V, R> = Box<dyn FnOnce(&mut V, Option<R>) -> R>;
 
+#[cfg(feature="batch_rt")]
+pub type FnMutBoxRt<V, R> = Box<dyn FnMut(&mut V, Option<R>) -> R>;
+
 
 /// An _empty_ compile-time batch.
 #[cfg(feature="batch_ct")]
@@ -119,6 +172,43 @@ impl<CPS> CpsBatch<CPS, ()> where
     {
         CpsBatch { cps: self.cps, list: (self.list, f) }
     }
+
+    /// Adds one of two functions to an _empty_ compile-time batch.
+    ///
+    /// See the [nonempty version](#method.add_if-1).
+    #[allow(clippy::type_complexity)]
+    pub fn add_if<F, G, R>(self, cond: bool, f: F, g: G)
+        -> CpsBatch<CPS, ((), impl FnOnce(&mut CPS::View, ()) -> R)> where
+        F: FnOnce(&mut CPS::View, ()) -> R,
+        G: FnOnce(&mut CPS::View, ()) -> R
+    {
+        self.add(move |v, prev| if cond { f(v, prev) } else { g(v, prev) })
+    }
+
+    /// Prepends a function to an _empty_ compile-time batch
+    /// (the same as [`add`](#method.add) but the function must return `()`).
+    pub fn prepend<G>(self, g: G) -> CpsBatch<CPS, ((), G)>
+        where G: FnOnce(&mut CPS::View, ())
+    {
+        CpsBatch { cps: self.cps, list: ((), g) }
+    }
+
+    /// Adds a new function accessing a [detached path](trait.Attach.html)
+    /// to an _empty_ compile-time batch.
+    ///
+    /// See the [nonempty version](#method.at_step-1).
+    ///
+    /// _Present only on `detach`._
+    #[cfg(feature="detach")]
+    #[allow(clippy::type_complexity)]
+    pub fn at_step<P, W, G, S>(self, path: P, g: G)
+        -> CpsBatch<CPS, ((), impl FnOnce(&mut CPS::View, ()) -> Option<S>)> where
+        P: Attach<CPS::View, View=W>,
+        W: ?Sized,
+        G: FnOnce(&mut W, ()) -> S
+    {
+        self.add(move |v, prev| v.attach(path).access(|w| g(w, prev)))
+    }
 }
 
 /// A _nonempty_ compile-time batch.
@@ -141,6 +231,97 @@ impl<CPS,Prev,F,R> CpsBatch<CPS, (Prev, F)> where
         CpsBatch { cps: self.cps, list: (self.list, g) }
     }
 
+    /// Adds one of two functions to a _nonempty_ compile-time batch.
+    ///
+    /// The step runs `f` if `cond` is true and `g` otherwise. Both functions
+    /// must have the same result type. The type of the batch doesn't depend
+    /// on the condition, thus the batch can be built in presence of
+    /// runtime branching:
+    ///
+    /// ```
+    /// use smart_access::Cps;
+    ///
+    /// fn update(foo: &mut Vec<i32>, reset: bool) -> Option<usize> {
+    ///     foo.batch_ct()
+    ///         .add(|v, _| { v.push(1); })
+    ///         .add_if(reset, |v, _| { v.clear(); 0 }, |v, _| v.len())
+    ///         .add(|v, n| { v.push(n as i32); v.len() })
+    ///         .run()
+    /// }
+    ///
+    /// let mut foo = vec![];
+    ///
+    /// assert!(update(&mut foo, false) == Some(2));
+    /// assert!(update(&mut foo, true) == Some(1));
+    /// assert!(foo == vec![0]);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn add_if<G, H, S>(self, cond: bool, g: G, h: H)
+        -> CpsBatch<CPS, ((Prev, F), impl FnOnce(&mut CPS::View, R) -> S)> where
+        G: FnOnce(&mut CPS::View, R) -> S,
+        H: FnOnce(&mut CPS::View, R) -> S
+    {
+        self.add(move |v, prev| if cond { g(v, prev) } else { h(v, prev) })
+    }
+
+    /// Adds a new function accessing a [detached path](trait.Attach.html)
+    /// to a _nonempty_ compile-time batch.
+    ///
+    /// Thus a single batch can update several parts of the same root.
+    /// The function gets the previous result. The step returns `None`
+    /// if the path doesn't resolve.
+    ///
+    /// _Present only on `detach`._
+    ///
+    /// ```
+    /// use smart_access::{ Cps, detached_at };
+    ///
+    /// let mut foo = vec![ vec![1, 2], vec![3] ];
+    ///
+    /// let result = foo.batch_ct()
+    ///     .at_step(detached_at(0).at(1), |x, _| { *x += 10; *x })
+    ///     .at_step(detached_at(1).at(0), |x, prev| { *x += prev.unwrap(); *x })
+    ///     .at_step(detached_at(2).at(0), |x, _| { *x = 0; })
+    ///     .run();
+    ///
+    /// assert!(result == Some(None));  // the last step has failed
+    /// assert!(foo == vec![ vec![1, 12], vec![15] ]);
+    /// ```
+    #[cfg(feature="detach")]
+    #[allow(clippy::type_complexity)]
+    pub fn at_step<P, W, G, S>(self, path: P, g: G)
+        -> CpsBatch<CPS, ((Prev, F), impl FnOnce(&mut CPS::View, R) -> Option<S>)> where
+        P: Attach<CPS::View, View=W>,
+        W: ?Sized,
+        G: FnOnce(&mut W, R) -> S
+    {
+        self.add(move |v, prev| v.attach(path).access(|w| g(w, prev)))
+    }
+
+    /// Prepends a function to a _nonempty_ compile-time batch.
+    ///
+    /// The function is run before all the other functions of the batch.
+    /// It must return `()` because the first function of the batch
+    /// receives `()` as its second argument.
+    ///
+    /// ```
+    /// # use smart_access::Cps;
+    /// let mut foo = vec![1];
+    ///
+    /// let result = foo.batch_ct()
+    ///     .add(|v, _| v.len())
+    ///     .prepend(|v, _| { v.push(2); })
+    ///     .run();
+    ///
+    /// assert!(result == Some(2));
+    /// ```
+    pub fn prepend<G>(self, g: G) -> CpsBatch<CPS, <(Prev, F) as Prepend<G>>::Output> where
+        G: FnOnce(&mut CPS::View, ()),
+        (Prev, F): Prepend<G>
+    {
+        CpsBatch { cps: self.cps, list: self.list.prepend(g) }
+    }
+
     /// Takes the last function from a _nonempty_ compile-time batch.
     ///
     /// You can use it as follows:
@@ -195,6 +376,26 @@ fn test_ct_batch_editing() {
         .run();
 
     assert!(foo == 2);
+
+    let result = foo.batch_ct()
+        .prepend(|x, _| { *x *= 10; })
+        .add(|x, _| *x)
+        .prepend(|x, _| { *x += 1; })
+        .prepend(|x, _| { *x *= 2; })
+        .run();
+
+    assert!(result == Some(50));
+
+    for &cond in &[true, false] {
+        let result = foo.batch_ct()
+            .add_if(cond, |x, _| { *x += 1; "then" }, |x, _| { *x -= 1; "else" })
+            .add_if(!cond, |_, s| s, |_, _| "other")
+            .run();
+
+        assert!(result == Some(if cond { "other" } else { "else" }));
+    }
+
+    assert!(foo == 50);
 }
 
 
@@ -286,12 +487,244 @@ fn test_rt_batch_editing() {
 
 
 
+/// A looping runtime batch.
+///
+/// Created by method `.batch_loop()`. Unlike an ordinary runtime batch
+/// its functions are `FnMut` and thus can be run several times.
+///
+/// ```
+/// use smart_access::Cps;
+///
+/// let mut foo = vec![1, 5, 3, 2, 4];
+///
+/// // one pass of the bubble sort
+/// let pass = |v: &mut Vec<i32>, _| {
+///     let mut swapped = false;
+///
+///     for i in 1..v.len() {
+///         if v[i-1] > v[i] { v.swap(i-1, i); swapped = true; }
+///     }
+///
+///     swapped
+/// };
+///
+/// let result = foo.batch_loop().add(pass).run_until(10, |_, swapped| !swapped);
+///
+/// assert!(result == Some(Ok(false)));
+/// assert!(foo == vec![1, 2, 3, 4, 5]);
+/// ```
+#[cfg(feature="batch_rt")]
+impl<CPS: Cps, R> CpsBatch<CPS, Vec<FnMutBoxRt<CPS::View, R>>> {
+    /// Runs a looping batch once.
+    ///
+    /// Immediately returns `None` if the batch is empty.
+    pub fn run(self) -> Option<R> {
+        self.run_until(1, |_, _| true).map(|result| result.unwrap_or_else(|x| x))
+    }
+
+    /// Runs the functions of a looping batch repeatedly until
+    /// the predicate holds for the view and the last result.
+    ///
+    /// The first function of every iteration except the first one receives
+    /// the last result of the previous iteration.
+    ///
+    /// At most `max_iterations` iterations are run. Returns `Ok(result)` if the
+    /// predicate holds and `Err(result)` if the iterations are exhausted.
+    /// Returns `None` if the batch is empty, `max_iterations` is zero or
+    /// the access fails.
+    pub fn run_until<P>(self, max_iterations: usize, mut pred: P) -> Option<Result<R, R>> where
+        P: FnMut(&mut CPS::View, &R) -> bool
+    {
+        let mut list = self.list;
+
+        if list.is_empty() || max_iterations == 0 { return None; }
+
+        self.cps.access(|v| {
+            let mut current_result = None;
+
+            for _ in 0..max_iterations {
+                for f in list.iter_mut() {
+                    current_result = Some(f(v, current_result));
+                }
+
+                let result = current_result.take().unwrap();
+
+                if pred(v, &result) { return Ok(result); }
+
+                current_result = Some(result);
+            }
+
+            Err(current_result.unwrap())
+        })
+    }
+
+    /// Adds a new function to a looping batch.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add<F>(mut self, f: F) -> Self where
+        F: FnMut(&mut CPS::View, Option<R>) -> R + 'static
+    {
+        self.list.push(Box::new(f));
+
+        self
+    }
+
+    /// Clears a looping batch.
+    pub fn clear(mut self) -> Self {
+        self.list.clear();
+
+        self
+    }
+
+    /// A direct access to the underlying vector.
+    pub fn edit(&mut self) -> &mut Vec<FnMutBoxRt<CPS::View, R>> {
+        &mut self.list
+    }
+}
+
+
+#[cfg(feature="batch_rt")]#[test]
+fn test_loop_batch() {
+    use crate::Cps;
+    let mut foo = 1;
+
+    let result = foo.batch_loop()
+        .add(|x, _| { *x *= 2; *x })
+        .add(|x, prev| { *x += 1; prev.unwrap() })
+        .run_until(10, |x, _| *x > 20);
+
+    assert!(result == Some(Ok(30)));
+    assert!(foo == 31);
+
+    let result = foo.batch_loop().add(|x, prev| { *x += 1; prev.unwrap_or(0) + 1 }).run_until(3, |_, _| false);
+
+    assert!(result == Some(Err(3)));
+    assert!(foo == 34);
+
+    assert!(foo.batch_loop().add(|x, _| { *x += 1; }).run() == Some(()));
+    assert!(foo.batch_loop::<()>().run().is_none());
+    assert!(foo == 35);
+}
+
+
+#[cfg(all(feature="batch_ct", feature="batch_rt"))]#[test]
+fn test_run_or() {
+    use crate::Cps;
+    let mut foo = 1;
+
+    assert!(foo.batch_rt().add(|x, _| { *x += 1; *x }).run_or(0) == 2);
+    assert!(foo.batch_rt().run_or_else(|| 5) == 5);
+    assert!(foo.batch_ct().add(|x, _| *x * 10).run_or_else(|| 0) == 20);
+}
+
+
+/// A fixed-capacity runtime batch.
+///
+/// Has the same interface as an ordinary runtime batch
+/// except that [`add`](#method.add-2) panics if the batch is full.
+#[cfg(feature="batch_bounded")]
+impl<CPS: Cps, S, const N: usize> CpsBatch<CPS, heapless::Vec<S, N>> {
+    /// Runs a fixed-capacity batch.
+    ///
+    /// Immediately returns `None` if the batch is empty.
+    pub fn run<R>(self) -> Option<R> where
+        S: FnOnce(&mut CPS::View, Option<R>) -> R
+    {
+        let list = self.list;
+
+        if list.is_empty() { return None; }
+
+        self.cps.access(|v| {
+            let mut current_result = None;
+
+            for f in list {
+                current_result = Some(f(v, current_result));
+            }
+
+            current_result.unwrap()
+        })
+    }
+
+    /// Adds a new function to a fixed-capacity batch.
+    ///
+    /// ### Panics
+    ///
+    /// If the batch already contains `N` functions. Use [`edit`](#method.edit-2)
+    /// for a fallible version.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(mut self, f: S) -> Self {
+        if self.list.push(f).is_err() { panic!("the batch is full"); }
+
+        self
+    }
+
+    /// Checks if no more functions can be added.
+    pub fn is_full(&self) -> bool {
+        self.list.is_full()
+    }
+
+    /// Takes the last function from a fixed-capacity batch.
+    pub fn pop(mut self, dst: Option<&mut Option<S>>) -> Self {
+        let maybe_f = self.list.pop();
+
+        if let Some(place) = dst { *place = maybe_f; }
+
+        self
+    }
+
+    /// Clears a fixed-capacity batch.
+    pub fn clear(mut self) -> Self {
+        self.list.clear();
+
+        self
+    }
+
+    /// A direct access to the underlying vector.
+    pub fn edit(&mut self) -> &mut heapless::Vec<S, N> {
+        &mut self.list
+    }
+}
+
+
+#[cfg(feature="batch_bounded")]#[test]
+fn test_bounded_batch() {
+    use crate::Cps;
+    let mut foo = 1;
+
+    let mut batch = foo.batch_bounded::<_, 3>();
+
+    for i in 0..3 {
+        batch = batch.add(move |x: &mut i32, _| { *x += i; *x });
+    }
+
+    assert!(batch.is_full());
+
+    let mut maybe_f = None;
+    assert!(batch.pop(Some(&mut maybe_f)).run() == Some(2));
+    assert!(maybe_f.unwrap()(&mut foo, None) == 4);
+
+    type Step = fn(&mut i32, Option<()>);
+
+    let mut batch = foo.batch_bounded::<Step, 1>().add(|x, _| { *x = 0; });
+    assert!(batch.edit().push(|x, _| { *x = 1; }).is_err());
+    assert!(batch.clear().run().is_none());
+    assert!(foo == 4);
+}
+
+
 // Helpers for the Cps trait.
 #[cfg(feature="batch_ct")]
 pub fn new_batch_ct<CPS: Cps>(cps: CPS) -> CpsBatch<CPS, ()> {
     CpsBatch { cps: cps, list: () }
 }
 
+#[cfg(feature="batch_rt")]
+pub fn new_batch_loop<CPS, V, R>(cps: CPS) -> CpsBatch<CPS, Vec<FnMutBoxRt<V,R>>> where
+    CPS: Cps<View=V>,
+    V: ?Sized
+{
+    CpsBatch { cps, list: Vec::new() }
+}
+
 #[cfg(feature="batch_rt")]
 pub fn new_batch_rt<CPS, V, R>(cps: CPS) -> CpsBatch<CPS, Vec<FnBoxRt<V,R>>> where 
     CPS: Cps<View=V>,
@@ -301,6 +734,12 @@ pub fn new_batch_rt<CPS, V, R>(cps: CPS) -> CpsBatch<CPS, Vec<FnBoxRt<V,R>>> whe
 }
 
 
+#[cfg(feature="batch_bounded")]
+pub fn new_batch_bounded<CPS: Cps, S, const N: usize>(cps: CPS) -> CpsBatch<CPS, heapless::Vec<S, N>> {
+    CpsBatch { cps, list: heapless::Vec::new() }
+}
+
+
 /// An abstraction over [compile-time and runtime batches](struct.CpsBatch.html). 
 /// __Requires `batch_ct` or `batch_rt`.__
 ///
@@ -312,9 +751,34 @@ pub fn new_batch_rt<CPS, V, R>(cps: CPS) -> CpsBatch<CPS, Vec<FnBoxRt<V,R>>> whe
 /// If the batch returned by a function is to be edited later 
 /// then consider using more precise bounds:
 /// [`BatchCt`](trait.BatchCt.html) and [`BatchRt`](trait.BatchRt.html).
+///
+/// A caller which doesn't distinguish an empty batch from a failed access
+/// can provide a fallback result:
+///
+/// ```
+/// use smart_access::{ Batch, Cps };
+///
+/// let mut foo = vec![1, 2];
+///
+/// assert!(foo.at(1).batch_ct().add(|x, _| { *x += 1; *x }).run_or(0) == 3);
+/// assert!(foo.at(5).batch_ct().add(|x, _| { *x += 1; *x }).run_or(0) == 0);
+/// assert!(foo.at(0).batch_rt().run_or_else(|| -1) == -1);
+/// ```
 #[must_use]
 pub trait Batch<R>: Sized {
     fn run(self) -> Option<R>;
+
+    /// Runs a batch. Returns `default` if the batch is empty or the access fails.
+    fn run_or(self, default: R) -> R {
+        self.run().unwrap_or(default)
+    }
+
+    /// Runs a batch. Computes the result by `f` if the batch is empty or the access fails.
+    fn run_or_else<F>(self, f: F) -> R where
+        F: FnOnce() -> R
+    {
+        self.run().unwrap_or_else(f)
+    }
 }
 
 
@@ -345,6 +809,16 @@ impl<CPS: Cps, R> Batch<R> for CpsBatch<CPS, Vec<FnBoxRt<CPS::View, R>>> {
 }
 
 
+#[cfg(feature="batch_bounded")]
+impl<CPS: Cps, S, R, const N: usize> Batch<R> for CpsBatch<CPS, heapless::Vec<S, N>> where
+    S: FnOnce(&mut CPS::View, Option<R>) -> R
+{
+    fn run(self) -> Option<R> {
+        self.run()
+    }
+}
+
+
 /// A compile-time batch. __Requires `batch_ct` feature.__
 ///
 /// See basic usage guide [here](struct.CpsBatch.html).
@@ -374,6 +848,11 @@ pub trait BatchCt<V: ?Sized, R>: Batch<R> {
     fn add<G, S>(self, g: G) -> CpsBatch<Self::CPS, (Self::List, G)> where 
         G: FnOnce(&mut V, R) -> S;
 
+    /// Prepends a function to a compile-time batch.
+    fn prepend<G>(self, g: G) -> CpsBatch<Self::CPS, <Self::List as Prepend<G>>::Output> where
+        G: FnOnce(&mut V, ()),
+        Self::List: Prepend<G>;
+
     /// Clears a compile-time batch.
     fn clear(self) -> CpsBatch<Self::CPS, ()>;
 
@@ -395,6 +874,12 @@ impl<CPS: Cps> BatchCt<CPS::View, ()> for CpsBatch<CPS, ()> {
         self.add(g)
     }
 
+    fn prepend<G>(self, g: G) -> CpsBatch<CPS, <Self::List as Prepend<G>>::Output> where
+        G: FnOnce(&mut CPS::View, ())
+    {
+        self.prepend(g)
+    }
+
     fn clear(self) -> CpsBatch<CPS, ()> {
         self
     }
@@ -415,6 +900,13 @@ impl<CPS, Prev, F, R> BatchCt<CPS::View, R> for CpsBatch<CPS, (Prev, F)> where
         self.add(g)
     }
 
+    fn prepend<G>(self, g: G) -> CpsBatch<CPS, <Self::List as Prepend<G>>::Output> where
+        G: FnOnce(&mut CPS::View, ()),
+        Self::List: Prepend<G>
+    {
+        self.prepend(g)
+    }
+
     fn clear(self) -> CpsBatch<CPS, ()> {
         self.clear()
     }
@@ -36,6 +36,35 @@ impl<View: ?Sized, Prev, F, R> RunBatch<View> for (Prev, F) where
     }
 }
 
+
+// type-level prepending to compile-time call chains
+//
+// Is private to the "crate::batch" module.
+#[cfg(feature="batch_ct")]
+pub trait Prepend<G> {
+    type Output;
+
+    fn prepend(self, g: G) -> Self::Output;
+}
+
+#[cfg(feature="batch_ct")]
+impl<G> Prepend<G> for () {
+    type Output = ((), G);
+
+    fn prepend(self, g: G) -> ((), G) { ((), g) }
+}
+
+#[cfg(feature="batch_ct")]
+impl<G, Prev, F> Prepend<G> for (Prev, F) where
+    Prev: Prepend<G>
+{
+    type Output = (Prev::Output, F);
+
+    fn prepend(self, g: G) -> Self::Output {
+        (self.0.prepend(g), self.1)
+    }
+}
+
 #[cfg(feature="batch_rt")]
 impl<View: ?Sized, R> RunBatch<View> for Vec<FnBoxRt<View, R>> {
     type Output = Option<R>;
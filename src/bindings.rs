@@ -0,0 +1,158 @@
+//! Data binding. __Requires `bindings`.__
+//!
+//! A [`Bindings`](struct.Bindings.html) registry lets observers subscribe
+//! to [detached paths](../trait.Attach.html). After every access performed
+//! through [`access`](struct.Bindings.html#method.access) the observers
+//! of the path are notified with the new value.
+//!
+//! The paths are compared by their indices (thus they must be `Eq + Hash`).
+//!
+//! ```
+//! use smart_access::detached_at;
+//! use smart_access::bindings::Bindings;
+//! use std::rc::Rc;
+//! use std::cell::RefCell;
+//!
+//! let mut state = vec![ vec![10, 20], vec![30] ];
+//! let label = Rc::new(RefCell::new(String::new()));
+//!
+//! let mut bindings = Bindings::new();
+//! let label_ref = label.clone();
+//!
+//! bindings.subscribe(detached_at(0).at(1), move |x: &i32| {
+//!     *label_ref.borrow_mut() = format!("volume: {}", x);
+//! });
+//!
+//! assert!(bindings.access(&mut state, &detached_at(0).at(1), |x| *x += 1) == Some(()));
+//! assert!(*label.borrow() == "volume: 21");
+//!
+//! // observers of other paths are not notified
+//! bindings.access(&mut state, &detached_at(1).at(0), |x| *x += 1);
+//! assert!(*label.borrow() == "volume: 21");
+//! assert!(state == vec![ vec![10, 21], vec![31] ]);
+//! ```
+
+use crate::at::Cps;
+use crate::Attach;
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::hash::Hash;
+use core::marker::PhantomData;
+use hashbrown::HashMap;
+
+
+type Observers<V> = Vec<(Subscription, Box<dyn FnMut(&V)>)>;
+
+
+/// An identifier of a subscription (for unsubscribing).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Subscription(usize);
+
+
+/// A registry of observers of detached paths.
+///
+/// All the paths of a registry have the same type `P`, thus the same
+/// shape (e.g. `detached_at(0).at(1)` and `detached_at(1).at(0)` can share
+/// a registry but `detached_at(0)` can't join them). Paths of different
+/// shapes need separate registries.
+///
+/// See the [module-level documentation](index.html) for an example.
+pub struct Bindings<Root: ?Sized, P: Attach<Root>> {
+    observers: HashMap<P, Observers<P::View>>,
+    next_id: usize,
+    root: PhantomData<fn(&mut Root)>,
+}
+
+impl<Root: ?Sized, P: Attach<Root> + Eq + Hash> Bindings<Root, P> {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Bindings { observers: HashMap::new(), next_id: 0, root: PhantomData }
+    }
+
+    /// Subscribes the observer to the path.
+    pub fn subscribe<F>(&mut self, path: P, observer: F) -> Subscription where
+        F: FnMut(&P::View) + 'static
+    {
+        let id = Subscription(self.next_id);
+        self.next_id += 1;
+
+        self.observers.entry(path).or_default().push((id, Box::new(observer)));
+
+        id
+    }
+
+    /// Removes the subscription. Returns `false` if there is no such subscription.
+    pub fn unsubscribe(&mut self, id: Subscription) -> bool {
+        let mut found = false;
+
+        for observers in self.observers.values_mut() {
+            let len = observers.len();
+            observers.retain(|(other, _)| *other != id);
+            found |= observers.len() != len;
+        }
+
+        self.observers.retain(|_, observers| !observers.is_empty());
+
+        found
+    }
+
+    /// Checks if the path has observers.
+    pub fn is_observed(&self, path: &P) -> bool {
+        self.observers.contains_key(path)
+    }
+
+    /// Accesses the root along the path then notifies the observers of the path.
+    ///
+    /// Returns `None` (and notifies nobody) if the path doesn't resolve.
+    pub fn access<R, F>(&mut self, root: &mut Root, path: &P, f: F) -> Option<R> where
+        P: Clone,
+        F: FnOnce(&mut P::View) -> R
+    {
+        let observers = self.observers.get_mut(path);
+
+        root.attach(path.clone()).access(|v| {
+            let result = f(v);
+
+            for (_, observer) in observers.into_iter().flatten() { observer(v); }
+
+            result
+        })
+    }
+}
+
+impl<Root: ?Sized, P: Attach<Root> + Eq + Hash> Default for Bindings<Root, P> {
+    fn default() -> Self {
+        Bindings::new()
+    }
+}
+
+
+#[test]
+fn test_bindings() {
+    use crate::detached_at;
+    use alloc::rc::Rc;
+    use core::cell::RefCell;
+
+    let mut foo = [1, 2, 3];
+    let seen = Rc::new(RefCell::new(0));
+
+    let mut bindings = Bindings::new();
+
+    let seen_ref = seen.clone();
+    let first = bindings.subscribe(detached_at(0), move |x: &i32| *seen_ref.borrow_mut() += x);
+    let seen_ref = seen.clone();
+    bindings.subscribe(detached_at(0), move |x: &i32| *seen_ref.borrow_mut() += 10 * x);
+
+    assert!(bindings.access(&mut foo[..], &detached_at(0), |x| *x = 2) == Some(()));
+    assert!(*seen.borrow() == 22);
+
+    assert!(bindings.access(&mut foo[..], &detached_at(5), |x| *x = 2).is_none());
+    assert!(*seen.borrow() == 22);
+
+    assert!(bindings.unsubscribe(first));
+    assert!(!bindings.unsubscribe(first));
+
+    bindings.access(&mut foo[..], &detached_at(0), |x| *x = 1);
+    assert!(*seen.borrow() == 32);
+    assert!(bindings.is_observed(&detached_at(0)));
+}
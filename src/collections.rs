@@ -4,24 +4,57 @@
 //! The following traits are implemented:
 //! * `At<(), View=[T]> for Vec<T>`: the slice owned by the vector
 //! * `At<usize, View=T> for Vec<T>`: simple indexing
+//! * `At<Rotated, View=[T]> for Vec<T>`: the same as for [slices](../core_impls/struct.Rotated.html)
+//! * `At<Offset<I>, View=T>`, `At<Scaled<I>, View=T>` and `At<Reversed<I>, View=T> for Vec<T>`:
+//!   the same as for [slices](../core_impls/trait.Position.html)
 //! * `At<range, View=Vec<T>> for Vec<T>`: subvector (its size can be changed); 
 //!   __Warning:__ access is O(n); consider passing to slices to get O(1) access
+//! * `At<TryReserve<range>, View=Vec<T>> for Vec<T>`: the same but 
+//!   [allocation failures](struct.TryReserve.html) result in `None`
+//! * `At<AsUtf8, View=String> for Vec<u8>`: the bytes [as text](../core_impls/struct.AsUtf8.html)
+//!   (if they are valid UTF-8)
+//! * `At<Append<I>, View=[T]> for Vec<T>`: [append](struct.Append.html) 
+//!   the items then access the new tail
+//! * `At<Slot(i), View=T>`, `At<SlotOr(i,T), View=T>` and `At<Vacate(i), View=T> for Vec<Option<T>>`:
+//!   [sparse storage](struct.Slot.html) access (skipping the `Option` layer)
+//! * `At<Sorted<C>, View=[T]> for Vec<T>` and `for [T]`: the items in the
+//!   [sorted order](struct.Sorted.html) (moved back to their positions after the update)
+//! * `At<PartitionBy<P>, View=(Vec<T>,Vec<T>)> for Vec<T>`: the items [split](struct.PartitionBy.html)
+//!   by a predicate (reassembled after the update)
+//! * `At<(), View=[T]> for Rc<[T]>` and `At<(), View=str> for Rc<str>` 
+//!   (and the same for `Arc`): clone-on-write access
 //! * `At<&Q, View=V> for <Some>Map<K,V>`: access the value if it is present 
 //! * `At<(K,V), View=V> for <Some>Map<K,V>`: ensure that the value is 
 //!   present (using the provided default) then access it
+//! * `At<(K,), View=V> for <Some>Map<K,V>`: the same but using `V::default()`
 //! * `AT<(K,V,M), View=V> for <Some>Map<K,V>`: if the value is present 
 //!   then preprocess it with a mutator `M`, otherwise insert the provided `V`
+//! * `At<Ensure(K,V), View=(V,bool)> for <Some>Map<K,V>`: the same
+//!   as `(K,V)` but also [reports](struct.Ensure.html) whether the value was inserted
+//! * `At<Floor(&Q), View=V>` and `At<Ceiling(&Q), View=V> for BTreeMap<K,V>`: 
+//!   access the value of the [nearest key](struct.Floor.html) (not greater 
+//!   or not less respectively)
+//! * `At<SubMap(range), View=BTreeMap<K,V>> for BTreeMap<K,V>`: the [entries](struct.SubMap.html)
+//!   with the keys in the range (merged back after the update)
+//! * `At<Occurrence(&str, n), View=String> for String`: the [`n`-th occurrence](struct.Occurrence.html)
+//!   of a substring (spliced back if changed)
 //! * `AT<&Q, View=T> for <Some>Set<T>`: access the value if it is present
 //! * `AT<(T,()), View=T> for <Some>Set<T>`: ensure that the value is present 
 //!   then access it
 //! * `AT<(T,), View=<Some>Set<T>> for <Some>Set<T>`: ensure that the value 
 //!   is present
+//! * `At<Ensure(T,()), View=(T,bool)> for <Some>Set<T>`: the same
+//!   as `(T,())` but also [reports](struct.Ensure.html) whether the value was inserted
 //!
 //! Though in normal circumstances these implementations __do not__ panic
 //! there __exists__ a possibility of panicking. For example 
-//! `At<range> for Vec<T>` splits vector into (at most) three parts
-//! then glues them back after the update. Every of these actions 
-//! can panic on Out Of Memory.
+//! `At<range> for Vec<T>` moves the range out into a temporary vector
+//! then splices it back after the update (the allocation of the original 
+//! vector is reused). Both of these actions can panic on Out Of Memory.
+//!
+//! If the accessing closure panics the collection stays structurally intact:
+//! the range of a vector is spliced back and the element taken out of a set 
+//! is reinserted (with the modifications made before the panic).
 //!
 //! ## Vector accessors
 //!
@@ -45,12 +78,31 @@
 //! ```
 //!
 //!
+//! ## Shared buffers
+//!
+//! A shared slice (or string) is copied into a new allocation before 
+//! the update, unless the pointer is unique:
+//!
+//! ```
+//! # use smart_access::{ Cps };
+//! use std::rc::Rc;
+//!
+//! let mut foo: Rc<[i32]> = Rc::from(vec![1, 2, 3]);
+//! let bar = foo.clone();
+//!
+//! assert!(foo.at(()).at(0).replace(4) == Some(1));
+//! assert!(*foo == [4, 2, 3]);
+//! assert!(*bar == [1, 2, 3]);
+//! ```
+//!
+//!
 //! ## Map accessors
 //!
 //! Implemented for `HashMap` and `BTreeMap`:
 //!
 //! * `map.at(&k).access(f)` is equivalent to `map.get_mut(&k).map(|v| f(v))`
 //! * `map.at( (k,v) ).access(f)` is equivalent to `Some(f(map.entry(k).or_insert(v)))`
+//! * `map.at( (k,) ).access(f)` is equivalent to `Some(f(map.entry(k).or_default()))`
 //! * `map.at( (k,v,m) ).access(f)` is equivalent to `Some(f(self.entry(k).and_modify(m).or_insert(v)))`
 //!
 //! ```
@@ -78,6 +130,9 @@
 //! 
 //! assert!(hm.get(&41) == Some(&3));
 //! assert!(hm.get(&42) == Some(&4));
+//!
+//! hm.at( (43,) ).access(|x| { *x += 5; });
+//! assert!(hm.get(&43) == Some(&5));
 //! ```
 //!
 //!
@@ -119,6 +174,14 @@
 mod vec;
 mod map;
 mod set;
+mod shared;
+mod string;
+mod sorted;
+
+pub use vec::{ TryReserve, Append, PartitionBy, Slot, SlotOr, Vacate };
+pub use map::{ Floor, Ceiling, Ensure, SubMap };
+pub use string::{ Occurrence };
+pub use sorted::{ Sorted };
 
 #[test]
 fn test_vec() {
@@ -199,6 +262,10 @@ fn test_btree_map() {
     assert!(map.at("foo").replace(4) == Some(1));
     assert!(map.at("quuz").replace(5) == None);
 
+    map.at( ("qux".to_string(),) ).touch();
+    assert!(map.at( ("qux".to_string(),) ).replace(6) == Some(0));
+    assert!(map.remove("qux") == Some(6));
+
     let mut reference_map = BTreeMap::<String,i32>::new();
     reference_map.entry("foo".to_string()).or_insert(4);
     reference_map.entry("bar".to_string()).or_insert(2);
@@ -207,3 +274,32 @@ fn test_btree_map() {
     assert!(map == reference_map);
 }
 
+
+
+#[test]
+fn test_panic_safety() {
+    extern crate std;
+    use std::vec;
+    use std::prelude::v1::*;
+    use std::collections::BTreeSet;
+    use std::panic::{ catch_unwind, AssertUnwindSafe };
+    use crate::Cps;
+
+    let mut foo = vec![1,2,3,4,5];
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        foo.at(1..3).access(|xs| { xs.push(6); std::panic!() })
+    }));
+
+    assert!(result.is_err());
+    assert!(foo == vec![1,2,3,6,4,5]);
+
+    let mut bar: BTreeSet<_> = vec![1,2,3].into_iter().collect();
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        bar.at(&2).access(|x| { *x = 7; std::panic!() })
+    }));
+
+    assert!(result.is_err());
+    assert!(bar.into_iter().collect::<Vec<_>>() == vec![1,3,7]);
+}
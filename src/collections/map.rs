@@ -1,4 +1,5 @@
 use core::borrow::Borrow;
+use core::ops::{ Bound, RangeBounds };
 use alloc::collections::BTreeMap;
 use core::hash::Hash;
 use crate::At;
@@ -30,6 +31,20 @@ impl<K,V> At<(K,V)> for hashbrown::HashMap<K,V> where
     }
 }
 
+#[cfg(feature="hashbrown")]
+impl<K,V> At<(K,)> for hashbrown::HashMap<K,V> where
+    K: Eq + Hash,
+    V: Default
+{
+    type View = V;
+
+    fn access_at<R,F>(&mut self, k: (K,), f: F) -> Option<R> where
+        F: FnOnce(&mut V) -> R
+    {
+        Some(f(self.entry(k.0).or_default()))
+    }
+}
+
 #[cfg(feature="hashbrown")]
 impl<K,V,M> At<(K,V,M)> for hashbrown::HashMap<K,V> where
     K: Eq + Hash,
@@ -76,6 +91,20 @@ impl<K,V> At<(K,V)> for std::collections::HashMap<K,V> where
     }
 }
 
+#[cfg(feature="std_hashmap")]
+impl<K,V> At<(K,)> for std::collections::HashMap<K,V> where
+    K: Eq + Hash,
+    V: Default
+{
+    type View = V;
+
+    fn access_at<R,F>(&mut self, k: (K,), f: F) -> Option<R> where
+        F: FnOnce(&mut V) -> R
+    {
+        Some(f(self.entry(k.0).or_default()))
+    }
+}
+
 #[cfg(feature="std_hashmap")]
 impl<K,V,M> At<(K,V,M)> for std::collections::HashMap<K,V> where
     K: Eq + Hash,
@@ -165,6 +194,19 @@ impl<K,V> At<(K,V)> for BTreeMap<K,V> where
     }
 }
 
+impl<K,V> At<(K,)> for BTreeMap<K,V> where
+    K: Ord,
+    V: Default
+{
+    type View = V;
+
+    fn access_at<R,F>(&mut self, k: (K,), f: F) -> Option<R> where
+        F: FnOnce(&mut V) -> R
+    {
+        Some(f(self.entry(k.0).or_default()))
+    }
+}
+
 impl<K,V,M> At<(K,V,M)> for BTreeMap<K,V> where
     K: Ord,
     M: FnOnce(&mut V)
@@ -178,3 +220,321 @@ impl<K,V,M> At<(K,V,M)> for BTreeMap<K,V> where
     }
 }
 
+
+/// A neighbor lookup index: the greatest key less than or equal to the given one.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::Cps;
+/// use smart_access::collections::{ Floor, Ceiling };
+/// use std::collections::BTreeMap;
+///
+/// // tax rates by income thresholds
+/// let mut rates = BTreeMap::new();
+/// rates.insert(0, 10);
+/// rates.insert(1000, 20);
+///
+/// assert!(rates.at(Floor(&999)).get_clone() == Some(10));
+/// assert!(rates.at(Floor(&1000)).replace(25) == Some(20));
+/// assert!(rates.at(Ceiling(&1)).get_clone() == Some(25));
+/// assert!(rates.at(Ceiling(&1001)).touch().is_none());
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Floor<'a, Q: ?Sized>(pub &'a Q);
+
+/// A neighbor lookup index: the least key greater than or equal to the given one.
+///
+/// See [`Floor`](struct.Floor.html) for an example.
+#[derive(Debug, Copy, Clone)]
+pub struct Ceiling<'a, Q: ?Sized>(pub &'a Q);
+
+impl<Q,K,V> At<Floor<'_, Q>> for BTreeMap<K,V> where
+    K: Borrow<Q> + Ord,
+    Q: ?Sized + Ord
+{
+    type View = V;
+
+    fn access_at<R,F>(&mut self, i: Floor<'_, Q>, f: F) -> Option<R> where
+        F: FnOnce(&mut V) -> R
+    {
+        self.range_mut::<Q, _>((Bound::Unbounded, Bound::Included(i.0)))
+            .next_back()
+            .map(|(_, v)| f(v))
+    }
+}
+
+impl<Q,K,V> At<Ceiling<'_, Q>> for BTreeMap<K,V> where
+    K: Borrow<Q> + Ord,
+    Q: ?Sized + Ord
+{
+    type View = V;
+
+    fn access_at<R,F>(&mut self, i: Ceiling<'_, Q>, f: F) -> Option<R> where
+        F: FnOnce(&mut V) -> R
+    {
+        self.range_mut::<Q, _>((Bound::Included(i.0), Bound::Unbounded))
+            .next()
+            .map(|(_, v)| f(v))
+    }
+}
+
+
+/// An ensuring index reporting whether the entry was inserted.
+///
+/// `Ensure(key, value)` inserts the value if the key is missing (like the
+/// `(K,V)` index). The entry is taken out of the map for the duration of
+/// the access (and put back even if the accessing closure panics), thus
+/// the view is an owned tuple: the (possibly just inserted) value and
+/// the `inserted` flag. Thus initialization logic can run exactly once
+/// without a second lookup.
+///
+/// Implemented for `BTreeMap`, `HashMap` from `hashbrown` (with the `hashbrown`
+/// feature enabled) and `HashMap` from `std` (with the `std_hashmap` feature enabled).
+///
+/// Sets implement `At<Ensure<T,()>>`: the analogue of the `(T,())` index.
+/// The view is the (possibly just inserted) item and the `inserted` flag.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::Cps;
+/// use smart_access::collections::Ensure;
+/// use std::collections::BTreeMap;
+///
+/// let mut sessions = BTreeMap::new();
+/// let mut greeted = vec![];
+///
+/// for &user in &["alice", "bob", "alice"] {
+///     sessions.at(Ensure(user, 0)).access(|(visits, inserted)| {
+///         if *inserted { greeted.push(user); }
+///         *visits += 1;
+///     });
+/// }
+///
+/// assert!(greeted == vec!["alice", "bob"]);
+/// assert!(sessions["alice"] == 2 && sessions["bob"] == 1);
+/// ```
+///
+/// The same for a set:
+///
+/// ```
+/// use smart_access::Cps;
+/// use smart_access::collections::Ensure;
+/// use std::collections::BTreeSet;
+///
+/// let mut seen = BTreeSet::new();
+///
+/// assert!(seen.at(Ensure("alice", ())).access(|(_, inserted)| *inserted) == Some(true));
+/// assert!(seen.at(Ensure("alice", ())).access(|(_, inserted)| *inserted) == Some(false));
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Ensure<K, V>(pub K, pub V);
+
+// Puts the taken entry back into the map on drop, i.e. even if `f` panics.
+struct Reinsert<'a, M, K, V> {
+    map: &'a mut M,
+    entry: Option<(K, (V, bool))>,
+    insert: fn(&mut M, K, V),
+}
+
+impl<M, K, V> Drop for Reinsert<'_, M, K, V> {
+    fn drop(&mut self) {
+        if let Some((k, (v, _))) = self.entry.take() { (self.insert)(self.map, k, v); }
+    }
+}
+
+fn with_ensured<M, K, V, R, F>(map: &mut M, taken: Option<(K, V)>, i: Ensure<K, V>, insert: fn(&mut M, K, V), f: F) -> Option<R> where
+    F: FnOnce(&mut (V, bool)) -> R
+{
+    let entry = match taken {
+        Some((k, v)) => (k, (v, false)),
+        None         => (i.0, (i.1, true)),
+    };
+
+    let mut guard = Reinsert { map, entry: Some(entry), insert };
+
+    guard.entry.as_mut().map(|(_, p)| f(p))
+}
+
+/// `access_at` is guaranteed to return `Some(f(..))`
+impl<K,V> At<Ensure<K,V>> for BTreeMap<K,V> where
+    K: Ord,
+{
+    type View = (V, bool);
+
+    fn access_at<R,F>(&mut self, i: Ensure<K,V>, f: F) -> Option<R> where
+        F: FnOnce(&mut (V, bool)) -> R
+    {
+        let taken = self.remove_entry(&i.0);
+
+        with_ensured(self, taken, i, |m, k, v| { m.insert(k, v); }, f)
+    }
+}
+
+/// `access_at` is guaranteed to return `Some(f(..))`
+#[cfg(feature="hashbrown")]
+impl<K,V> At<Ensure<K,V>> for hashbrown::HashMap<K,V> where
+    K: Eq + Hash,
+{
+    type View = (V, bool);
+
+    fn access_at<R,F>(&mut self, i: Ensure<K,V>, f: F) -> Option<R> where
+        F: FnOnce(&mut (V, bool)) -> R
+    {
+        let taken = self.remove_entry(&i.0);
+
+        with_ensured(self, taken, i, |m, k, v| { m.insert(k, v); }, f)
+    }
+}
+
+/// `access_at` is guaranteed to return `Some(f(..))`
+#[cfg(feature="std_hashmap")]
+impl<K,V> At<Ensure<K,V>> for std::collections::HashMap<K,V> where
+    K: Eq + Hash,
+{
+    type View = (V, bool);
+
+    fn access_at<R,F>(&mut self, i: Ensure<K,V>, f: F) -> Option<R> where
+        F: FnOnce(&mut (V, bool)) -> R
+    {
+        let taken = self.remove_entry(&i.0);
+
+        with_ensured(self, taken, i, |m, k, v| { m.insert(k, v); }, f)
+    }
+}
+
+
+/// A sub-map index: the entries with the keys in a range.
+///
+/// The entries are moved out into an owned `BTreeMap` (by `split_off`)
+/// which is merged back (by `append`) after the access, even if the
+/// accessing closure panics. Thus the entries can be rewritten,
+/// removed or inserted in bulk. Entries inserted outside of the range
+/// are merged too (replacing the existing ones).
+///
+/// The access is O(log n) plus the size of the range.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::Cps;
+/// use smart_access::collections::SubMap;
+/// use std::collections::BTreeMap;
+///
+/// // log lines by timestamps
+/// let mut log: BTreeMap<u32, &str> = (1..=6).map(|t| (t, "ok")).collect();
+///
+/// // drops the lines from the interval [2, 4)
+/// assert!(log.at(SubMap(2..4)).access(|lines| lines.clear()) == Some(()));
+/// assert!(log.at(SubMap(5..)).access(|lines| lines.len()) == Some(2));
+/// assert!(log.at(SubMap(..=5)).at(&5).replace("failed") == Some("ok"));
+///
+/// assert!(log.into_iter().collect::<Vec<_>>() == vec![(1, "ok"), (4, "ok"), (5, "failed"), (6, "ok")]);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct SubMap<R>(pub R);
+
+/// `access_at` is guaranteed to return `Some(f(..))`
+impl<K,V,Range> At<SubMap<Range>> for BTreeMap<K,V> where
+    K: Ord,
+    Range: RangeBounds<K>,
+{
+    type View = BTreeMap<K,V>;
+
+    fn access_at<R,F>(&mut self, i: SubMap<Range>, f: F) -> Option<R> where
+        F: FnOnce(&mut BTreeMap<K,V>) -> R
+    {
+        let mut sub = match i.0.start_bound() {
+            Bound::Unbounded    => core::mem::take(self),
+            Bound::Included(k)  => self.split_off(k),
+            Bound::Excluded(k)  => {
+                let mut sub = self.split_off(k);
+                if let Some((k, v)) = sub.remove_entry(k) { self.insert(k, v); }
+                sub
+            },
+        };
+
+        let tail = match i.0.end_bound() {
+            Bound::Unbounded    => BTreeMap::new(),
+            Bound::Excluded(k)  => sub.split_off(k),
+            Bound::Included(k)  => {
+                let mut tail = sub.split_off(k);
+                if let Some((k, v)) = tail.remove_entry(k) { sub.insert(k, v); }
+                tail
+            },
+        };
+
+        self.extend(tail);
+
+        let mut guard = MergeBack { map: self, sub };
+
+        Some(f(&mut guard.sub))
+    }
+}
+
+// Merges the sub-map back on drop, i.e. even if `f` panics.
+struct MergeBack<'a, K: Ord, V> {
+    map: &'a mut BTreeMap<K,V>,
+    sub: BTreeMap<K,V>,
+}
+
+impl<K: Ord, V> Drop for MergeBack<'_, K, V> {
+    fn drop(&mut self) {
+        self.map.append(&mut self.sub);
+    }
+}
+
+#[test]
+fn test_ensure() {
+    extern crate std;
+    use std::panic::{ catch_unwind, AssertUnwindSafe };
+    use crate::Cps;
+
+    let mut foo = BTreeMap::new();
+
+    assert!(foo.at(Ensure(1, 10)).access(|p| p.1) == Some(true));
+    assert!(foo.at(Ensure(1, 20)).access(|p| { p.0 += 1; p.1 }) == Some(false));
+    assert!(foo[&1] == 11);
+
+    let panicked = catch_unwind(AssertUnwindSafe(|| {
+        foo.at(Ensure(1, 30)).access(|p| { p.0 += 1; std::panic!(); })
+    }));
+    assert!(panicked.is_err());
+    assert!(foo[&1] == 12);
+
+    #[cfg(feature="hashbrown")] {
+        let mut bar = hashbrown::HashMap::new();
+
+        assert!(bar.at(Ensure("a", 1)).access(|p| p.1) == Some(true));
+        assert!(bar.at(Ensure("a", 2)).access(|p| *p) == Some((1, false)));
+    }
+}
+
+
+#[test]
+fn test_sub_map() {
+    extern crate std;
+    use std::vec;
+    use std::prelude::v1::*;
+    use std::panic::{ catch_unwind, AssertUnwindSafe };
+    use core::ops::Bound::{ Excluded, Included };
+    use crate::Cps;
+
+    let mut foo: BTreeMap<i32, i32> = (0..6).map(|k| (k, k)).collect();
+    let keys = |map: &mut BTreeMap<i32, i32>, range| map.at(SubMap(range))
+        .access(|sub| sub.keys().cloned().collect::<Vec<_>>());
+
+    assert!(keys(&mut foo, (Excluded(1), Included(3))) == Some(vec![2, 3]));
+    assert!(keys(&mut foo, (Excluded(5), Excluded(5))) == Some(vec![]));
+    assert!(keys(&mut foo, (Included(4), Included(2))) == Some(vec![]));
+
+    assert!(foo.at(SubMap(..)).access(|sub| { sub.remove(&0); sub.insert(10, 10); }) == Some(()));
+
+    let panicked = catch_unwind(AssertUnwindSafe(|| {
+        foo.at(SubMap(2..)).access(|sub| { sub.insert(2, 20); std::panic!(); })
+    }));
+
+    assert!(panicked.is_err());
+    assert!(foo.into_iter().collect::<Vec<_>>() == vec![(1, 1), (2, 20), (3, 3), (4, 4), (5, 5), (10, 10)]);
+}
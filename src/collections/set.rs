@@ -2,6 +2,21 @@ use core::borrow::Borrow;
 use alloc::collections::BTreeSet;
 use core::hash::Hash;
 use crate::At;
+use super::Ensure;
+
+
+// Puts the taken item back into the set on drop, i.e. even if `f` panics.
+struct Reinsert<'a, S, T> {
+    set: &'a mut S,
+    item: Option<T>,
+    insert: fn(&mut S, T),
+}
+
+impl<S, T> Drop for Reinsert<'_, S, T> {
+    fn drop(&mut self) {
+        if let Some(item) = self.item.take() { (self.insert)(self.set, item); }
+    }
+}
 
 
 #[cfg(feature="hashbrown")]
@@ -33,11 +48,30 @@ impl<T> At<(T,())> for hashbrown::HashSet<T> where
             item.0 = v;
         }
 
-        let result = f(&mut item.0);
-        
-        self.insert(item.0);
+        let mut guard = Reinsert { set: self, item: Some(item.0), insert: |s, x| { s.insert(x); } };
+
+        guard.item.as_mut().map(f)
+    }
+}
+
+
+#[cfg(feature="hashbrown")]
+impl<T> At<Ensure<T,()>> for hashbrown::HashSet<T> where
+    T: Eq + Hash,
+{
+    type View = (T, bool);
+
+    fn access_at<R,F>(&mut self, i: Ensure<T,()>, f: F) -> Option<R> where
+        F: FnOnce(&mut (T, bool)) -> R
+    {
+        let (item, inserted) = match self.take(&i.0) {
+            Some(v) => (v, false),
+            None    => (i.0, true),
+        };
+
+        let mut guard = Reinsert { set: self, item: Some((item, inserted)), insert: |s, (x, _)| { s.insert(x); } };
 
-        Some(result)
+        guard.item.as_mut().map(f)
     }
 }
 
@@ -52,13 +86,11 @@ impl<Q,T> At<&Q> for hashbrown::HashSet<T> where
     fn access_at<R,F>(&mut self, i: &Q, f: F) -> Option<R> where
         F: FnOnce(&mut T) -> R
     {
-        self.take(i).map(|mut v| {
-            let result = f(&mut v);
+        let v = self.take(i)?;
 
-            self.insert(v);
+        let mut guard = Reinsert { set: self, item: Some(v), insert: |s, x| { s.insert(x); } };
 
-            result
-        })
+        guard.item.as_mut().map(f)
     }
 }
 
@@ -96,11 +128,30 @@ impl<T> At<(T,())> for std::collections::HashSet<T> where
             item.0 = v;
         }
 
-        let result = f(&mut item.0);
-        
-        self.insert(item.0);
+        let mut guard = Reinsert { set: self, item: Some(item.0), insert: |s, x| { s.insert(x); } };
+
+        guard.item.as_mut().map(f)
+    }
+}
+
+
+#[cfg(feature="std_hashmap")]
+impl<T> At<Ensure<T,()>> for std::collections::HashSet<T> where
+    T: Eq + Hash,
+{
+    type View = (T, bool);
+
+    fn access_at<R,F>(&mut self, i: Ensure<T,()>, f: F) -> Option<R> where
+        F: FnOnce(&mut (T, bool)) -> R
+    {
+        let (item, inserted) = match self.take(&i.0) {
+            Some(v) => (v, false),
+            None    => (i.0, true),
+        };
+
+        let mut guard = Reinsert { set: self, item: Some((item, inserted)), insert: |s, (x, _)| { s.insert(x); } };
 
-        Some(result)
+        guard.item.as_mut().map(f)
     }
 }
 
@@ -115,13 +166,11 @@ impl<Q,T> At<&Q> for std::collections::HashSet<T> where
     fn access_at<R,F>(&mut self, i: &Q, f: F) -> Option<R> where
         F: FnOnce(&mut T) -> R
     {
-        self.take(i).map(|mut v| {
-            let result = f(&mut v);
+        let v = self.take(i)?;
 
-            self.insert(v);
+        let mut guard = Reinsert { set: self, item: Some(v), insert: |s, x| { s.insert(x); } };
 
-            result
-        })
+        guard.item.as_mut().map(f)
     }
 }
 
@@ -156,11 +205,28 @@ impl<T> At<(T,())> for BTreeSet<T> where
             item.0 = v;
         }
 
-        let result = f(&mut item.0);
-        
-        self.insert(item.0);
+        let mut guard = Reinsert { set: self, item: Some(item.0), insert: |s, x| { s.insert(x); } };
+
+        guard.item.as_mut().map(f)
+    }
+}
+
+impl<T> At<Ensure<T,()>> for BTreeSet<T> where
+    T: Ord,
+{
+    type View = (T, bool);
+
+    fn access_at<R,F>(&mut self, i: Ensure<T,()>, f: F) -> Option<R> where
+        F: FnOnce(&mut (T, bool)) -> R
+    {
+        let (item, inserted) = match self.take(&i.0) {
+            Some(v) => (v, false),
+            None    => (i.0, true),
+        };
 
-        Some(result)
+        let mut guard = Reinsert { set: self, item: Some((item, inserted)), insert: |s, (x, _)| { s.insert(x); } };
+
+        guard.item.as_mut().map(f)
     }
 }
 
@@ -173,13 +239,11 @@ impl<Q,T> At<&Q> for BTreeSet<T> where
     fn access_at<R,F>(&mut self, i: &Q, f: F) -> Option<R> where
         F: FnOnce(&mut T) -> R
     {
-        self.take(i).map(|mut v| {
-            let result = f(&mut v);
+        let v = self.take(i)?;
 
-            self.insert(v);
+        let mut guard = Reinsert { set: self, item: Some(v), insert: |s, x| { s.insert(x); } };
 
-            result
-        })
+        guard.item.as_mut().map(f)
     }
 }
 
@@ -210,3 +274,23 @@ impl<Q,T> At<Option<&Q>> for BTreeSet<T> where
     }
 }*/
 
+
+
+#[test]
+fn test_set_ensure() {
+    use crate::Cps;
+
+    let mut foo = BTreeSet::new();
+
+    assert!(foo.at(Ensure(1, ())).access(|p| p.1) == Some(true));
+    assert!(foo.at(Ensure(1, ())).access(|p| { p.0 = 2; p.1 }) == Some(false));
+    assert!(foo.iter().eq([2].iter()));
+
+    #[cfg(feature="hashbrown")] {
+        let mut bar = hashbrown::HashSet::new();
+
+        assert!(bar.at(Ensure("a", ())).access(|p| p.1) == Some(true));
+        assert!(bar.at(Ensure("a", ())).access(|p| *p) == Some(("a", false)));
+        assert!(bar.len() == 1);
+    }
+}
@@ -0,0 +1,70 @@
+use crate::at::At;
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+
+
+macro_rules! shared_slice {
+    ( $($ptr:ident),* ) => { $(
+        /// `access_at` is guaranteed to return `Some(f(..))`
+        impl<T: Clone> At<()> for $ptr<[T]> {
+            type View = [T];
+
+            fn access_at<R, F>(&mut self, _: (), f: F) -> Option<R> where
+                F: FnOnce(&mut [T]) -> R
+            {
+                if let Some(unique) = $ptr::get_mut(self) { return Some(f(unique)); }
+
+                let mut copy: $ptr<[T]> = $ptr::from(&self[..]);
+                let result = f($ptr::get_mut(&mut copy)?);
+
+                *self = copy;
+
+                Some(result)
+            }
+        }
+
+        /// `access_at` is guaranteed to return `Some(f(..))`
+        impl At<()> for $ptr<str> {
+            type View = str;
+
+            fn access_at<R, F>(&mut self, _: (), f: F) -> Option<R> where
+                F: FnOnce(&mut str) -> R
+            {
+                if let Some(unique) = $ptr::get_mut(self) { return Some(f(unique)); }
+
+                let mut copy: $ptr<str> = $ptr::from(&self[..]);
+                let result = f($ptr::get_mut(&mut copy)?);
+
+                *self = copy;
+
+                Some(result)
+            }
+        }
+    )* };
+}
+
+shared_slice!(Rc, Arc);
+
+
+#[test]
+fn test_shared() {
+    use crate::Cps;
+
+    let mut foo: Rc<[i32]> = Rc::from(&[1, 2][..]);
+    let bar = foo.clone();
+
+    assert!(foo.at(()).at(0).replace(3) == Some(1));
+    assert!(*foo == [3, 2]);
+    assert!(*bar == [1, 2]);
+
+    let ptr = foo.as_ptr();
+    assert!(foo.at(()).at(1).replace(4) == Some(2));
+    assert!(foo.as_ptr() == ptr);
+
+    let mut baz: Arc<str> = Arc::from("abc");
+    let quux = baz.clone();
+
+    baz.at(()).access(|s| s.make_ascii_uppercase());
+    assert!(&*baz == "ABC");
+    assert!(&*quux == "abc");
+}
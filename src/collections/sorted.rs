@@ -0,0 +1,153 @@
+use crate::at::At;
+use core::cmp::Ordering;
+use alloc::vec::Vec;
+
+
+/// A sorted view of a sequence.
+///
+/// `Sorted(compare)` presents the items sorted (stably) by the comparator.
+/// After the access every item is moved back to its original position,
+/// thus the mutations made through the view are written to the right places.
+/// The order is restored even if the accessing closure panics.
+///
+/// The view is a slice: the number of items can't change.
+/// The access takes O(n log n) time and O(n) additional memory
+/// (for the index permutation); the items themselves are only swapped.
+///
+/// Implemented for slices and for `Vec`.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::Cps;
+/// use smart_access::collections::Sorted;
+///
+/// // (player, score)
+/// let mut board = vec![("alice", 30), ("bob", 50), ("carol", 10), ("dave", 40)];
+///
+/// // awards the bonus to the top two players
+/// board.at(Sorted(|a: &(&str, i32), b: &(&str, i32)| b.1.cmp(&a.1))).access(|top| {
+///     for player in &mut top[..2] { player.1 += 5; }
+/// });
+///
+/// assert!(board == vec![("alice", 30), ("bob", 55), ("carol", 10), ("dave", 45)]);
+///
+/// // the view can be navigated further
+/// let lowest = board.at(Sorted(|a: &(&str, i32), b: &(&str, i32)| a.1.cmp(&b.1))).at(0).get_clone();
+/// assert!(lowest == Some(("carol", 10)));
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Sorted<C>(pub C);
+
+/// `access_at` is guaranteed to return `Some(f(..))`
+impl<T, C> At<Sorted<C>> for [T] where
+    C: FnMut(&T, &T) -> Ordering
+{
+    type View = [T];
+
+    fn access_at<R, F>(&mut self, i: Sorted<C>, f: F) -> Option<R> where
+        F: FnOnce(&mut [T]) -> R
+    {
+        let mut compare = i.0;
+
+        // `order[k]` is the original position of the `k`-th item of the view
+        let mut order: Vec<usize> = (0..self.len()).collect();
+        order.sort_by(|&a, &b| compare(&self[a], &self[b]));
+
+        permute(self, &order);
+
+        let mut inverse = order;
+        invert(&mut inverse);
+
+        let guard = Unsort { xs: self, inverse };
+
+        Some(f(guard.xs))
+    }
+}
+
+/// `access_at` is guaranteed to return `Some(f(..))`
+impl<T, C> At<Sorted<C>> for Vec<T> where
+    C: FnMut(&T, &T) -> Ordering
+{
+    type View = [T];
+
+    fn access_at<R, F>(&mut self, i: Sorted<C>, f: F) -> Option<R> where
+        F: FnOnce(&mut [T]) -> R
+    {
+        self[..].access_at(i, f)
+    }
+}
+
+
+// Moves the items back to their original positions on drop, i.e. even if `f` panics.
+struct Unsort<'a, T> {
+    xs: &'a mut [T],
+    inverse: Vec<usize>,
+}
+
+impl<T> Drop for Unsort<'_, T> {
+    fn drop(&mut self) {
+        permute(self.xs, &self.inverse);
+    }
+}
+
+
+// Rearranges the items so that `xs[k]` becomes the old `xs[p[k]]`.
+fn permute<T>(xs: &mut [T], p: &[usize]) {
+    let mut done = alloc::vec![false; p.len()];
+
+    for start in 0..p.len() {
+        if done[start] { continue; }
+
+        let mut k = start;
+
+        loop {
+            done[k] = true;
+
+            let next = p[k];
+            if next == start { break; }
+
+            xs.swap(k, next);
+            k = next;
+        }
+    }
+}
+
+// Replaces a permutation with its inverse.
+fn invert(p: &mut [usize]) {
+    let mut q = alloc::vec![0; p.len()];
+
+    for (k, &i) in p.iter().enumerate() { q[i] = k; }
+
+    p.copy_from_slice(&q);
+}
+
+
+#[test]
+fn test_sorted() {
+    use crate::Cps;
+    use alloc::vec;
+
+    let mut foo = vec![3, 1, 4, 1, 5, 9, 2, 6];
+    let mut seen = vec![];
+
+    foo.at(Sorted(|a: &i32, b: &i32| a.cmp(b))).access(|xs| {
+        seen.extend_from_slice(xs);
+        for (k, x) in xs.iter_mut().enumerate() { *x = 10 * *x + k as i32; }
+    });
+
+    assert!(seen == vec![1, 1, 2, 3, 4, 5, 6, 9]);
+    assert!(foo == vec![33, 10, 44, 11, 55, 97, 22, 66]);
+
+    // the sort is stable
+    let mut bar = [(1, 'a'), (0, 'b'), (1, 'c'), (0, 'd')];
+    let by_key = |a: &(i32, char), b: &(i32, char)| a.0.cmp(&b.0);
+
+    assert!(bar[..].at(Sorted(by_key)).access(|xs| xs.iter().map(|x| x.1).collect::<alloc::string::String>())
+        == Some("bdac".into()));
+    assert!(bar[..].at(Sorted(by_key)).at(3).replace((2, 'e')) == Some((1, 'c')));
+    assert!(bar == [(1, 'a'), (0, 'b'), (2, 'e'), (0, 'd')]);
+
+    let mut empty: [i32; 0] = [];
+    assert!(empty[..].at(Sorted(|a: &i32, b: &i32| a.cmp(b))).access(|xs| xs.len()) == Some(0));
+}
@@ -0,0 +1,57 @@
+use crate::At;
+use alloc::string::String;
+
+
+/// An index of the `n`-th (counting from zero) non-overlapping occurrence
+/// of a substring.
+///
+/// The view is a copy of the occurrence. If it is changed then it's
+/// spliced back in place of the occurrence. Returns `None` if there are
+/// fewer occurrences.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::Cps;
+/// use smart_access::collections::Occurrence;
+///
+/// let mut text = "to be or not to be".to_string();
+///
+/// assert!(text.at(Occurrence("be", 1)).replace("see".into()) == Some("be".into()));
+/// assert!(text.at(Occurrence("to", 2)).touch() == None);
+///
+/// assert!(text == "to be or not to see");
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Occurrence<'a>(pub &'a str, pub usize);
+
+impl At<Occurrence<'_>> for String {
+    type View = String;
+
+    fn access_at<R, F>(&mut self, i: Occurrence<'_>, f: F) -> Option<R> where
+        F: FnOnce(&mut String) -> R
+    {
+        let (start, found) = self.match_indices(i.0).nth(i.1)?;
+        let end = start + found.len();
+
+        let mut view = String::from(found);
+        let result = f(&mut view);
+
+        if view != i.0 { self.replace_range(start..end, &view); }
+
+        Some(result)
+    }
+}
+
+
+#[test]
+fn test_occurrence() {
+    use crate::Cps;
+
+    let mut foo = String::from("aaaa");
+
+    assert!(foo.at(Occurrence("aa", 1)).replace("b".into()) == Some("aa".into()));
+    assert!(foo.at(Occurrence("aa", 1)).touch().is_none());
+    assert!(foo.at(Occurrence("a", 0)).access(|s| s.push('c')) == Some(()));
+    assert!(foo == "acab");
+}
@@ -1,6 +1,8 @@
 use crate::at::At;
-use core::ops;
+use crate::core_impls::{ Rotated, AsUtf8, Position, Offset, Scaled, Reversed };
+use core::ops::{ self, Bound, RangeBounds };
 use alloc::vec::Vec;
+use alloc::string::String;
 
 
 impl<T> At<()> for Vec<T> 
@@ -27,6 +29,34 @@ impl<T> At<usize> for Vec<T>
 }
 
 
+impl<T> At<Rotated> for Vec<T> 
+{
+    type View = [T];
+
+    fn access_at<R, F>(&mut self, i: Rotated, f: F) -> Option<R> where 
+        F: FnOnce(&mut Self::View) -> R 
+    {
+        (self as &mut [T]).access_at(i,f)
+    }
+}
+
+
+macro_rules! impl_position_adapters {
+    ( $($adapter:ident),* ) => { $(
+        impl<T, I: Position> At<$adapter<I>> for Vec<T> {
+            type View = T;
+
+            fn access_at<R, F>(&mut self, i: $adapter<I>, f: F) -> Option<R> where
+                F: FnOnce(&mut T) -> R
+            {
+                (self as &mut [T]).access_at(i, f)
+            }
+        }
+    )* };
+}
+
+impl_position_adapters!(Offset, Scaled, Reversed);
+
 impl<T> At<ops::Range<usize>> for Vec<T> {
     type View = Vec<T>;
     
@@ -36,15 +66,7 @@ impl<T> At<ops::Range<usize>> for Vec<T> {
         if i.end > self.len() { return None; }
         if i.start > i.end    { return None; }
 
-        let right_part   = self.split_off(i.end);
-        let mut mid_part = self.split_off(i.start);
-
-        let result = f(&mut mid_part);
-        
-        self.extend(mid_part);
-        self.extend(right_part);
-
-        Some(result)
+        Some(splice_access(self, i.start, i.end, f))
     }
 }
 
@@ -57,13 +79,9 @@ impl<T> At<ops::RangeFrom<usize>> for Vec<T> {
     {
         if i.start > self.len() { return None; }
 
-        let mut mid_part = self.split_off(i.start);
+        let end = self.len();
 
-        let result = f(&mut mid_part);
-        
-        self.extend(mid_part);
-
-        Some(result)
+        Some(splice_access(self, i.start, end, f))
     }
 }
 
@@ -92,15 +110,7 @@ impl<T> At<ops::RangeInclusive<usize>> for Vec<T> {
         // overflow is prevented by the previous line
         if start > end+1   { return None; }
 
-        let right_part   = self.split_off(end+1);
-        let mut mid_part = self.split_off(start);
-
-        let result = f(&mut mid_part);
-        
-        self.extend(mid_part);
-        self.extend(right_part);
-
-        Some(result)
+        Some(splice_access(self, start, end+1, f))
     }
 }
 
@@ -113,13 +123,7 @@ impl<T> At<ops::RangeTo<usize>> for Vec<T> {
     {
         if i.end > self.len() { return None; }
 
-        let right_part = self.split_off(i.end);
-
-        let result = f(self);
-        
-        self.extend(right_part);
-
-        Some(result)
+        Some(splice_access(self, 0, i.end, f))
     }
 }
 
@@ -132,12 +136,435 @@ impl<T> At<ops::RangeToInclusive<usize>> for Vec<T> {
     {
         if i.end >= self.len() { return None; }
 
-        let right_part = self.split_off(i.end+1);
+        Some(splice_access(self, 0, i.end+1, f))
+    }
+}
+
+
+/// An index appending items to a vector.
+///
+/// The view is the newly appended tail.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::Cps;
+/// use smart_access::collections::Append;
+///
+/// let mut rows = vec![vec![0; 2]];
+///
+/// rows.at(Append(vec![vec![1; 2], vec![2; 2]])).at(1).at(0).replace(3);
+/// assert!(rows == vec![vec![0, 0], vec![1, 1], vec![3, 2]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Append<I>(pub I);
+
+/// `access_at` is guaranteed to return `Some(f(..))`
+impl<T, I> At<Append<I>> for Vec<T> where
+    I: IntoIterator<Item=T>
+{
+    type View = [T];
+
+    fn access_at<R, F>(&mut self, i: Append<I>, f: F) -> Option<R> where 
+        F: FnOnce(&mut Self::View) -> R 
+    {
+        let start = self.len();
+
+        self.extend(i.0);
+
+        Some(f(&mut self[start..]))
+    }
+}
+
+
+/// A fallible version of the range accessors of `Vec<T>`.
+///
+/// All allocations are made before the access: the temporary vector and
+/// the `additional` capacity for growing the range. If any of them fails 
+/// then `access_at` returns `None` instead of panicking.
+///
+/// The view can still grow beyond `additional` elements but such growth
+/// is not guarded against Out Of Memory.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::Cps;
+/// use smart_access::collections::TryReserve;
+///
+/// let mut foo = vec![1, 2, 3];
+///
+/// let grown = foo.at(TryReserve { range: 1.., additional: 1 })
+///     .access(|xs| { xs.push(4); xs.len() });
+///
+/// assert!(grown == Some(3));
+/// assert!(foo == vec![1, 2, 3, 4]);
+///
+/// let too_much = foo.at(TryReserve { range: .., additional: usize::MAX })
+///     .access(|xs| xs.clear());
+///
+/// assert!(too_much.is_none());
+/// assert!(foo == vec![1, 2, 3, 4]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TryReserve<I> {
+    pub range: I,
+    pub additional: usize,
+}
+
+/// `access_at` returns `None` if the range is invalid or an allocation fails
+impl<T, I: RangeBounds<usize>> At<TryReserve<I>> for Vec<T> {
+    type View = Vec<T>;
+
+    fn access_at<R, F>(&mut self, i: TryReserve<I>, f: F) -> Option<R> where 
+        F: FnOnce(&mut Self::View) -> R 
+    {
+        let start = match i.range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1)?,
+            Bound::Unbounded        => 0,
+        };
+
+        let end = match i.range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1)?,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded      => self.len(),
+        };
+
+        if end > self.len() { return None; }
+        if start > end      { return None; }
+
+        let mut mid_part = Vec::new();
+        mid_part.try_reserve_exact((end - start).checked_add(i.additional)?).ok()?;
+        self.try_reserve(i.additional).ok()?;
+
+        mid_part.extend(self.drain(start..end));
+
+        let mut guard = SpliceBack { v: self, start, mid_part };
+
+        Some(f(&mut guard.mid_part))
+    }
+}
+
+
+/// A partitioning index: the items satisfying the predicate and the rest.
+///
+/// The view is a pair of vectors: the matching items and
+/// the non-matching ones (both in the original order).
+/// On exit the vector is reassembled: the items are placed back into
+/// the positions of their sides. If a side has grown its extra items
+/// are appended (the matching ones first). If a side has shrunk
+/// the positions left unfilled are skipped.
+///
+/// The reassembly happens even if the accessing closure panics.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::Cps;
+/// use smart_access::collections::PartitionBy;
+///
+/// let mut tasks = vec![(1, "urgent"), (2, "later"), (3, "urgent"), (4, "later")];
+///
+/// tasks.at(PartitionBy(|t: &(i32, &str)| t.1 == "urgent")).access(|(urgent, later)| {
+///     for t in urgent.iter_mut() { t.0 *= 10; }
+///     later.remove(0);
+///     later.push((5, "later"));
+/// });
+///
+/// assert!(tasks == vec![(10, "urgent"), (4, "later"), (30, "urgent"), (5, "later")]);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct PartitionBy<P>(pub P);
+
+/// `access_at` is guaranteed to return `Some(f(..))`
+impl<T, P> At<PartitionBy<P>> for Vec<T> where
+    P: FnMut(&T) -> bool
+{
+    type View = (Vec<T>, Vec<T>);
+
+    fn access_at<R, F>(&mut self, i: PartitionBy<P>, f: F) -> Option<R> where
+        F: FnOnce(&mut Self::View) -> R
+    {
+        let mut pred = i.0;
+
+        let mask: Vec<bool> = self.iter().map(&mut pred).collect();
+        let (mut matching, mut rest) = (Vec::new(), Vec::new());
+
+        for (x, &m) in self.drain(..).zip(&mask) {
+            if m { matching.push(x); } else { rest.push(x); }
+        }
+
+        let mut guard = Reassemble { v: self, mask, sides: (matching, rest) };
+
+        Some(f(&mut guard.sides))
+    }
+}
+
+// Reassembles the partitioned vector on drop, i.e. even if `f` panics.
+struct Reassemble<'a, T> {
+    v: &'a mut Vec<T>,
+    mask: Vec<bool>,
+    sides: (Vec<T>, Vec<T>),
+}
 
-        let result = f(self);
-        
-        self.extend(right_part);
+impl<T> Drop for Reassemble<'_, T> {
+    fn drop(&mut self) {
+        let (matching, rest) = core::mem::take(&mut self.sides);
+        let (mut matching, mut rest) = (matching.into_iter(), rest.into_iter());
 
-        Some(result)
+        self.v.reserve(matching.len() + rest.len());
+
+        for &m in &self.mask {
+            let side = if m { &mut matching } else { &mut rest };
+
+            if let Some(x) = side.next() { self.v.push(x); }
+        }
+
+        self.v.extend(matching);
+        self.v.extend(rest);
+    }
+}
+
+/// A sparse storage index: the value in an occupied slot of `Vec<Option<T>>`.
+///
+/// The sparse storage indices skip the `Option` layer:
+///
+/// * `Slot(i)` views the value; `access_at` returns `None` if the slot is
+///   vacant or out of bounds
+/// * `SlotOr(i, value)` puts the value into the slot if it is vacant
+///   (growing the vector with vacant slots as needed) then views
+///   the value in the slot; `access_at` returns `None` only if the index
+///   is `usize::MAX` (the vector can't be grown to hold it)
+/// * `Vacate(i)` takes the value out of the slot (if there is one) and views it;
+///   after the access the value is dropped and the vacant slots at the end
+///   of the vector are removed
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::Cps;
+/// use smart_access::collections::{ Slot, SlotOr, Vacate };
+///
+/// let mut slots: Vec<Option<Vec<i32>>> = vec![];
+///
+/// assert!(slots.at(SlotOr(2, vec![])).access(|xs| xs.push(1)) == Some(()));
+/// assert!(slots.at(SlotOr(2, vec![])).at(0).replace(2) == Some(1));
+/// assert!(slots.at(Slot(0)).touch() == None);
+/// assert!(slots == vec![None, None, Some(vec![2])]);
+///
+/// // the value can be moved out
+/// assert!(slots.at(Vacate(2)).access(core::mem::take) == Some(vec![2]));
+/// assert!(slots.is_empty());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Slot(pub usize);
+
+/// A sparse storage index ensuring that the slot is occupied.
+///
+/// See [`Slot`](struct.Slot.html) for details.
+#[derive(Debug, Clone)]
+pub struct SlotOr<T>(pub usize, pub T);
+
+/// A sparse storage index vacating the slot.
+///
+/// See [`Slot`](struct.Slot.html) for details.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Vacate(pub usize);
+
+/// `access_at` returns `None` if the slot is vacant or out of bounds
+impl<T> At<Slot> for Vec<Option<T>> {
+    type View = T;
+
+    fn access_at<R, F>(&mut self, i: Slot, f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        self.get_mut(i.0)?.as_mut().map(f)
     }
 }
+
+/// `access_at` returns `None` only if the index is `usize::MAX`
+impl<T> At<SlotOr<T>> for Vec<Option<T>> {
+    type View = T;
+
+    fn access_at<R, F>(&mut self, i: SlotOr<T>, f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        if self.len() <= i.0 { self.resize_with(i.0.checked_add(1)?, || None); }
+
+        Some(f(self[i.0].get_or_insert(i.1)))
+    }
+}
+
+/// `access_at` returns `None` if the slot is vacant or out of bounds
+impl<T> At<Vacate> for Vec<Option<T>> {
+    type View = T;
+
+    fn access_at<R, F>(&mut self, i: Vacate, f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        let mut value = self.get_mut(i.0)?.take()?;
+
+        while let Some(None) = self.last() { self.pop(); }
+
+        Some(f(&mut value))
+    }
+}
+
+// Moves the range out into a temporary vector and splices it back.
+//
+// The allocation of `v` is reused: only the temporary vector is allocated.
+fn splice_access<T, R, F>(v: &mut Vec<T>, start: usize, end: usize, f: F) -> R where
+    F: FnOnce(&mut Vec<T>) -> R
+{
+    let mid_part = v.drain(start..end).collect();
+
+    let mut guard = SpliceBack { v, start, mid_part };
+
+    f(&mut guard.mid_part)
+}
+
+// Splices the temporary vector back on drop, i.e. even if `f` panics.
+struct SpliceBack<'a, T> {
+    v: &'a mut Vec<T>,
+    start: usize,
+    mid_part: Vec<T>,
+}
+
+impl<T> Drop for SpliceBack<'_, T> {
+    fn drop(&mut self) {
+        let mid_part = core::mem::take(&mut self.mid_part);
+
+        self.v.splice(self.start..self.start, mid_part);
+    }
+}
+
+
+/// `access_at` returns `None` if the bytes are not valid UTF-8
+impl At<AsUtf8> for Vec<u8> {
+    type View = String;
+
+    fn access_at<R, F>(&mut self, _: AsUtf8, f: F) -> Option<R> where
+        F: FnOnce(&mut String) -> R
+    {
+        let text = match String::from_utf8(core::mem::take(self)) {
+            Ok(text) => text,
+            Err(e)   => { *self = e.into_bytes(); return None; }
+        };
+
+        let mut guard = Utf8Back { v: self, text };
+
+        Some(f(&mut guard.text))
+    }
+}
+
+// Moves the bytes of the string back on drop, i.e. even if `f` panics.
+struct Utf8Back<'a> {
+    v: &'a mut Vec<u8>,
+    text: String,
+}
+
+impl Drop for Utf8Back<'_> {
+    fn drop(&mut self) {
+        *self.v = core::mem::take(&mut self.text).into_bytes();
+    }
+}
+
+
+#[test]
+fn test_range_capacity() {
+    use crate::Cps;
+    use alloc::vec;
+
+    let mut foo = Vec::with_capacity(16);
+    foo.extend([1, 2, 3, 4]);
+
+    let ptr = foo.as_ptr();
+
+    assert!(foo.at(1..3).replace(vec![5, 6, 7]) == Some(vec![2, 3]));
+    assert!(foo.at(..=0).access(|xs| xs.clear()).is_some());
+    assert!(foo.at(3..).access(|xs| xs.push(8)).is_some());
+    assert!(foo.at(..2).access(|xs| xs.reverse()).is_some());
+
+    assert!(foo == vec![6, 5, 7, 4, 8]);
+    assert!(foo.as_ptr() == ptr);
+
+    assert!(foo.at(TryReserve { range: 1..=2, additional: 2 }).replace(vec![0; 4]) == Some(vec![5, 7]));
+    assert!(foo == vec![6, 0, 0, 0, 0, 4, 8]);
+    assert!(foo.as_ptr() == ptr);
+
+    assert!(foo.at(TryReserve { range: (Bound::Excluded(3), Bound::Included(2)), additional: 0 }).touch().is_none());
+    assert!(foo.at(TryReserve { range: 0..=usize::MAX, additional: 0 }).touch().is_none());
+    assert!(foo.at(TryReserve { range: 0..8, additional: 0 }).touch().is_none());
+    assert!(foo.at(TryReserve { range: 0..1, additional: isize::MAX as usize }).touch().is_none());
+    assert!(foo == vec![6, 0, 0, 0, 0, 4, 8]);
+}
+
+
+#[test]
+fn test_as_utf8() {
+    use crate::Cps;
+    use alloc::vec;
+
+    let mut foo = Vec::with_capacity(16);
+    foo.extend(b"abc");
+
+    let ptr = foo.as_ptr();
+
+    assert!(foo.at(AsUtf8).access(|s| { s.insert(0, 'é'); s.len() }) == Some(5));
+    assert!(foo.as_ptr() == ptr);
+
+    foo.push(0xc3);
+    assert!(foo.at(AsUtf8).touch().is_none());
+    assert!(foo[..].at(AsUtf8).touch().is_none());
+    assert!(foo == vec![0xc3, 0xa9, b'a', b'b', b'c', 0xc3]);
+}
+
+
+#[test]
+fn test_partition_by() {
+    use crate::Cps;
+    use alloc::vec;
+
+    let mut foo = vec![1, 2, 3, 4, 5, 6];
+    let is_even = |x: &i32| x % 2 == 0;
+
+    let sizes = foo.at(PartitionBy(is_even)).access(|(even, odd)| {
+        let sizes = (even.len(), odd.len());
+
+        even.truncate(1);
+        odd.insert(0, 0);
+
+        sizes
+    });
+
+    assert!(sizes == Some((3, 3)));
+    assert!(foo == vec![0, 2, 1, 3, 5]);
+
+    assert!(foo.at(PartitionBy(|_: &i32| false)).access(|p| p.0.push(7)) == Some(()));
+    assert!(foo == vec![0, 2, 1, 3, 5, 7]);
+}
+
+
+#[test]
+fn test_sparse() {
+    use crate::Cps;
+    use alloc::vec;
+
+    let mut foo = vec![Some(1), None, Some(3)];
+
+    assert!(foo.at(Slot(0)).replace(2) == Some(1));
+    assert!(foo.at(Slot(1)).touch().is_none());
+    assert!(foo.at(Slot(5)).touch().is_none());
+    assert!(foo.at(SlotOr(1, 4)).get_clone() == Some(4));
+    assert!(foo.at(SlotOr(1, 5)).get_clone() == Some(4));
+    assert!(foo.at(SlotOr(usize::MAX, 6)).touch().is_none());
+
+    assert!(foo.at(Vacate(1)).get_clone() == Some(4));
+    assert!(foo.at(Vacate(1)).touch().is_none());
+    assert!(foo == vec![Some(2), None, Some(3)]);
+
+    assert!(foo.at(Vacate(2)).touch() == Some(()));
+    assert!(foo.at(Vacate(7)).touch().is_none());
+    assert!(foo == vec![Some(2)]);
+}
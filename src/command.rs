@@ -0,0 +1,170 @@
+//! Mutation commands. __Requires `command`.__
+//!
+//! A [`Command`](struct.Command.html) is a [runtime path](../struct.DynPath.html)
+//! paired with an [operation](enum.Op.html). Commands are plain data: they can
+//! be logged, persisted (with the `serde` feature enabled both commands and
+//! runtime paths are serializable) and replayed later.
+//!
+//! Commands operate on self-similar datatypes: every node `T` of a tree
+//! must implement `At<I, View=T>`. The `Insert` and `Remove` operations
+//! additionally require the [`Edit`](trait.Edit.html) trait.
+//!
+//! ```
+//! use smart_access::{ At, DynPath };
+//! use smart_access::command::{ Command, Edit };
+//!
+//! #[derive(Debug, Clone, PartialEq)]
+//! enum Tree { Leaf(i32), Node(Vec<Tree>) }
+//!
+//! impl At<usize> for Tree {
+//!     type View = Tree;
+//!
+//!     fn access_at<R, F>(&mut self, i: usize, f: F) -> Option<R> where
+//!         F: FnOnce(&mut Tree) -> R
+//!     {
+//!         match self {
+//!             Tree::Node(children) => children.get_mut(i).map(f),
+//!             Tree::Leaf(_) => None,
+//!         }
+//!     }
+//! }
+//!
+//! impl Edit<usize> for Tree {
+//!     fn insert_at(&mut self, i: usize, child: Tree) -> bool {
+//!         match self {
+//!             Tree::Node(children) if i <= children.len() => {
+//!                 children.insert(i, child); true
+//!             }
+//!             _ => false,
+//!         }
+//!     }
+//!
+//!     fn remove_at(&mut self, i: usize) -> Option<Tree> {
+//!         match self {
+//!             Tree::Node(children) if i < children.len() => Some(children.remove(i)),
+//!             _ => None,
+//!         }
+//!     }
+//! }
+//!
+//! let mut tree = Tree::Node(vec![ Tree::Leaf(1), Tree::Node(vec![]) ]);
+//!
+//! let log = vec![
+//!     Command::replace(DynPath::new().at(0), Tree::Leaf(2)),
+//!     Command::insert(DynPath::new().at(1).at(0), Tree::Leaf(3)),
+//!     Command::remove(DynPath::new().at(5)),
+//! ];
+//!
+//! let applied = log.iter().cloned().map(|cmd| cmd.apply(&mut tree)).collect::<Vec<_>>();
+//!
+//! assert!(applied == vec![true, true, false]);
+//! assert!(tree == Tree::Node(vec![ Tree::Leaf(2), Tree::Node(vec![ Tree::Leaf(3) ]) ]));
+//! ```
+
+use crate::at::{ At, Cps };
+use crate::DynPath;
+
+#[cfg(feature="serde")]
+use serde::{ Serialize, Deserialize };
+
+
+/// Structural editing of a self-similar datatype.
+pub trait Edit<I>: At<I, View=Self> + Sized {
+    /// Inserts a child at the index. Returns `false` if it is impossible.
+    fn insert_at(&mut self, i: I, child: Self) -> bool;
+
+    /// Removes the child at the index.
+    fn remove_at(&mut self, i: I) -> Option<Self>;
+}
+
+
+/// An operation performed by a [`Command`](struct.Command.html).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub enum Op<T> {
+    /// Replaces the node at the path.
+    Replace(T),
+
+    /// Inserts a node at the path. The last index of the path
+    /// is passed to [`Edit::insert_at`](trait.Edit.html#tymethod.insert_at).
+    Insert(T),
+
+    /// Removes the node at the path. The last index of the path
+    /// is passed to [`Edit::remove_at`](trait.Edit.html#tymethod.remove_at).
+    Remove,
+}
+
+
+/// A serializable mutation: a runtime path and an operation.
+///
+/// See the [module-level documentation](index.html) for an example.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature="serde", derive(Serialize, Deserialize))]
+pub struct Command<I, T> {
+    pub path: DynPath<I>,
+    pub op: Op<T>,
+}
+
+impl<I, T> Command<I, T> {
+    /// A command replacing the node at the path.
+    pub fn replace(path: DynPath<I>, value: T) -> Self {
+        Command { path, op: Op::Replace(value) }
+    }
+
+    /// A command inserting a node at the path.
+    pub fn insert(path: DynPath<I>, value: T) -> Self {
+        Command { path, op: Op::Insert(value) }
+    }
+
+    /// A command removing the node at the path.
+    pub fn remove(path: DynPath<I>) -> Self {
+        Command { path, op: Op::Remove }
+    }
+
+    /// Applies the command to a root node.
+    ///
+    /// Returns `false` if the command couldn't be applied. In such a case
+    /// the root is left untouched.
+    pub fn apply(self, root: &mut T) -> bool where
+        T: Edit<I>
+    {
+        let mut path = self.path;
+
+        match self.op {
+            Op::Replace(value) => root.at_dyn(path).set(value),
+
+            Op::Insert(value) => match path.pop() {
+                None => false,
+                Some(i) => root.at_dyn(path)
+                    .access(|parent| parent.insert_at(i, value))
+                    .unwrap_or(false),
+            },
+
+            Op::Remove => match path.pop() {
+                None => false,
+                Some(i) => root.at_dyn(path)
+                    .access(|parent| parent.remove_at(i).is_some())
+                    .unwrap_or(false),
+            },
+        }
+    }
+}
+
+
+#[test]#[cfg(feature="serde")]
+fn test_command_serialization() {
+    extern crate std;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    let log = vec![
+        Command::replace(DynPath::new().at(1), vec![2]),
+        Command::remove(DynPath::new().at(0)),
+    ];
+
+    let json = serde_json::to_string(&log).unwrap();
+    assert!(json == r#"[{"path":[1],"op":{"Replace":[2]}},{"path":[0],"op":"Remove"}]"#);
+
+    let replayed: Vec<Command<usize, Vec<i32>>> = serde_json::from_str(&json).unwrap();
+    assert!(replayed == log);
+}
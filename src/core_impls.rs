@@ -3,10 +3,108 @@
 //! The following traits are implemented:
 //! * `At<usize, View=T> for [T]`: simple indexing
 //! * `At<range, View=[T]> for [T]`: subslice (of fixed size)
+//! * `At<Rotated, View=[T]> for [T]`: the whole slice [rotated](struct.Rotated.html)
+//!   to the left for the duration of the access
+//! * `At<AsUtf8, View=str> for [u8]`: the bytes [as text](struct.AsUtf8.html)
+//!   (if they are valid UTF-8)
+//! * `At<Offset<I>, View=T>`, `At<Scaled<I>, View=T>` and `At<Reversed<I>, View=T> for [T]`:
+//!   simple indexing by a [transformed position](trait.Position.html)
 //! * `At<(), View=T> for Option<T>`: the only meaningful sort of access
+//! * `At<(T,), View=T> for Option<T>`: ensure that the value is present
+//!   (using the provided default) then access it
 //! * `At<(), View=R> for Result<R,E>`: access to the `Ok` value
+//! * `At<(), View=C> for ControlFlow<B,C>`: access to the `Continue` value
+//! * `At<BreakP, View=B> for ControlFlow<B,C>`: access to the `Break` value
 //!
-//! All implementations never panic: `None` is returned instead if the 
+//! Named markers are also provided as readable alternatives to `()`:
+//! * `At<SomeP, View=T> for Option<T>`: the same as `At<()>`
+//! * `At<OkP, View=R> for Result<R,E>`: the same as `At<()>`
+//! * `At<ErrP, View=E> for Result<R,E>`: access to the `Err` value
+//!
+//! The [`Flatten`](struct.Flatten.html) marker collapses two optional layers:
+//! * `At<Flatten, View=T> for Option<Option<T>>`: the same as `.at(()).at(())`
+//! * `At<Flatten, View=T> for Result<Option<T>,E>`: the same as `.at(()).at(())`
+//!
+//! ```
+//! # use smart_access::Cps;
+//! use smart_access::core_impls::{ SomeP, OkP, ErrP };
+//!
+//! let mut foo: Option<Result<i32, String>> = Some(Err("oops".into()));
+//!
+//! assert!(foo.at(SomeP).at(OkP).touch() == None);
+//! assert!(foo.at(SomeP).at(ErrP).access(|e| e.push('!')) == Some(()));
+//! assert!(foo == Some(Err("oops!".into())));
+//!
+//! use smart_access::core_impls::Flatten;
+//!
+//! // `None`: not loaded, `Some(None)`: loaded but missing
+//! let mut cache: Vec<Option<Option<i32>>> = vec![ Some(Some(1)), Some(None), None ];
+//!
+//! assert!(cache.at(0).at(Flatten).replace(2) == Some(1));
+//! assert!(cache.at(1).at(Flatten).replace(3) == None);
+//! assert!(cache.at(2).at(Flatten).replace(4) == None);
+//! assert!(cache == vec![ Some(Some(2)), Some(None), None ]);
+//! ```
+//!
+//! The [`Idx`](struct.Idx.html) adapter makes any container
+//! implementing `IndexMut` usable in paths:
+//! * `At<Idx<I>, View=T::Output> for T where T: IndexMut<I>`: delegates
+//!   to `index_mut`; __panics__ if the container panics
+//!
+//! ```
+//! # use smart_access::Cps;
+//! use smart_access::core_impls::Idx;
+//! use std::collections::VecDeque;
+//!
+//! let mut foo: VecDeque<Vec<i32>> = vec![ vec![1, 2] ].into();
+//!
+//! assert!(foo.at(Idx(0)).at(1).replace(3) == Some(2));
+//! assert!(foo[0] == vec![1, 3]);
+//! ```
+//!
+//! The [`Deref`](struct.Deref.html) marker steps through smart pointers:
+//! * `At<Deref, View=T::Target> for T where T: DerefMut`
+//!
+//! ```
+//! # use smart_access::Cps;
+//! use smart_access::core_impls::Deref;
+//!
+//! let mut foo = vec![ Box::new(Some(1)) ];
+//!
+//! assert!(foo.at(0).at(Deref).at(()).replace(2) == Some(1));
+//! assert!(*foo[0] == Some(2));
+//! ```
+//!
+//! The [`OrInsert`](struct.OrInsert.html) adapter (usually created by
+//! [`or_insert_at`](fn.or_insert_at.html)) solves the `or_insert` problem
+//! from the [crate-level documentation](../index.html) for any map-like type:
+//! * `At<OrInsert<K,V,Ins>, View=V> for M where M: At<&K, View=V>`: access
+//!   the value if it is present, otherwise insert it with `Ins` then access it
+//!
+//! Numeric proxies (the closure sees the value, the write-back adjusts it):
+//! * `At<Clamped<T>, View=T> for T`: clamps the value into a range
+//! * `At<Normalized, View=[T]> for [T]` (for `f32` and `f64`): rescales
+//!   the numbers to make their sum equal to `1`
+//!
+//! ```
+//! # use smart_access::Cps;
+//! use smart_access::core_impls::{ Clamped, Normalized };
+//!
+//! let mut volume = vec![50, 80];
+//! volume.at(1).at(Clamped(0..=100)).access(|v| *v += 30);
+//! assert!(volume == vec![50, 100]);
+//!
+//! let mut weights = vec![0.5f64, 0.5];
+//! weights.at(()).at(Normalized).access(|w| w[1] = 1.5);
+//! assert!(weights == vec![0.25, 0.75]);
+//! ```
+//!
+//! A generic [`Proxy`](struct.Proxy.html) converts the view into
+//! another representation and back:
+//! * `At<Proxy<To,From>, View=U> for T where To: FnOnce(&T) -> Option<U>,
+//!   From: FnOnce(U) -> Option<T>`
+//!
+//! All other implementations never panic: `None` is returned instead if the 
 //! index doesn't make sense. If you want panicking behaviour simply 
 //! add `.unwrap()` to your access:
 //!
@@ -26,6 +124,14 @@
 //! ```
 
 mod slice;
+mod numeric;
+mod proxy;
+mod position;
+
+pub use slice::{ Rotated, AsUtf8 };
+pub use numeric::{ Clamped, Normalized };
+pub use proxy::{ Proxy, iso };
+pub use position::{ Position, Offset, Scaled, Reversed };
 
 #[test]#[cfg(feature="alloc")]
 fn test_slice() {
@@ -64,10 +170,28 @@ fn test_slice() {
     assert!(foo == vec![9,6,7,0,1]);
 }
 
+#[test]#[cfg(feature="alloc")]
+fn test_rotated_panic() {
+    extern crate std;
+    use std::panic::{ catch_unwind, AssertUnwindSafe };
+    use crate::Cps;
+    use alloc::vec;
+
+    let mut foo = vec![4,5,1,2,3];
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        foo[..].at(Rotated(2)).access(|xs| { xs[0] = 0; std::panic!() })
+    }));
+
+    assert!(result.is_err());
+    assert!(foo == vec![4,5,0,2,3]);
+}
+
 
 // Other implementations
 
-use crate::At;
+use crate::{ At, AT, Cps };
+use core::ops::{ ControlFlow, IndexMut, DerefMut };
 
 impl<T> At<()> for Option<T> {
     type View = T;
@@ -82,6 +206,17 @@ impl<T> At<()> for Option<T> {
     }
 }
 
+/// `access_at` is guaranteed to return `Some(f(..))`
+impl<T> At<(T,)> for Option<T> {
+    type View = T;
+
+    fn access_at<R, F>(&mut self, default: (T,), f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        Some(f(self.get_or_insert(default.0)))
+    }
+}
+
 impl<T,S> At<()> for Result<T,S> {
     type View = T;
 
@@ -96,6 +231,244 @@ impl<T,S> At<()> for Result<T,S> {
 }
 
 
+/// An adapter for containers implementing `IndexMut`.
+///
+/// __Panics__ if the container panics (usually on an invalid index).
+/// Use a dedicated `At` implementation if failures must be reported
+/// as `None`.
+#[repr(transparent)]#[derive(Debug, Copy, Clone)]
+pub struct Idx<I>(pub I);
+
+/// `access_at` is guaranteed to return `Some(f(..))` or panic
+impl<T, I> At<Idx<I>> for T where
+    T: IndexMut<I> + ?Sized
+{
+    type View = T::Output;
+
+    fn access_at<R, F>(&mut self, i: Idx<I>, f: F) -> Option<R> where
+        F: FnOnce(&mut T::Output) -> R
+    {
+        Some(f(self.index_mut(i.0)))
+    }
+}
+
+
+/// A marker index for the target of a smart pointer.
+#[derive(Debug, Copy, Clone)]
+pub struct Deref;
+
+/// `access_at` is guaranteed to return `Some(f(..))`
+impl<T> At<Deref> for T where
+    T: DerefMut + ?Sized
+{
+    type View = T::Target;
+
+    fn access_at<R, F>(&mut self, _: Deref, f: F) -> Option<R> where
+        F: FnOnce(&mut T::Target) -> R
+    {
+        Some(f(self.deref_mut()))
+    }
+}
+
+
+/// An adapter ensuring that a key is present in a map-like type.
+///
+/// The `insert` function is called only if the key is missing.
+#[derive(Debug, Copy, Clone)]
+pub struct OrInsert<K, V, Ins> {
+    pub key: K,
+    pub value: V,
+    pub insert: Ins,
+}
+
+/// `access_at` returns `None` only if the key is missing after the insertion
+/// or if the access of a present key fails (then nothing is inserted)
+impl<M, K, V, Ins> At<OrInsert<K, V, Ins>> for M where
+    M: for<'k> At<&'k K, View=V> + ?Sized,
+    K: Clone,
+    Ins: FnOnce(&mut M, K, V)
+{
+    type View = V;
+
+    fn access_at<R, F>(&mut self, i: OrInsert<K, V, Ins>, f: F) -> Option<R> where
+        F: FnOnce(&mut V) -> R
+    {
+        let mut f = Some(f);
+
+        let result = self.access_at(&i.key, |v| (f.take().unwrap())(v));
+        if result.is_some() { return result; }
+
+        // the closure has run but the access failed: nothing to insert
+        let f = f?;
+
+        (i.insert)(self, i.key.clone(), i.value);
+
+        self.access_at(&i.key, f)
+    }
+}
+
+/// Ensures that a key is present in a map-like type then accesses the value.
+///
+/// A generic version of the `or_insert` function from
+/// the [crate-level documentation](../index.html):
+///
+/// ```
+/// use smart_access::Cps;
+/// use smart_access::core_impls::or_insert_at;
+/// use std::collections::BTreeMap;
+///
+/// fn or_insert<'a>(map: &'a mut BTreeMap<usize, i32>, k: usize, v: i32) -> impl Cps<View=i32> + 'a {
+///     or_insert_at(map, k, v, |m, k, v| { m.insert(k, v); })
+/// }
+///
+/// let mut map = BTreeMap::new();
+///
+/// assert!(or_insert(&mut map, 1, 2).access(|v| { *v += 1; *v }) == Some(3));
+/// assert!(or_insert(&mut map, 1, 5).get_clone() == Some(3));
+/// ```
+pub fn or_insert_at<CPS, K, V, Ins>(cps: CPS, key: K, value: V, insert: Ins) -> AT<CPS, ((), OrInsert<K, V, Ins>)> where
+    CPS: Cps,
+    CPS::View: At<OrInsert<K, V, Ins>, View=V>
+{
+    cps.at(OrInsert { key, value, insert })
+}
+
+
+/// A marker index for the `Some` value of an `Option`.
+#[derive(Debug, Copy, Clone)]
+pub struct SomeP;
+
+/// A marker index for the `Ok` value of a `Result`.
+#[derive(Debug, Copy, Clone)]
+pub struct OkP;
+
+/// A marker index for the `Err` value of a `Result`.
+#[derive(Debug, Copy, Clone)]
+pub struct ErrP;
+
+impl<T> At<SomeP> for Option<T> {
+    type View = T;
+
+    fn access_at<R, F>(&mut self, _: SomeP, f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        self.access_at((), f)
+    }
+}
+
+impl<T,S> At<OkP> for Result<T,S> {
+    type View = T;
+
+    fn access_at<R, F>(&mut self, _: OkP, f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        self.access_at((), f)
+    }
+}
+
+impl<T,S> At<ErrP> for Result<T,S> {
+    type View = S;
+
+    fn access_at<R, F>(&mut self, _: ErrP, f: F) -> Option<R> where
+        F: FnOnce(&mut S) -> R
+    {
+        match self {
+            Ok(_)  => None,
+            Err(x) => Some(f(x)),
+        }
+    }
+}
+
+
+/// A marker index collapsing two optional layers.
+///
+/// Not to be confused with the [traversal](../traversal/struct.Flatten.html)
+/// of nested containers.
+#[derive(Debug, Copy, Clone)]
+pub struct Flatten;
+
+impl<T> At<Flatten> for Option<Option<T>> {
+    type View = T;
+
+    fn access_at<R, F>(&mut self, _: Flatten, f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        self.as_mut()?.as_mut().map(f)
+    }
+}
+
+impl<T,S> At<Flatten> for Result<Option<T>,S> {
+    type View = T;
+
+    fn access_at<R, F>(&mut self, _: Flatten, f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        self.as_mut().ok()?.as_mut().map(f)
+    }
+}
+
+
+impl<B,C> At<()> for ControlFlow<B,C> {
+    type View = C;
+
+    fn access_at<R, F>(&mut self, _: (), f: F) -> Option<R> where
+        F: FnOnce(&mut C) -> R
+    {
+        match self {
+            ControlFlow::Continue(x) => Some(f(x)),
+            ControlFlow::Break(_)    => None,
+        }
+    }
+}
+
+
+/// A marker index for the `Break` value of a `ControlFlow`.
+#[derive(Debug, Copy, Clone)]
+pub struct BreakP;
+
+impl<B,C> At<BreakP> for ControlFlow<B,C> {
+    type View = B;
+
+    fn access_at<R, F>(&mut self, _: BreakP, f: F) -> Option<R> where
+        F: FnOnce(&mut B) -> R
+    {
+        match self {
+            ControlFlow::Continue(_) => None,
+            ControlFlow::Break(x)    => Some(f(x)),
+        }
+    }
+}
+
+
+#[test]#[cfg(feature="alloc")]
+fn test_or_insert() {
+    use crate::Cps;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    // runs the closure on a copy of the value and rejects the write-back
+    struct Rejecting(Vec<(u8, u8)>);
+
+    impl At<&u8> for Rejecting {
+        type View = u8;
+
+        fn access_at<R, F>(&mut self, k: &u8, f: F) -> Option<R> where
+            F: FnOnce(&mut u8) -> R
+        {
+            let mut v = self.0.iter().find(|e| e.0 == *k)?.1;
+            f(&mut v);
+
+            None
+        }
+    }
+
+    let mut foo = Rejecting(vec![(1, 2)]);
+    let insert = |m: &mut Rejecting, k, v| m.0.push((k, v));
+
+    assert!(foo.at(OrInsert { key: 1, value: 3, insert }).touch().is_none());
+    assert!(foo.0 == vec![(1, 2)]);
+}
+
 #[test]
 fn test_optional() {
     use crate::Cps;
@@ -108,6 +481,20 @@ fn test_optional() {
     assert!(bar.at(()).replace(2) == None);
     assert!(bar == None);
 
+    assert!(bar.at((3,)).replace(4) == Some(3));
+    assert!(bar == Some(4));
+    assert!(foo.at((5,)).replace(6) == Some(1));
+    assert!(foo == Some(6));
+    assert!(foo.at(SomeP).replace(7) == Some(6));
+
+    let mut baz = [1, 2, 3];
+    assert!(baz.at(Idx(1..)).at(0).replace(4) == Some(2));
+    assert!(baz == [1, 4, 3]);
+
+    let mut boxed = Some(&mut baz[..]);
+    assert!(boxed.at(()).at(Deref).at(2).replace(5) == Some(3));
+    assert!(baz == [1, 4, 5]);
+
     let mut foo: Result<i32,i32> = Ok(0);
     let mut bar: Result<i32,i32> = Err(1);
     
@@ -115,6 +502,29 @@ fn test_optional() {
     assert!(foo == Ok(1));
     assert!(bar.at(()).replace(2) == None);
     assert!(bar == Err(1));
+
+    assert!(foo.at(OkP).replace(2) == Some(1));
+    assert!(foo.at(ErrP).replace(3).is_none());
+    assert!(bar.at(ErrP).replace(4) == Some(1));
+    assert!(bar == Err(4));
+
+    let mut foo: Option<Option<i32>> = Some(Some(0));
+    let mut bar: Result<Option<i32>,i32> = Ok(None);
+
+    assert!(foo.at(Flatten).replace(1) == Some(0));
+    assert!(foo == Some(Some(1)));
+    assert!(bar.at(Flatten).replace(2).is_none());
+    assert!(bar == Ok(None));
+
+    let mut foo: ControlFlow<i32,i32> = ControlFlow::Continue(0);
+    let mut bar: ControlFlow<i32,i32> = ControlFlow::Break(1);
+
+    assert!(foo.at(()).replace(1) == Some(0));
+    assert!(foo.at(BreakP).replace(2).is_none());
+    assert!(foo == ControlFlow::Continue(1));
+    assert!(bar.at(()).replace(2).is_none());
+    assert!(bar.at(BreakP).replace(3) == Some(1));
+    assert!(bar == ControlFlow::Break(3));
 }
 
 
@@ -0,0 +1,84 @@
+use crate::at::At;
+use core::ops::RangeInclusive;
+
+
+/// A proxy index clamping a value into a range after the access.
+///
+/// The closure sees the value itself, the write-back clamps it.
+/// An incomparable value (e.g. `NaN`) is left as is.
+#[derive(Debug, Clone)]
+pub struct Clamped<T>(pub RangeInclusive<T>);
+
+/// `access_at` is guaranteed to return `Some(f(..))`
+impl<T: PartialOrd + Copy> At<Clamped<T>> for T {
+    type View = T;
+
+    fn access_at<R, F>(&mut self, i: Clamped<T>, f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        let result = f(self);
+
+        let (min, max) = i.0.into_inner();
+
+        if *self < min { *self = min; }
+        if *self > max { *self = max; }
+
+        Some(result)
+    }
+}
+
+
+/// A proxy index rescaling numbers to make their sum equal to `1`
+/// after the access.
+///
+/// Useful for weights and probability distributions. If the sum is zero
+/// then the numbers are left as is.
+#[derive(Debug, Copy, Clone)]
+pub struct Normalized;
+
+macro_rules! normalized {
+    ( $($t:ty),* ) => { $(
+        /// `access_at` is guaranteed to return `Some(f(..))`
+        impl At<Normalized> for [$t] {
+            type View = [$t];
+
+            fn access_at<R, F>(&mut self, _: Normalized, f: F) -> Option<R> where
+                F: FnOnce(&mut [$t]) -> R
+            {
+                let result = f(self);
+
+                let sum: $t = self.iter().sum();
+
+                if sum != 0.0 {
+                    for x in self.iter_mut() { *x /= sum; }
+                }
+
+                Some(result)
+            }
+        }
+    )* };
+}
+
+normalized!(f32, f64);
+
+
+#[test]
+fn test_numeric_proxies() {
+    use crate::Cps;
+
+    let mut foo = [0.5f64, 0.5, 0.0];
+
+    assert!((&mut foo[..]).at(Normalized).at(2).replace(1.0) == Some(0.0));
+    assert!(foo == [0.25, 0.25, 0.5]);
+
+    (&mut foo[..]).at(Normalized).access(|xs| for x in xs { *x = 0.0; });
+    assert!(foo == [0.0, 0.0, 0.0]);
+
+    let mut bar = (1, 2.5);
+
+    assert!(bar.0.at(Clamped(0..=10)).access(|x| { *x += 15; *x }) == Some(16));
+    assert!(bar.0 == 10);
+
+    bar.1.at(Clamped(0.0..=1.0)).access(|x| *x = f64::NAN);
+    assert!(bar.1.is_nan());
+}
@@ -0,0 +1,137 @@
+use crate::at::At;
+
+
+/// A numeric index resolved against the length of a sequence.
+///
+/// Implemented for `usize` (the position itself) and for the index
+/// adapters [`Offset`](struct.Offset.html), [`Scaled`](struct.Scaled.html)
+/// and [`Reversed`](struct.Reversed.html). The adapters wrap another
+/// position, thus they can be nested: the innermost position is in the
+/// coordinates of the caller, each adapter converts it outwards.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::Cps;
+/// use smart_access::core_impls::{ Offset, Scaled, Reversed };
+///
+/// // a 1-based table from a spreadsheet
+/// let mut column = vec![10, 20, 30, 40];
+///
+/// assert!(column.at(Offset(-1, 1)).get_clone() == Some(10));
+/// assert!(column.at(Offset(-1, 0)).touch() == None);
+///
+/// // the bottom row is the row 1
+/// assert!(column.at(Reversed(Offset(-1, 1))).replace(45) == Some(40));
+/// assert!(column == vec![10, 20, 30, 45]);
+///
+/// // interleaved stereo samples: (left, right) pairs
+/// let mut samples = [0, 1, 2, 3, 4, 5];
+///
+/// assert!(samples[..].at(Offset(1, Scaled(2, 2))).get_clone() == Some(5));  // the third right sample
+/// ```
+pub trait Position {
+    /// The position in a sequence of the length.
+    ///
+    /// The result can be out of bounds: it is checked by the accessor.
+    /// `None` means that the position can't be computed (e.g. it is negative).
+    fn position(self, len: usize) -> Option<usize>;
+}
+
+impl Position for usize {
+    fn position(self, _: usize) -> Option<usize> {
+        Some(self)
+    }
+}
+
+
+/// An index adapter shifting a position: `Offset(n, i)` is the position `i + n`.
+///
+/// See [`Position`](trait.Position.html) for an example.
+#[derive(Debug, Copy, Clone)]
+pub struct Offset<I>(pub isize, pub I);
+
+/// An index adapter multiplying a position: `Scaled(k, i)` is the position `k * i`.
+///
+/// See [`Position`](trait.Position.html) for an example.
+#[derive(Debug, Copy, Clone)]
+pub struct Scaled<I>(pub usize, pub I);
+
+/// An index adapter counting positions from the end: `Reversed(0)` is the last position.
+///
+/// See [`Position`](trait.Position.html) for an example.
+#[derive(Debug, Copy, Clone)]
+pub struct Reversed<I>(pub I);
+
+impl<I: Position> Position for Offset<I> {
+    fn position(self, len: usize) -> Option<usize> {
+        let i = self.1.position(len)?;
+
+        if self.0 < 0 {
+            i.checked_sub(self.0.unsigned_abs())
+        } else {
+            i.checked_add(self.0 as usize)
+        }
+    }
+}
+
+impl<I: Position> Position for Scaled<I> {
+    fn position(self, len: usize) -> Option<usize> {
+        self.1.position(len)?.checked_mul(self.0)
+    }
+}
+
+/// The inner position must be in bounds
+impl<I: Position> Position for Reversed<I> {
+    fn position(self, len: usize) -> Option<usize> {
+        let i = self.0.position(len)?;
+
+        if i < len { Some(len - 1 - i) } else { None }
+    }
+}
+
+
+macro_rules! impl_adapters {
+    ( $($adapter:ident),* ) => { $(
+        /// `access_at` returns `None` if the position is out of bounds
+        impl<T, I: Position> At<$adapter<I>> for [T] {
+            type View = T;
+
+            fn access_at<R, F>(&mut self, i: $adapter<I>, f: F) -> Option<R> where
+                F: FnOnce(&mut T) -> R
+            {
+                let i = i.position(self.len())?;
+
+                self.access_at(i, f)
+            }
+        }
+    )* };
+}
+
+impl_adapters!(Offset, Scaled, Reversed);
+
+
+#[test]
+fn test_position() {
+    use crate::Cps;
+
+    assert!(Offset(-2, 1).position(10).is_none());
+    assert!(Offset(-1, 10).position(10) == Some(9));
+    assert!(Offset(isize::MIN, usize::MAX).position(0) == Some(usize::MAX - isize::MIN.unsigned_abs()));
+    assert!(Offset(-1, Reversed(0)).position(10) == Some(8));
+
+    assert!(Scaled(3, 3).position(10) == Some(9));
+    assert!(Scaled(2, usize::MAX).position(10).is_none());
+
+    assert!(Reversed(0).position(0).is_none());
+    assert!(Reversed(Reversed(2)).position(3) == Some(2));
+    assert!(Reversed(Offset(-1, 1)).position(3) == Some(2));
+    assert!(Reversed(3).position(3).is_none());
+
+    let mut foo = [1, 2, 3];
+
+    assert!(foo[..].at(Offset(1, 2)).touch().is_none());
+    assert!(foo[..].at(Scaled(2, 1)).replace(4) == Some(3));
+    assert!(foo[..].at(Reversed(1)).replace(5) == Some(2));
+    assert!(foo == [1, 5, 4]);
+}
@@ -0,0 +1,105 @@
+use crate::at::At;
+
+
+/// A proxy index converting the view into another representation.
+///
+/// The `to` function converts the value for the closure, the `from`
+/// function converts the (modified) result back. Both conversions
+/// can fail: then `access_at` returns `None` and the value is left
+/// untouched (though the closure could have already been run if
+/// the backward conversion fails).
+///
+/// For infallible conversions see the [`iso`](fn.iso.html) helper.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::Cps;
+/// use smart_access::core_impls::Proxy;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Mode { Fast, Slow }
+///
+/// let mut config = vec![String::from("fast")];
+///
+/// let mode = || Proxy {
+///     to: |s: &String| match s.as_str() {
+///         "fast" => Some(Mode::Fast),
+///         "slow" => Some(Mode::Slow),
+///         _ => None,
+///     },
+///     from: |m: Mode| Some(format!("{:?}", m).to_lowercase()),
+/// };
+///
+/// assert!(config.at(0).at(mode()).replace(Mode::Slow) == Some(Mode::Fast));
+/// assert!(config == vec!["slow"]);
+///
+/// config[0] = "turbo".into();
+/// assert!(config.at(0).at(mode()).touch() == None);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Proxy<To, From> {
+    pub to: To,
+    pub from: From,
+}
+
+/// `access_at` returns `None` if either of the conversions fails
+impl<T, U, To, From> At<Proxy<To, From>> for T where
+    To: FnOnce(&T) -> Option<U>,
+    From: FnOnce(U) -> Option<T>
+{
+    type View = U;
+
+    fn access_at<R, F>(&mut self, i: Proxy<To, From>, f: F) -> Option<R> where
+        F: FnOnce(&mut U) -> R
+    {
+        let mut converted = (i.to)(self)?;
+        let result = f(&mut converted);
+
+        *self = (i.from)(converted)?;
+
+        Some(result)
+    }
+}
+
+
+/// A proxy for an infallible two-way conversion.
+///
+/// ```
+/// use smart_access::Cps;
+/// use smart_access::core_impls::iso;
+///
+/// let mut celsius = vec![20.0];
+/// let fahrenheit = || iso(|c: &f64| c * 1.8 + 32.0, |f: f64| (f - 32.0) / 1.8);
+///
+/// assert!(celsius.at(0).at(fahrenheit()).replace(212.0) == Some(68.0));
+/// assert!(celsius == vec![100.0]);
+/// ```
+pub fn iso<T, U, To, From>(to: To, from: From)
+    -> Proxy<impl FnOnce(&T) -> Option<U>, impl FnOnce(U) -> Option<T>> where
+    To: FnOnce(&T) -> U,
+    From: FnOnce(U) -> T
+{
+    Proxy {
+        to: move |x: &T| Some(to(x)),
+        from: move |y: U| Some(from(y)),
+    }
+}
+
+
+#[test]
+fn test_proxy() {
+    use crate::Cps;
+
+    let mut foo = Some(5u8);
+
+    let signed = || Proxy {
+        to: |x: &u8| Some(*x as i32),
+        from: |y: i32| if (0..256).contains(&y) { Some(y as u8) } else { None },
+    };
+
+    assert!(foo.at(()).at(signed()).access(|y| { *y -= 10; *y }).is_none());
+    assert!(foo == Some(5));
+    assert!(foo.at(()).at(signed()).access(|y| { *y += 10; *y }) == Some(15));
+    assert!(foo == Some(15));
+}
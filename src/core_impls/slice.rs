@@ -97,3 +97,95 @@ impl<T> At<ops::RangeToInclusive<usize>> for [T] {
     }
 }
 
+
+/// A proxy index rotating a slice to the left for the duration of the access.
+///
+/// The view is the whole slice rotated by `n` positions: the element `n` 
+/// becomes the first one. The rotation is undone after the access.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::Cps;
+/// use smart_access::core_impls::Rotated;
+///
+/// // a ring buffer with the oldest element at the position 2
+/// let mut ring = vec![4, 5, 1, 2, 3];
+///
+/// assert!(ring.at(Rotated(2)).access(|xs| xs.to_vec()) == Some(vec![1, 2, 3, 4, 5]));
+/// assert!(ring.at(Rotated(2)).at(0).replace(0) == Some(1));
+/// assert!(ring == vec![4, 5, 0, 2, 3]);
+///
+/// assert!(ring.at(Rotated(6)).touch().is_none());
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Rotated(pub usize);
+
+/// `access_at` returns `None` if the shift exceeds the length of the slice
+impl<T> At<Rotated> for [T] {
+    type View = [T];
+
+    fn access_at<R, F>(&mut self, i: Rotated, f: F) -> Option<R> where 
+        F: FnOnce(&mut Self::View) -> R 
+    {
+        if i.0 > self.len() { return None; }
+
+        self.rotate_left(i.0);
+
+        let guard = RotateBack { slice: self, mid: i.0 };
+
+        Some(f(&mut *guard.slice))
+    }
+}
+
+// Rotates the slice back on drop, i.e. even if `f` panics.
+struct RotateBack<'a, T> {
+    slice: &'a mut [T],
+    mid: usize,
+}
+
+impl<T> Drop for RotateBack<'_, T> {
+    fn drop(&mut self) {
+        self.slice.rotate_right(self.mid);
+    }
+}
+
+
+
+/// A marker index viewing UTF-8 bytes as text.
+///
+/// * `At<AsUtf8, View=str> for [u8]`: the bytes as a string slice
+///   (its length can't be changed)
+/// * `At<AsUtf8, View=String> for Vec<u8>`: the bytes as a string
+///   (__requires `collections`__); no copying is done
+///
+/// The access fails if the bytes are not valid UTF-8.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::Cps;
+/// use smart_access::core_impls::AsUtf8;
+///
+/// let mut buffer = b"hello".to_vec();
+///
+/// assert!(buffer[..].at(AsUtf8).access(|s| s.make_ascii_uppercase()) == Some(()));
+/// assert!(buffer.at(AsUtf8).access(|s| s.push_str(", world")) == Some(()));
+/// assert!(buffer == b"HELLO, world");
+///
+/// let mut binary = vec![0xff, 0xfe];
+/// assert!(binary.at(AsUtf8).touch() == None);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct AsUtf8;
+
+/// `access_at` returns `None` if the bytes are not valid UTF-8
+impl At<AsUtf8> for [u8] {
+    type View = str;
+
+    fn access_at<R, F>(&mut self, _: AsUtf8, f: F) -> Option<R> where
+        F: FnOnce(&mut str) -> R
+    {
+        core::str::from_utf8_mut(self).ok().map(f)
+    }
+}
\ No newline at end of file
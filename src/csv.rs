@@ -0,0 +1,161 @@
+//! Accessors for CSV text. __Requires `csv`.__
+//!
+//! A `String` containing comma-separated values can be accessed cell-wise:
+//! `At<Cell, View=String> for String` parses the text, gives the unquoted
+//! cell value to the closure and writes the (quoted if needed) new value back.
+//! The rest of the text is preserved byte-for-byte.
+//!
+//! The rows are numbered from zero (a header, if present, is the row zero).
+//! The records are separated by `\n` or `\r\n` and the fields are quoted as in
+//! [RFC 4180](https://tools.ietf.org/html/rfc4180). A record can have any
+//! number of fields.
+//!
+//! ```
+//! use smart_access::Cps;
+//! use smart_access::csv::Cell;
+//!
+//! let mut text = "name,city\nalice,paris\n\"doe, john\",berlin\n".to_string();
+//!
+//! assert!(text.at(Cell(2, 0)).get_clone() == Some("doe, john".to_string()));
+//! assert!(text.at(Cell(1, 1)).replace("new \"york\"".into()) == Some("paris".into()));
+//! assert!(text.at(Cell(1, 2)).touch() == None);
+//!
+//! assert!(text == "name,city\nalice,\"new \"\"york\"\"\"\n\"doe, john\",berlin\n");
+//! ```
+
+use crate::At;
+use alloc::string::String;
+
+
+/// A cell index: `Cell(row, column)`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Cell(pub usize, pub usize);
+
+
+/// `access_at` returns `None` if the cell is missing
+///
+/// The text is written back only if the value has changed.
+impl At<Cell> for String {
+    type View = String;
+
+    fn access_at<R, F>(&mut self, i: Cell, f: F) -> Option<R> where
+        F: FnOnce(&mut String) -> R
+    {
+        let (start, end) = find_field(self, i.0, i.1)?;
+
+        let old = unquote(&self[start..end]);
+        let mut new = old.clone();
+
+        let result = f(&mut new);
+
+        if new != old { self.replace_range(start..end, &quote(&new)); }
+
+        Some(result)
+    }
+}
+
+
+// the byte range of a raw (possibly quoted) field
+fn find_field(text: &str, row: usize, col: usize) -> Option<(usize, usize)> {
+    let bytes = text.as_bytes();
+    let (mut r, mut c) = (0, 0);
+    let mut start = 0;
+    let mut pos = 0;
+    let mut quoted = false;
+
+    while pos < bytes.len() {
+        let b = bytes[pos];
+
+        if quoted {
+            if b == b'"' {
+                if bytes.get(pos + 1) == Some(&b'"') { pos += 1; } else { quoted = false; }
+            }
+
+            pos += 1;
+            continue;
+        }
+
+        match b {
+            b'"' if pos == start => { quoted = true; },
+
+            b',' => {
+                if (r, c) == (row, col) { return Some((start, pos)); }
+
+                c += 1;
+                start = pos + 1;
+            },
+
+            b'\n' => {
+                let end = if pos > start && bytes[pos - 1] == b'\r' { pos - 1 } else { pos };
+
+                if (r, c) == (row, col) { return Some((start, end)); }
+
+                r += 1;
+                c = 0;
+                start = pos + 1;
+            },
+
+            _ => {},
+        }
+
+        pos += 1;
+    }
+
+    // the last record without a trailing newline (possibly ending with an empty field)
+    if (start < bytes.len() || c > 0) && (r, c) == (row, col) { return Some((start, bytes.len())); }
+
+    None
+}
+
+fn unquote(raw: &str) -> String {
+    match raw.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        Some(inner) => inner.replace("\"\"", "\""),
+        None => raw.into(),
+    }
+}
+
+fn quote(value: &str) -> String {
+    if !value.contains(&[',', '"', '\n', '\r'][..]) { return value.into(); }
+
+    let mut result = String::with_capacity(value.len() + 2);
+
+    result.push('"');
+    result.push_str(&value.replace('"', "\"\""));
+    result.push('"');
+
+    result
+}
+
+
+#[test]
+fn test_csv() {
+    use crate::Cps;
+    use alloc::vec::Vec;
+
+    let mut foo = String::from("a,\"b\nc\",\r\n1,2,3");
+
+    assert!(foo.at(Cell(0, 1)).get_clone() == Some("b\nc".into()));
+    assert!(foo.at(Cell(0, 2)).get_clone() == Some("".into()));
+    assert!(foo.at(Cell(1, 2)).replace("x,y".into()) == Some("3".into()));
+    assert!(foo.at(Cell(0, 0)).access(|_| ()) == Some(()));
+    assert!(foo.at(Cell(2, 0)).touch().is_none());
+    assert!(foo == "a,\"b\nc\",\r\n1,2,\"x,y\"");
+
+    let fields: Vec<_> = (0..3).map(|c| foo.at(Cell(1, c)).get_clone().unwrap()).collect();
+    assert!(fields == ["1", "2", "x,y"]);
+
+    assert!(String::new().at(Cell(0, 0)).touch().is_none());
+
+    // a quote inside an unquoted field is literal
+    let mut bar = String::from("5\" disk,ok\n");
+    assert!(bar.at(Cell(0, 1)).get_clone() == Some("ok".into()));
+
+    // a trailing empty field with and without the final newline
+    assert!(String::from("1,2,").at(Cell(0, 2)).get_clone() == Some("".into()));
+    assert!(String::from("1,2,\n").at(Cell(0, 2)).get_clone() == Some("".into()));
+    assert!(String::from("1,2,").at(Cell(0, 3)).touch().is_none());
+
+    let mut baz = String::from("1,2,");
+    assert!(baz.at(Cell(0, 2)).replace("3".into()) == Some("".into()));
+    assert!(baz == "1,2,3");
+}
@@ -0,0 +1,66 @@
+//! Structural diffs. __Requires `diff`.__
+//!
+//! Given two instances of the same datatype and a set of
+//! [detached paths](../trait.Attach.html), the [`diff`](fn.diff.html)
+//! function computes the paths whose values differ:
+//!
+//! ```
+//! use smart_access::{ Cps, detached_at };
+//! use smart_access::diff::{ diff, Change };
+//!
+//! let mut old = vec![ vec![1, 2], vec![3, 4] ];
+//! let mut new = vec![ vec![1, 5], vec![3] ];
+//!
+//! let paths = (0..2).flat_map(|i| (0..2).map(move |j| detached_at(i).at(j)));
+//!
+//! let changes = diff(&mut old, &mut new, paths)
+//!     .into_iter()
+//!     .map(|change| (change.old, change.new))
+//!     .collect::<Vec<_>>();
+//!
+//! assert!(changes == vec![ (Some(2), Some(5)), (Some(4), None) ]);
+//! ```
+//!
+//! ### Note
+//!
+//! All accessors take the data by a mutable reference, thus the diffed
+//! values are taken by mutable references too. Lawful accessors
+//! (see the [`At`](../trait.At.html) contract) leave the values unchanged.
+
+use crate::at::Cps;
+use crate::Attach;
+use alloc::vec::Vec;
+
+
+/// A difference at a single path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change<P, V> {
+    /// The path where the values differ.
+    pub path: P,
+
+    /// The value in the old instance (`None` if the path doesn't resolve).
+    pub old: Option<V>,
+
+    /// The value in the new instance (`None` if the path doesn't resolve).
+    pub new: Option<V>,
+}
+
+
+/// Compares two instances along the provided paths.
+///
+/// Returns the changes in the order of the paths.
+pub fn diff<Root, P, V, Paths>(old: &mut Root, new: &mut Root, paths: Paths) -> Vec<Change<P, V>> where
+    Root: ?Sized,
+    Paths: IntoIterator<Item=P>,
+    P: Attach<Root, View=V> + Clone,
+    V: Clone + PartialEq
+{
+    paths.into_iter().filter_map(|path| {
+        let old_value = (&mut *old).attach(path.clone()).get_clone();
+        let new_value = (&mut *new).attach(path.clone()).get_clone();
+
+        if old_value == new_value { return None; }
+
+        Some(Change { path, old: old_value, new: new_value })
+    }).collect()
+}
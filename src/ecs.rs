@@ -0,0 +1,267 @@
+//! Entity-component storage with path access. __Requires `ecs`.__
+//!
+//! A [`World`](struct.World.html) holds entities and their components.
+//! The components of each type are kept in a separate storage. The index
+//! `(entity, Component::<T>::new())` resolves the entity in the storage
+//! of `T` and views the component:
+//!
+//! ```
+//! use smart_access::Cps;
+//! use smart_access::ecs::{ World, Component };
+//!
+//! #[derive(Debug, Clone, PartialEq)]
+//! struct Position(f64, f64);
+//!
+//! #[derive(Debug, Clone, PartialEq)]
+//! struct Name(String);
+//!
+//! let mut world = World::new();
+//!
+//! let player = world.spawn();
+//! world.insert(player, Position(0.0, 0.0));
+//! world.insert(player, Name("player".into()));
+//!
+//! let tree = world.spawn();
+//! world.insert(tree, Position(5.0, 5.0));
+//!
+//! let pos = Component::<Position>::new();
+//! let name = Component::<Name>::new();
+//!
+//! assert!(world.at((player, pos)).access(|p| p.0 += 1.0) == Some(()));
+//! assert!(world.at((player, pos)).get_clone() == Some(Position(1.0, 0.0)));
+//! assert!(world.at((tree, name)).touch() == None);  // no such component
+//!
+//! world.despawn(player);
+//! assert!(world.at((player, pos)).touch() == None);  // no such entity
+//! ```
+//!
+//! With the `detach` feature enabled such an index is a perfectly
+//! ordinary path component: a detached path can describe
+//! &#8220;this component of this entity&#8221;:
+//!
+//! ```
+//! # #[cfg(feature="detach")] fn test() {
+//! use smart_access::{ Cps, detached_at };
+//! use smart_access::ecs::{ World, Component };
+//!
+//! let mut world = World::new();
+//! let e = world.spawn();
+//! world.insert(e, vec![1, 2, 3]);
+//!
+//! let second = detached_at((e, Component::<Vec<i32>>::new())).at(1);
+//!
+//! assert!(world.attach(second.clone()).replace(5) == Some(2));
+//! assert!(world.attach(second).get_clone() == Some(5));
+//! # }
+//! # #[cfg(not(feature="detach"))] fn test() {}
+//! # test();
+//! ```
+//!
+//! An entity is a generational index: the index of a despawned entity
+//! can be reused but the old handle stays invalid.
+
+use crate::at::At;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::any::{ Any, TypeId };
+use core::fmt;
+use core::marker::PhantomData;
+
+
+/// A handle of an entity.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Entity {
+    index: usize,
+    generation: u32,
+}
+
+impl Entity {
+    /// The position of the entity in the storages.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The number of times the index has been reused.
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+
+/// A component type marker used as a part of an index.
+pub struct Component<T>(PhantomData<fn() -> T>);
+
+impl<T> Component<T> {
+    /// Creates the marker.
+    pub const fn new() -> Self {
+        Component(PhantomData)
+    }
+}
+
+impl<T> Default for Component<T> {
+    fn default() -> Self { Component::new() }
+}
+
+impl<T> Clone for Component<T> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<T> Copy for Component<T> {}
+
+impl<T> fmt::Debug for Component<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Component::<{}>", core::any::type_name::<T>())
+    }
+}
+
+
+// A type-erased storage of components of a single type.
+trait Storage {
+    fn remove(&mut self, index: usize);
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: 'static> Storage for Vec<Option<T>> {
+    fn remove(&mut self, index: usize) {
+        if let Some(slot) = self.get_mut(index) { *slot = None; }
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+
+/// A collection of entities and their components.
+///
+/// See the [module-level documentation](index.html) for an example.
+#[derive(Default)]
+pub struct World {
+    generations: Vec<u32>,
+    alive: Vec<bool>,
+    free: Vec<usize>,
+    storages: BTreeMap<TypeId, Box<dyn Storage>>,
+}
+
+impl World {
+    /// Creates an empty world.
+    pub fn new() -> Self {
+        World::default()
+    }
+
+    /// Creates an entity without components.
+    pub fn spawn(&mut self) -> Entity {
+        match self.free.pop() {
+            Some(index) => {
+                self.alive[index] = true;
+                Entity { index, generation: self.generations[index] }
+            },
+            None => {
+                self.generations.push(0);
+                self.alive.push(true);
+                Entity { index: self.alive.len() - 1, generation: 0 }
+            },
+        }
+    }
+
+    /// Removes the entity with all its components.
+    ///
+    /// Returns `false` if the entity is not alive.
+    pub fn despawn(&mut self, entity: Entity) -> bool {
+        if !self.is_alive(entity) { return false; }
+
+        for storage in self.storages.values_mut() { storage.remove(entity.index); }
+
+        self.alive[entity.index] = false;
+        self.generations[entity.index] = self.generations[entity.index].wrapping_add(1);
+        self.free.push(entity.index);
+
+        true
+    }
+
+    /// Checks if the handle refers to a living entity.
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.alive.get(entity.index) == Some(&true) && self.generations[entity.index] == entity.generation
+    }
+
+    /// Attaches a component to the entity.
+    ///
+    /// Returns the replaced component of the same type. If the entity
+    /// is not alive the component is returned back.
+    pub fn insert<T: 'static>(&mut self, entity: Entity, component: T) -> Option<T> {
+        if !self.is_alive(entity) { return Some(component); }
+
+        let storage = self.storages.entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(Vec::<Option<T>>::new()))
+            .as_any_mut()
+            .downcast_mut::<Vec<Option<T>>>()
+            .unwrap();
+
+        if storage.len() <= entity.index { storage.resize_with(entity.index + 1, || None); }
+
+        storage[entity.index].replace(component)
+    }
+
+    /// Detaches the component of the type from the entity.
+    pub fn remove<T: 'static>(&mut self, entity: Entity) -> Option<T> {
+        if !self.is_alive(entity) { return None; }
+
+        self.storage::<T>()?.get_mut(entity.index)?.take()
+    }
+
+    fn storage<T: 'static>(&mut self) -> Option<&mut Vec<Option<T>>> {
+        self.storages.get_mut(&TypeId::of::<T>())?.as_any_mut().downcast_mut()
+    }
+}
+
+impl fmt::Debug for World {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("World")
+            .field("entities", &(self.alive.iter().filter(|&&a| a).count()))
+            .field("storages", &self.storages.len())
+            .finish()
+    }
+}
+
+/// `access_at` returns `None` if the entity is not alive or has no such component
+impl<T: 'static> At<(Entity, Component<T>)> for World {
+    type View = T;
+
+    fn access_at<R, F>(&mut self, i: (Entity, Component<T>), f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        if !self.is_alive(i.0) { return None; }
+
+        self.storage::<T>()?.get_mut(i.0.index)?.as_mut().map(f)
+    }
+}
+
+
+#[test]
+fn test_ecs() {
+    use crate::Cps;
+
+    let mut world = World::new();
+
+    let a = world.spawn();
+    let b = world.spawn();
+
+    assert!(world.insert(a, 1u8).is_none());
+    assert!(world.insert(a, 2u8) == Some(1));
+    assert!(world.insert(b, 'b').is_none());
+
+    assert!(world.despawn(a));
+    assert!(!world.despawn(a));
+    assert!(world.insert(a, 3u8) == Some(3));
+
+    let c = world.spawn();
+    assert!(c.index() == a.index() && c.generation() == 1);
+    assert!(world.at((c, Component::<u8>::new())).touch().is_none());
+
+    assert!(world.insert(c, 4u8).is_none());
+    assert!(world.at((a, Component::<u8>::new())).touch().is_none());
+    assert!(world.at((c, Component::<u8>::new())).replace(5) == Some(4));
+    assert!(world.remove::<char>(b) == Some('b'));
+    assert!(world.at((b, Component::<char>::new())).touch().is_none());
+}
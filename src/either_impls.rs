@@ -0,0 +1,79 @@
+//! Support for the [`either`](https://crates.io/crates/either) crate. __Requires `either`.__
+//!
+//! The following traits are implemented:
+//! * `At<LeftP, View=L> for Either<L,R>`: access to the `Left` value
+//! * `At<RightP, View=R> for Either<L,R>`: access to the `Right` value
+//! * `At<(), View=T> for Either<T,T>`: access to the value
+//!   regardless of the side (the access never fails)
+//!
+//! ```
+//! use smart_access::Cps;
+//! use smart_access::either_impls::{ LeftP, RightP };
+//! use either::Either;
+//!
+//! let mut foo: Vec<Either<i32, String>> = vec![ Either::Left(1), Either::Right("a".into()) ];
+//!
+//! assert!(foo.at(0).at(LeftP).replace(2) == Some(1));
+//! assert!(foo.at(0).at(RightP).touch() == None);
+//! assert!(foo.at(1).at(RightP).access(|s| s.push('b')) == Some(()));
+//!
+//! assert!(foo == vec![ Either::Left(2), Either::Right("ab".into()) ]);
+//!
+//! let mut bar: Either<i32, i32> = Either::Right(3);
+//!
+//! assert!(bar.at(()).replace(4) == Some(3));
+//! assert!(bar == Either::Right(4));
+//! ```
+
+use crate::At;
+use either::Either;
+
+
+/// A marker index for the `Left` value of an `Either`.
+#[derive(Debug, Copy, Clone)]
+pub struct LeftP;
+
+/// A marker index for the `Right` value of an `Either`.
+#[derive(Debug, Copy, Clone)]
+pub struct RightP;
+
+
+impl<L, R> At<LeftP> for Either<L, R> {
+    type View = L;
+
+    fn access_at<T, F>(&mut self, _: LeftP, f: F) -> Option<T> where
+        F: FnOnce(&mut L) -> T
+    {
+        match self {
+            Either::Left(x)  => Some(f(x)),
+            Either::Right(_) => None,
+        }
+    }
+}
+
+impl<L, R> At<RightP> for Either<L, R> {
+    type View = R;
+
+    fn access_at<T, F>(&mut self, _: RightP, f: F) -> Option<T> where
+        F: FnOnce(&mut R) -> T
+    {
+        match self {
+            Either::Left(_)  => None,
+            Either::Right(x) => Some(f(x)),
+        }
+    }
+}
+
+/// `access_at` is guaranteed to return `Some(f(..))`
+impl<V> At<()> for Either<V, V> {
+    type View = V;
+
+    fn access_at<T, F>(&mut self, _: (), f: F) -> Option<T> where
+        F: FnOnce(&mut V) -> T
+    {
+        match self {
+            Either::Left(x)  => Some(f(x)),
+            Either::Right(x) => Some(f(x)),
+        }
+    }
+}
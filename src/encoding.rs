@@ -0,0 +1,205 @@
+//! Text views of byte buffers. __Requires `encoding`.__
+//!
+//! The indices [`AsHex`](struct.AsHex.html) and [`AsBase64`](struct.AsBase64.html)
+//! present a byte buffer as an encoded `String`. The edited string is decoded
+//! back into bytes. If the string can't be decoded then `access_at` returns `None`
+//! and the buffer stays unchanged.
+//!
+//! Implemented for `Vec<u8>` and `[u8]`. A slice can't change its length:
+//! a string decoding to a different number of bytes is rejected.
+//!
+//! ```
+//! use smart_access::Cps;
+//! use smart_access::encoding::{ AsHex, AsBase64 };
+//!
+//! let mut blobs = vec![vec![0xde, 0xad], b"hi".to_vec()];
+//!
+//! assert!(blobs.at(0).at(AsHex).replace("BEEF00".into()) == Some("dead".into()));
+//! assert!(blobs.at(0).at(AsHex).replace("xyz".into()) == None);
+//! assert!(blobs.at(1).at(AsBase64).get_clone() == Some("aGk=".into()));
+//! assert!(blobs.at(1).at(AsBase64).access(|s| s.replace_range(.., "aGV5")) == Some(()));
+//!
+//! assert!(blobs == vec![vec![0xbe, 0xef, 0x00], b"hey".to_vec()]);
+//! ```
+
+use crate::At;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+
+/// A view of bytes as a lowercase hexadecimal string.
+///
+/// Both lowercase and uppercase digits are accepted on decoding.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct AsHex;
+
+/// A view of bytes as a Base64 string (the standard alphabet, with padding).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct AsBase64;
+
+
+/// A binary-to-text encoding.
+trait Encoding {
+    fn encode(bytes: &[u8]) -> String;
+    fn decode(text: &str) -> Option<Vec<u8>>;
+}
+
+impl Encoding for AsHex {
+    fn encode(bytes: &[u8]) -> String {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+        let mut text = String::with_capacity(2 * bytes.len());
+
+        for &b in bytes {
+            text.push(DIGITS[(b >> 4) as usize] as char);
+            text.push(DIGITS[(b & 15) as usize] as char);
+        }
+
+        text
+    }
+
+    #[allow(clippy::manual_is_multiple_of)]  // `is_multiple_of` needs Rust 1.87
+    fn decode(text: &str) -> Option<Vec<u8>> {
+        let text = text.as_bytes();
+        if text.len() % 2 != 0 { return None; }
+
+        let digit = |c: u8| (c as char).to_digit(16).map(|d| d as u8);
+
+        text.chunks(2).map(|pair| Some(digit(pair[0])? << 4 | digit(pair[1])?)).collect()
+    }
+}
+
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+impl Encoding for AsBase64 {
+    #[allow(clippy::manual_div_ceil)]  // `div_ceil` needs Rust 1.73
+    fn encode(bytes: &[u8]) -> String {
+        let mut text = String::with_capacity((bytes.len() + 2) / 3 * 4);
+
+        for chunk in bytes.chunks(3) {
+            let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    text.push(BASE64[(n >> (18 - 6 * i) & 63) as usize] as char);
+                } else {
+                    text.push('=');
+                }
+            }
+        }
+
+        text
+    }
+
+    #[allow(clippy::manual_is_multiple_of)]
+    fn decode(text: &str) -> Option<Vec<u8>> {
+        let text = text.as_bytes();
+        if text.len() % 4 != 0 { return None; }
+
+        let mut bytes = Vec::with_capacity(text.len() / 4 * 3);
+        let last = text.len() / 4;
+
+        for (k, chunk) in text.chunks(4).enumerate() {
+            let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+            if padding > 2 || (padding > 0 && k + 1 != last) { return None; }
+
+            let mut n = 0u32;
+
+            for &c in &chunk[..4 - padding] {
+                let d = BASE64.iter().position(|&x| x == c)? as u32;
+                n = n << 6 | d;
+            }
+
+            n <<= 6 * padding as u32;
+
+            let decoded = [(n >> 16) as u8, (n >> 8) as u8, n as u8];
+            let len = 3 - padding;
+
+            // non-canonical trailing bits
+            if decoded[len..].iter().any(|&b| b != 0) { return None; }
+
+            bytes.extend_from_slice(&decoded[..len]);
+        }
+
+        Some(bytes)
+    }
+}
+
+
+macro_rules! impl_encoding {
+    ( $($index:ident),* ) => { $(
+        /// `access_at` returns `None` if the new text can't be decoded
+        impl At<$index> for Vec<u8> {
+            type View = String;
+
+            fn access_at<R, F>(&mut self, _: $index, f: F) -> Option<R> where
+                F: FnOnce(&mut String) -> R
+            {
+                let old = $index::encode(self);
+                let mut new = old.clone();
+
+                let result = f(&mut new);
+
+                if new != old { *self = $index::decode(&new)?; }
+
+                Some(result)
+            }
+        }
+
+        /// `access_at` returns `None` if the new text can't be decoded
+        /// into the same number of bytes
+        impl At<$index> for [u8] {
+            type View = String;
+
+            fn access_at<R, F>(&mut self, _: $index, f: F) -> Option<R> where
+                F: FnOnce(&mut String) -> R
+            {
+                let old = $index::encode(self);
+                let mut new = old.clone();
+
+                let result = f(&mut new);
+
+                if new != old {
+                    let bytes = $index::decode(&new)?;
+                    if bytes.len() != self.len() { return None; }
+
+                    self.copy_from_slice(&bytes);
+                }
+
+                Some(result)
+            }
+        }
+    )* };
+}
+
+impl_encoding!(AsHex, AsBase64);
+
+
+#[test]
+fn test_encoding() {
+    use crate::Cps;
+    use alloc::vec;
+
+    for bytes in [&b""[..], b"f", b"fo", b"foo", b"foob", b"fooba", b"foobar"].iter() {
+        assert!(AsBase64::decode(&AsBase64::encode(bytes)).as_deref() == Some(*bytes));
+        assert!(AsHex::decode(&AsHex::encode(bytes)).as_deref() == Some(*bytes));
+    }
+
+    assert!(AsBase64::encode(b"foobar") == "Zm9vYmFy");
+    assert!(AsBase64::encode(b"fo") == "Zm8=");
+
+    for bad in ["Zm8", "Zm9=", "Z===", "Zg==Zg==", "Zm8*"].iter() {
+        assert!(AsBase64::decode(bad).is_none());
+    }
+
+    let mut foo = [1u8, 2, 3];
+
+    assert!(foo[..].at(AsHex).replace("0a0b".into()).is_none());
+    assert!(foo[..].at(AsHex).replace("0A0B0C".into()) == Some("010203".into()));
+    assert!(foo[..].at(AsBase64).get_clone() == Some("CgsM".into()));
+    assert!(foo == [10, 11, 12]);
+
+    let mut bar = vec![0u8; 2];
+    assert!(bar.at(AsBase64).replace("".into()) == Some("AAA=".into()));
+    assert!(bar.is_empty());
+}
@@ -0,0 +1,222 @@
+//! A registry of global values. __Requires `globals`.__
+//!
+//! Global values are registered under [typed names](struct.Global.html)
+//! and protected by mutexes. The registry is reachable from anywhere by
+//! [`globals()`](fn.globals.html), and a registered value can be accessed
+//! by a path starting with its name:
+//!
+//! ```
+//! use smart_access::Cps;
+//! use smart_access::globals::{ globals, Global };
+//!
+//! const HITS: Global<Vec<u64>> = Global::new("metrics.hits");
+//!
+//! assert!(globals().register(HITS, vec![0, 0]));
+//! assert!(!globals().register(HITS, vec![]));  // the name is taken
+//!
+//! // nothing is locked until the access
+//! let second = globals().at(HITS).at(1);
+//!
+//! assert!(second.access(|x| { *x += 1; *x }) == Some(1));
+//! assert!(globals().at(HITS).get_clone() == Some(vec![0, 1]));
+//!
+//! // an unregistered name or a wrong type
+//! assert!(globals().at(Global::<Vec<u64>>::new("metrics.misses")).touch() == None);
+//! assert!(globals().at(Global::<String>::new("metrics.hits")).touch() == None);
+//! ```
+//!
+//! The registry itself is locked only to find the value. Then the mutex
+//! of the value is locked for the duration of the access. Accesses
+//! to different values thus don't block each other. An access
+//! to a value nested in an access to the same value deadlocks.
+//!
+//! If an access panics then the mutex of the value is poisoned and
+//! all the subsequent accesses to the value fail. The registry
+//! itself is never poisoned: no user code runs while it's locked.
+//!
+//! __Links to `std`__ (for `std::sync::Mutex`).
+
+extern crate std;
+
+use crate::at::{ At, Cps };
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use core::any::Any;
+use core::fmt;
+use core::marker::PhantomData;
+use std::sync::{ Mutex, PoisonError };
+
+
+type Table = BTreeMap<&'static str, Arc<dyn Any + Send + Sync>>;
+
+static TABLE: Mutex<Option<Table>> = Mutex::new(None);
+
+// Locks the table (creating it on the first use) and runs `f` on it.
+//
+// Poisoning is ignored: the table can't be left half-updated
+// because no user code runs while it's locked.
+fn with_table<R, F>(f: F) -> R where
+    F: FnOnce(&mut Table) -> R
+{
+    let mut table = TABLE.lock().unwrap_or_else(PoisonError::into_inner);
+
+    f(table.get_or_insert_with(BTreeMap::new))
+}
+
+
+/// A name of a global value of the type `T`.
+///
+/// Usually defined as a constant.
+pub struct Global<T> {
+    name: &'static str,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Global<T> {
+    /// Creates a typed name.
+    pub const fn new(name: &'static str) -> Self {
+        Global { name, marker: PhantomData }
+    }
+
+    /// The name of the value.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T> Clone for Global<T> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<T> Copy for Global<T> {}
+
+impl<T> fmt::Debug for Global<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Global({:?})", self.name)
+    }
+}
+
+
+/// The global registry. Created by [`globals()`](fn.globals.html).
+///
+/// As a `Cps` value it views the [table](struct.GlobalTable.html) of values.
+#[must_use]
+#[derive(Debug, Copy, Clone)]
+pub struct Globals(());
+
+/// The global registry.
+pub fn globals() -> Globals {
+    Globals(())
+}
+
+impl Globals {
+    /// Registers a value under the name.
+    ///
+    /// Returns `false` (and drops the value) if the name is taken.
+    pub fn register<T: Send + 'static>(&self, key: Global<T>, value: T) -> bool {
+        with_table(|table| {
+            if table.contains_key(key.name) { return false; }
+
+            table.insert(key.name, Arc::new(Mutex::new(value)));
+
+            true
+        })
+    }
+
+    /// Removes the value with the name. Returns `false` if there is no such value.
+    ///
+    /// Running accesses to the value are not affected.
+    pub fn unregister(&self, name: &str) -> bool {
+        // the value (if it's the last reference) is dropped after unlocking
+        let removed = with_table(|table| table.remove(name));
+
+        removed.is_some()
+    }
+
+    /// Checks if a value with the name is registered.
+    pub fn contains(&self, name: &str) -> bool {
+        with_table(|table| table.contains_key(name))
+    }
+}
+
+/// `access` is guaranteed to return `Some(f(..))`
+impl Cps for Globals {
+    type View = GlobalTable;
+
+    fn access<R, F>(self, f: F) -> Option<R> where
+        F: FnOnce(&mut GlobalTable) -> R
+    {
+        Some(f(&mut GlobalTable(())))
+    }
+}
+
+
+/// The view of the global registry.
+///
+/// Doesn't hold any locks: values are found and locked by
+/// [`access_at`](#method.access_at).
+#[derive(Debug)]
+pub struct GlobalTable(());
+
+/// `access_at` returns `None` if there is no value of the type `T`
+/// with the name or if its mutex is poisoned (by a panicking access)
+impl<T: Send + 'static> At<Global<T>> for GlobalTable {
+    type View = T;
+
+    fn access_at<R, F>(&mut self, key: Global<T>, f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        let value = with_table(|table| table.get(key.name).cloned())?;
+        let value = value.downcast::<Mutex<T>>().ok()?;
+
+        let mut guard = value.lock().ok()?;
+
+        Some(f(&mut *guard))
+    }
+}
+
+
+#[test]
+fn test_globals() {
+    use alloc::string::String;
+
+    const NAME: Global<String> = Global::new("test_globals.name");
+    const LEN: Global<usize> = Global::new("test_globals.name");
+
+    assert!(globals().at(NAME).touch().is_none());
+    assert!(globals().register(NAME, "foo".into()));
+    assert!(globals().contains("test_globals.name"));
+    assert!(!globals().register(LEN, 0));
+
+    assert!(globals().at(NAME).access(|s| s.push('!')) == Some(()));
+    assert!(globals().at(LEN).touch().is_none());
+
+    let handle = std::thread::spawn(|| globals().at(NAME).get_clone());
+    assert!(handle.join().unwrap().as_deref() == Some("foo!"));
+
+    assert!(globals().unregister("test_globals.name"));
+    assert!(globals().at(NAME).touch().is_none());
+}
+
+#[test]
+fn test_poisoned_global() {
+    use std::panic::{ catch_unwind, AssertUnwindSafe };
+
+    const COUNT: Global<u32> = Global::new("test_poisoned_global.count");
+    const OTHER: Global<u32> = Global::new("test_poisoned_global.other");
+
+    assert!(globals().register(COUNT, 0));
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        globals().at(COUNT).access(|_| std::panic!())
+    }));
+    assert!(result.is_err());
+
+    // the value is poisoned but the registry isn't
+    assert!(globals().at(COUNT).touch().is_none());
+    assert!(globals().register(OTHER, 1));
+    assert!(globals().at(OTHER).get_clone() == Some(1));
+
+    assert!(globals().unregister("test_poisoned_global.count"));
+    assert!(globals().unregister("test_poisoned_global.other"));
+}
@@ -55,6 +55,10 @@
 //! assert!(map.at(&5).at("b").get_clone() == Some(14));
 //! ```
 //!
+//! If the values in a key range need only to be visited one by one
+//! then the [`KeyRange`](../traversal/struct.KeyRange.html) traversal
+//! is simpler: `map.of(KeyRange(5..)).each(..)`.
+//!
 //! ## Usage
 //!
 //! Any `Iterator` (exactly `Iterator`, __not__ `IntoIterator`) 
@@ -0,0 +1,148 @@
+//! Checks of the [`At`](../trait.At.html) contract. __Requires `laws`.__
+//!
+//! Every function in this module takes a value and an index, runs some
+//! accesses on clones of the value and returns `true` if the corresponding
+//! law holds. Thus the functions can be used both in plain unit tests
+//! and as properties for a property-based testing framework.
+//!
+//! The following laws are checked:
+//!
+//! * [`none_means_untouched`](fn.none_means_untouched.html):
+//!   if `access_at` returns `None` then the value stays unchanged
+//! * [`get_put`](fn.get_put.html): writing back the view just read
+//!   doesn't change the value
+//! * [`put_get`](fn.put_get.html): reading the view just written
+//!   gives the written view
+//! * [`put_put`](fn.put_put.html): writing twice is the same as
+//!   writing only the second view
+//!
+//! The last three laws make sense only for the views which can be
+//! replaced as a whole (i.e. _lenses_ in the terminology of functional
+//! programming). If the path doesn't resolve, these laws are vacuously true.
+//!
+//! ## Usage example
+//!
+//! ```
+//! use smart_access::{ At, laws };
+//!
+//! #[derive(Clone, PartialEq)]
+//! struct Account { balance: i64 }
+//!
+//! #[derive(Clone)]
+//! struct Debt;
+//!
+//! impl At<Debt> for Account {
+//!     type View = i64;
+//!
+//!     fn access_at<R, F>(&mut self, _: Debt, f: F) -> Option<R> where
+//!         F: FnOnce(&mut i64) -> R
+//!     {
+//!         let mut debt = -self.balance;
+//!         let result = f(&mut debt);
+//!         self.balance = -debt;
+//!
+//!         Some(result)
+//!     }
+//! }
+//!
+//! assert!(laws::check_lens(&Account { balance: 100 }, Debt, 50, -20));
+//! ```
+
+use crate::at::{ At, Cps };
+
+
+/// If the access fails then the value must stay unchanged.
+///
+/// The access tries to replace the view with `new_view`.
+pub fn none_means_untouched<T, I>(value: &T, i: I, new_view: T::View) -> bool where
+    T: At<I> + Clone + PartialEq,
+    T::View: Sized
+{
+    let mut t = value.clone();
+
+    match t.at(i).replace(new_view) {
+        Some(_) => true,
+        None    => t == *value,
+    }
+}
+
+
+/// Putting back the view just obtained must not change the value.
+pub fn get_put<T, I>(value: &T, i: I) -> bool where
+    T: At<I> + Clone + PartialEq,
+    T::View: Clone,
+    I: Clone
+{
+    let mut t = value.clone();
+
+    match t.at(i.clone()).get_clone() {
+        None => true,
+        Some(view) => {
+            t.at(i).replace(view).is_some() && t == *value
+        }
+    }
+}
+
+
+/// Getting the view just put must return the put view.
+pub fn put_get<T, I>(value: &T, i: I, new_view: T::View) -> bool where
+    T: At<I> + Clone,
+    T::View: Clone + PartialEq,
+    I: Clone
+{
+    let mut t = value.clone();
+
+    match t.at(i.clone()).replace(new_view.clone()) {
+        None => true,
+        Some(_) => t.at(i).get_clone() == Some(new_view),
+    }
+}
+
+
+/// Putting two views in a row must be the same as putting only the second one.
+pub fn put_put<T, I>(value: &T, i: I, first: T::View, second: T::View) -> bool where
+    T: At<I> + Clone + PartialEq,
+    T::View: Clone,
+    I: Clone
+{
+    let mut twice = value.clone();
+    let mut once  = value.clone();
+
+    let twice_result = twice.at(i.clone()).replace(first)
+        .and_then(|_| twice.at(i.clone()).replace(second.clone()));
+    let once_result = once.at(i).replace(second);
+
+    twice_result.is_some() == once_result.is_some() && twice == once
+}
+
+
+/// Checks all the laws of this module.
+pub fn check_lens<T, I>(value: &T, i: I, first: T::View, second: T::View) -> bool where
+    T: At<I> + Clone + PartialEq,
+    T::View: Clone + PartialEq,
+    I: Clone
+{
+    none_means_untouched(value, i.clone(), first.clone()) &&
+    get_put(value, i.clone()) &&
+    put_get(value, i.clone(), first.clone()) &&
+    put_put(value, i, first, second)
+}
+
+
+#[test]#[cfg(feature="collections")]
+fn test_collection_laws() {
+    use alloc::vec;
+
+    let value = vec![1, 2, 3, 4];
+
+    for i in 0..6 {
+        assert!(check_lens(&value, i, 5, 6));
+        assert!(check_lens(&value, i..i+1, vec![7], vec![8]));
+    }
+
+    let value = Some(Some(1));
+    let none: Option<Option<i32>> = None;
+
+    assert!(check_lens(&value, (), None, Some(2)));
+    assert!(check_lens(&none, (), None, Some(2)));
+}
@@ -201,6 +201,9 @@
 //! map.entry("foo".to_string()).or_insert("bar".to_string());
 //! ```
 //!
+//! For other map-like types (having no `(K,V)` accessor) there is
+//! a generic [`or_insert_at`](core_impls/fn.or_insert_at.html) helper.
+//!
 //!
 //! ## Motivation (part II: bidirectional programming)
 //!
@@ -692,14 +695,81 @@
 //!   __Implies `alloc`.__
 //! * `batch_ct`: Provides compile-time [batching](struct.CpsBatch.html).
 //! * `batch`: An alias for `batch_rt` and `batch_ct` enabled simultaneously.
+//! * `batch_bounded`: Provides fixed-capacity runtime [batching](struct.CpsBatch.html)
+//!   (no heap is needed).
+//!   __Pulls the [`heapless`](https://crates.io/crates/heapless) crate.__
 //! * `detach`: Makes [`AT`](struct.AT.html)-paths [detachable](struct.AT.html#method.detach).
 //! * `iter_mut`: [Accessors for iterators](./iter_mut/). 
 //!   __Pulls the [`multiref`](https://crates.io/crates/multiref) crate, implies `alloc`.__
 //! * `traversal`: Bidirectional iterators in continuation passing style.
+//! * `tokio`: Accessors and async access for [`tokio` locks](./tokio_impls/).
+//!   __Pulls the [`tokio`](https://crates.io/crates/tokio) crate, links to `std`.__
+//! * `test_utils`: [Helpers](./test_utils/) for testing accessor-based code.
+//!   __Implies `alloc`.__
+//! * `laws`: [Checks](./laws/) of the `At` contract for user implementations.
+//! * `profile`: [Per-path counting](./profile/) of accesses and failures.
+//!   __Implies `alloc`.__
+//! * `dyn_path`: [Homogeneous runtime paths](struct.DynPath.html)
+//!   and [rooted paths](struct.RootedPath.html).
+//!   __Implies `alloc`.__
+//! * `diff`: [Structural diffs](./diff/) along detached paths.
+//!   __Implies `detach` and `alloc`.__
+//! * `command`: [Mutation commands](./command/) over runtime paths.
+//!   __Implies `dyn_path`.__
+//! * `serde`: Serialization of runtime paths and commands.
+//!   __Pulls the [`serde`](https://crates.io/crates/serde) crate.__
+//! * `either`: Accessors for [`Either`](./either_impls/).
+//!   __Pulls the [`either`](https://crates.io/crates/either) crate.__
+//! * `unsafe-cache`: [Accessors caching](struct.CachedAt.html) the resolved place
+//!   (and [rooted paths](struct.RootedPath.html#method.new_cached) caching the resolved prefix).
+//!   __Implies `detach`.__
+//! * `unsafe-pair`: Views of [two places at once](struct.Pair.html): zipped accessors,
+//!   zipped traversals, parallel columns, and [map entries](traversal/struct.Entries.html).
+//!   __Uses raw pointers internally.__
+//! * `derive`: [Field accessors](derive.Fields.html), typed path builders,
+//!   [newtype delegation](derive.AtDelegate.html)
+//!   and [traversals of homogeneous fields](traversal/derive.AllFields.html).
+//!   __Pulls the `smart_access_derive` crate, implies `detach`.__
+//! * `registry`: [Named paths](./registry/).
+//!   __Implies `detach` and `alloc`.__
+//! * `patch`: [A textual path and patch language](./patch/).
+//!   __Implies `command` and `collections`.__
+//! * `serde_json`: Patching of JSON values.
+//!   __Pulls the [`serde_json`](https://crates.io/crates/serde_json) crate.__
+//! * `timing`: [Timing](./timing/) of batch steps.
+//!   __Implies `alloc`, links to `std`.__
+//! * `plan`: [Reusable mutation plans](./plan/).
+//!   __Implies `detach` and `alloc`.__
+//! * `tree`: [Helpers for self-similar trees](./tree/).
+//!   __Implies `dyn_path` and `traversal`.__
+//! * `bindings`: [Observers of detached paths](./bindings/).
+//!   __Pulls the [`hashbrown`](https://crates.io/crates/hashbrown) crate, implies `detach` and `alloc`.__
+//! * `store`: [A store with dispatch and middleware](./store/).
+//!   __Implies `detach` and `alloc`.__
+//! * `versioned`: [Values with the history of snapshots](./versioned/).
+//!   __Implies `detach` and `alloc`.__
+//! * `locks`: [Lock ordering](./locks/) for nested synchronized state.
+//!   __Implies `alloc`, links to `std`.__
+//! * `wire`: [A network patch format](./wire/).
+//!   __Implies `patch`, `serde` and `serde_json`.__
+//! * `validation`: [Validators attached to paths](./validation/).
+//!   __Implies `detach` and `alloc`.__
+//! * `arbitrary`: [Generation](./arbitrary_impls/) of indices and paths for fuzzing.
+//!   __Pulls the [`arbitrary`](https://crates.io/crates/arbitrary) crate, links to `std`.__
+//! * `csv`: [Cell accessors](./csv/) for CSV text.
+//!   __Implies `alloc`.__
+//! * `encoding`: [Hex and Base64 views](./encoding/) of byte buffers.
+//!   __Implies `alloc`.__
+//! * `globals`: [A registry of global values](./globals/).
+//!   __Implies `alloc`, links to `std`.__
+//! * `ecs`: [Entity-component storage](./ecs/) with path access.
+//!   __Implies `alloc`.__
 //!
-//! All features except `std_hashmap` are enabled by default.
+//! The features enabled by default are `collections`, `hashbrown`, `batch`,
+//! `detach`, `iter_mut` and `traversal`.
 
 #![no_std]
+#![cfg_attr(not(any(feature="unsafe-cache", feature="unsafe-pair")), forbid(unsafe_code))]
 
 #[cfg(feature="alloc")]
 extern crate alloc;
@@ -710,12 +780,15 @@ pub mod core_impls;
 #[cfg(feature="collections")]
 pub mod collections;
 
-pub use at::{At, AT, Cps};
+pub use at::{At, AT, Cps, RenderPath, PathLen, VisitPath, TuplePath, AtAny, AtBy, Validated, TryAt, Checked, ReadAccess, WriteAccess, ReadOnly, ReadWrite, CpsNum, OkOr, OkOrElse};
 
-#[cfg(any(feature="batch_rt", feature="batch_ct"))]
+#[cfg(feature="alloc")]
+pub use at::{ CpsCollect, CpsString };
+
+#[cfg(any(feature="batch_rt", feature="batch_ct", feature="batch_bounded"))]
 mod batch;
 
-#[cfg(any(feature="batch_rt", feature="batch_ct"))]
+#[cfg(any(feature="batch_rt", feature="batch_ct", feature="batch_bounded"))]
 pub use batch::{ CpsBatch, Batch };
 
 #[cfg(feature="batch_ct")]
@@ -725,7 +798,88 @@ pub use batch::{ BatchCt };
 pub use batch::{ BatchRt };
 
 #[cfg(feature="detach")]
-pub use at::{ Attach, detached_at, DetachedPath };
+pub use at::{ Attach, detached_at, detached, DetachedPath, EmptyPath, PathTo, Named, DetachNamed };
+
+/// Derives field accessors and a typed path builder. __Requires `derive`.__
+///
+/// For a struct `Config` with named fields the derive generates:
+/// * a module `config_fields` containing a marker index for every field
+///   (named exactly as the field)
+/// * `At<config_fields::field, View=FieldType>` for `Config`
+/// * a trait `ConfigFields` with a method for every field, extending
+///   any [`AT`](struct.AT.html) viewing a `Config`
+///
+/// Combined with [`EmptyPath`](type.EmptyPath.html) it gives compile-time checked
+/// (and IDE-completable) detached paths:
+///
+/// ```
+/// use smart_access::{ At, Cps, Fields, EmptyPath };
+///
+/// #[derive(Fields)]
+/// struct Config { server: Server, name: String }
+///
+/// #[derive(Fields)]
+/// struct Server { port: u16 }
+///
+/// let mut config = Config { server: Server { port: 80 }, name: "test".into() };
+///
+/// let port = EmptyPath::<Config>::new().server().port();
+/// assert!(config.attach(port.clone()).replace(8080) == Some(80));
+/// assert!(config.attach(port).get_clone() == Some(8080));
+///
+/// // the markers can be used directly
+/// assert!(config.at(config_fields::name).access(|s| s.len()) == Some(4));
+///
+/// // an attached path can be built too
+/// assert!(config.cut().server().port().get_clone() == Some(8080));
+/// ```
+///
+/// Only structs with named fields are supported. The generated trait
+/// must be in scope to use the builder methods.
+///
+/// The markers and the `At` impls (but not the builder trait) can be
+/// generated without a proc-macro by [`impl_at_field!`](macro.impl_at_field.html).
+#[cfg(feature="derive")]
+pub use smart_access_derive::Fields;
+
+/// Derives `At` impls of a newtype delegating to the wrapped value. __Requires `derive`.__
+///
+/// For a struct with a single field of type `Inner` the derive generates:
+/// * `At<(), View=Inner>` unwrapping the value
+/// * `At<I, View=Inner::View>` for every index type `I` listed in the
+///   `#[at_delegate(..)]` attribute
+///
+/// The index types must be listed because a blanket forwarding impl would
+/// overlap with the blanket impls of the crate (e.g. for [`Idx`](core_impls/struct.Idx.html)).
+/// Index types containing references must have explicit lifetimes
+/// (e.g. `&'static str`).
+///
+/// ```
+/// use smart_access::{ AtDelegate, Cps };
+/// use std::collections::BTreeMap;
+/// use std::ops::Range;
+///
+/// #[derive(AtDelegate)]
+/// #[at_delegate(usize, Range<usize>)]
+/// struct UserList(Vec<String>);
+///
+/// #[derive(AtDelegate)]
+/// #[at_delegate(&'static str, (&'static str, u32))]
+/// struct Scores<V: Clone> { inner: BTreeMap<&'static str, V> }
+///
+/// let mut users = UserList(vec!["alice".into(), "bob".into()]);
+/// assert!(users.at(1).replace("carol".into()) == Some("bob".into()));
+/// assert!(users.at(0..2).access(|xs| xs.len()) == Some(2));
+/// assert!(users.at(()).at(5).touch() == None);
+///
+/// let mut scores = Scores { inner: BTreeMap::new() };
+/// scores.at(("alice", 0)).access(|x| *x += 10);
+/// assert!(scores.at("alice").get_clone() == Some(10));
+/// ```
+///
+/// Only structs with exactly one field are supported.
+#[cfg(feature="derive")]
+pub use smart_access_derive::AtDelegate;
 
 #[cfg(feature="iter_mut")]
 pub mod iter_mut;
@@ -733,4 +887,82 @@ pub mod iter_mut;
 #[cfg(feature="traversal")]
 pub use at::traversal;
 
+#[cfg(feature="dyn_path")]
+pub use at::{ DynPath, DynAT, RootedPath };
+
+#[cfg(feature="unsafe-cache")]
+pub use at::{ CachedAt };
+
+#[cfg(feature="unsafe-pair")]
+pub use at::{ Pair, Zip };
+
+#[cfg(feature="tokio")]
+pub mod tokio_impls;
+
+#[cfg(feature="either")]
+pub mod either_impls;
+
+#[cfg(feature="test_utils")]
+pub mod test_utils;
+
+#[cfg(feature="laws")]
+pub mod laws;
+
+#[cfg(feature="profile")]
+pub mod profile;
+
+#[cfg(feature="diff")]
+pub mod diff;
+
+#[cfg(feature="command")]
+pub mod command;
+
+#[cfg(feature="registry")]
+pub mod registry;
+
+#[cfg(feature="patch")]
+pub mod patch;
+
+#[cfg(feature="timing")]
+pub mod timing;
+
+#[cfg(feature="plan")]
+pub mod plan;
+
+#[cfg(feature="tree")]
+pub mod tree;
+
+#[cfg(feature="bindings")]
+pub mod bindings;
+
+#[cfg(feature="store")]
+pub mod store;
+
+#[cfg(feature="versioned")]
+pub mod versioned;
+
+#[cfg(feature="locks")]
+pub mod locks;
+
+#[cfg(feature="wire")]
+pub mod wire;
+
+#[cfg(feature="validation")]
+pub mod validation;
+
+#[cfg(feature="arbitrary")]
+pub mod arbitrary_impls;
+
+#[cfg(feature="csv")]
+pub mod csv;
+
+#[cfg(feature="encoding")]
+pub mod encoding;
+
+#[cfg(feature="globals")]
+pub mod globals;
+
+#[cfg(feature="ecs")]
+pub mod ecs;
+
 mod macros;
@@ -0,0 +1,238 @@
+//! Lock ordering for nested synchronized state. __Requires `locks`.__
+//!
+//! Nested locks invite deadlocks: if one thread locks `a` then `b` and
+//! another one locks `b` then `a` both can wait forever. The module provides
+//! a [`RankedMutex`](struct.RankedMutex.html): a mutex with a rank defining
+//! a global order of locking.
+//!
+//! * [`lock2`](fn.lock2.html), [`lock3`](fn.lock3.html) and
+//!   [`lock4`](fn.lock4.html) acquire several locks in the order of their
+//!   ranks (regardless of the order of the arguments)
+//! * in debug builds acquiring a lock while holding one with a greater
+//!   or equal rank (on the same thread) panics, thus ordering violations
+//!   are detected even if a deadlock doesn't occur
+//! * `At<(), View=T> for Arc<RankedMutex<T>>` locks the mutex for the
+//!   duration of the access (following the same rules), thus paths can
+//!   cross several locks
+//!
+//! ```
+//! use smart_access::Cps;
+//! use smart_access::locks::{ RankedMutex, lock2 };
+//! use std::sync::Arc;
+//!
+//! let alice = RankedMutex::new(1, 100);
+//! let bob = RankedMutex::new(2, 50);
+//!
+//! let transfer = |from: &RankedMutex<i32>, to: &RankedMutex<i32>, amount| {
+//!     lock2(from, to, |from, to| { *from -= amount; *to += amount; })
+//! };
+//!
+//! // both calls lock `alice` first
+//! assert!(transfer(&alice, &bob, 30) == Some(()));
+//! assert!(transfer(&bob, &alice, 10) == Some(()));
+//! assert!(alice.lock().unwrap().get_clone() == Some(80));
+//!
+//! // a path crossing two locks (the outer one has the lower rank)
+//! let inner = Arc::new(RankedMutex::new(20, vec![1, 2]));
+//! let mut outer = Arc::new(RankedMutex::new(10, vec![inner.clone()]));
+//!
+//! assert!(outer.at(()).at(0).at(()).at(1).replace(3) == Some(2));
+//! assert!(inner.lock().unwrap().at(1).get_clone() == Some(3));
+//! ```
+//!
+//! __Links to `std`__ (for `std::sync::Mutex`).
+
+extern crate std;
+
+use crate::at::{ At, Cps };
+use alloc::sync::Arc;
+use std::sync::{ Mutex, MutexGuard };
+
+#[cfg(debug_assertions)]
+use alloc::vec::Vec;
+
+#[cfg(debug_assertions)]
+use core::cell::RefCell;
+
+
+#[cfg(debug_assertions)]
+std::thread_local! {
+    // the ranks of the locks held by the current thread
+    static HELD: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+
+/// A mutex with a rank.
+///
+/// The ranks should be unique: locks must be acquired in the increasing order of ranks.
+#[derive(Debug, Default)]
+pub struct RankedMutex<T: ?Sized> {
+    rank: usize,
+    mutex: Mutex<T>,
+}
+
+impl<T> RankedMutex<T> {
+    /// Creates an unlocked mutex.
+    pub fn new(rank: usize, value: T) -> Self {
+        RankedMutex { rank, mutex: Mutex::new(value) }
+    }
+
+    /// Consumes the mutex. Returns `None` if it is poisoned.
+    pub fn into_inner(self) -> Option<T> {
+        self.mutex.into_inner().ok()
+    }
+}
+
+impl<T: ?Sized> RankedMutex<T> {
+    /// The rank of the mutex.
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    /// Acquires the mutex, blocking the current thread.
+    ///
+    /// Returns `None` if the mutex is poisoned.
+    ///
+    /// ### Panics
+    ///
+    /// In debug builds: if the current thread holds a ranked lock
+    /// with a greater or equal rank.
+    pub fn lock(&self) -> Option<RankedGuard<'_, T>> {
+        #[cfg(debug_assertions)]
+        HELD.with(|held| {
+            if let Some(&max) = held.borrow().iter().max() {
+                assert!(self.rank > max, "lock order violation: rank {} acquired while holding rank {}", self.rank, max);
+            }
+        });
+
+        let guard = self.mutex.lock().ok()?;
+
+        #[cfg(debug_assertions)]
+        HELD.with(|held| held.borrow_mut().push(self.rank));
+
+        Some(RankedGuard {
+            #[cfg(debug_assertions)]
+            rank: self.rank,
+            guard,
+        })
+    }
+
+    /// Accesses the data through an exclusive reference (no locking is needed).
+    ///
+    /// Returns `None` if the mutex is poisoned.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.mutex.get_mut().ok()
+    }
+}
+
+
+/// A guard of a [`RankedMutex`](struct.RankedMutex.html).
+///
+/// The lock is released when the guard is dropped (e.g. after an access).
+#[derive(Debug)]
+pub struct RankedGuard<'a, T: ?Sized> {
+    #[cfg(debug_assertions)]
+    rank: usize,
+    guard: MutexGuard<'a, T>,
+}
+
+impl<T: ?Sized> Drop for RankedGuard<'_, T> {
+    fn drop(&mut self) {
+        #[cfg(debug_assertions)]
+        let _ = HELD.try_with(|held| {
+            let mut held = held.borrow_mut();
+
+            if let Some(pos) = held.iter().rposition(|&rank| rank == self.rank) { held.remove(pos); }
+        });
+    }
+}
+
+/// `access` is guaranteed to return `Some(f(..))`
+impl<T: ?Sized> Cps for RankedGuard<'_, T> {
+    type View = T;
+
+    fn access<R, F>(mut self, f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        Some(f(&mut *self.guard))
+    }
+}
+
+
+/// `access_at` locks the mutex; returns `None` if it is poisoned
+impl<T: ?Sized> At<()> for Arc<RankedMutex<T>> {
+    type View = T;
+
+    fn access_at<R, F>(&mut self, _: (), f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        self.lock()?.access(f)
+    }
+}
+
+
+macro_rules! lock_n {
+    ( $(#[$meta:meta])* $name:ident: $( $T:ident $m:ident $g:ident $n:tt ),* ) => {
+        $(#[$meta])*
+        ///
+        /// The locks are acquired in the increasing order of ranks.
+        /// Returns `None` if any of the mutexes is poisoned.
+        pub fn $name<$($T: ?Sized,)* R, F>($($m: &RankedMutex<$T>,)* f: F) -> Option<R> where
+            F: FnOnce($(&mut $T),*) -> R
+        {
+            let mut order = [ $( ($m.rank, $n) ),* ];
+            order.sort_unstable();
+
+            $( let mut $g = None; )*
+
+            for &(_, i) in &order {
+                match i {
+                    $( $n => { $g = Some($m.lock()?); } )*
+                    _ => unreachable!(),
+                }
+            }
+
+            $( let mut $g = $g?; )*
+
+            Some(f( $( &mut *$g.guard ),* ))
+        }
+    };
+}
+
+lock_n!(
+    /// Locks two mutexes then accesses their data.
+    lock2: A a ga 0, B b gb 1
+);
+
+lock_n!(
+    /// Locks three mutexes then accesses their data.
+    lock3: A a ga 0, B b gb 1, C c gc 2
+);
+
+lock_n!(
+    /// Locks four mutexes then accesses their data.
+    lock4: A a ga 0, B b gb 1, C c gc 2, D d gd 3
+);
+
+
+#[test]
+fn test_lock_order() {
+    use std::panic::{ catch_unwind, AssertUnwindSafe };
+
+    let a = RankedMutex::new(1, 1);
+    let b = RankedMutex::new(2, 2);
+    let c = RankedMutex::new(3, 3);
+
+    assert!(lock3(&c, &a, &b, |c, a, b| { *c += *a + *b; *c }) == Some(6));
+
+    // the locks are released
+    assert!(lock2(&b, &c, |b, c| *b + *c) == Some(8));
+
+    let violation = catch_unwind(AssertUnwindSafe(|| {
+        let _outer = b.lock();
+        let _inner = a.lock();
+    }));
+
+    assert!(violation.is_err() == cfg!(debug_assertions));
+    assert!(a.lock().is_some());
+}
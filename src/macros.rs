@@ -47,3 +47,75 @@ macro_rules! path {
     ( ;; ) => { () };
 }
 
+
+
+/// Implements `At` for named fields of a struct without a proc-macro.
+///
+/// A declarative counterpart of [`Fields`](derive.Fields.html) (which needs
+/// the `derive` feature). The invocation
+/// `impl_at_field!(Config { server: Server, port: u16 })` generates
+/// a marker type for every listed field (named as the field) and
+/// an implementation of `At<marker, View=field type>` for `Config`.
+///
+/// The markers are declared at the invocation site. A marker named as a local
+/// variable would forbid such a variable (unit structs can't be shadowed),
+/// thus it's better to place them into a module:
+/// `impl_at_field!(pub mod config_fields; Config { .. })`.
+///
+/// Generic structs are not supported.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::{ Cps, impl_at_field };
+///
+/// struct Server { host: String, port: u16 }
+/// struct Config { server: Server, retries: u8 }
+///
+/// impl_at_field!(mod server_fields; Server { host: String, port: u16 });
+/// impl_at_field!(mod config_fields; Config { server: Server, retries: u8 });
+///
+/// # fn main() {
+/// let mut config = Config {
+///     server: Server { host: "localhost".to_string(), port: 80 },
+///     retries: 3,
+/// };
+///
+/// config.at(config_fields::server).at(server_fields::port).replace(8080);
+/// config.at(config_fields::retries).access(|r| *r += 1);
+///
+/// assert!(config.server.port == 8080);
+/// assert!(config.retries == 4);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! impl_at_field {
+    ( $vis:vis mod $module:ident; $name:ident { $($field:ident: $ty:ty),* $(,)? } ) => {
+        #[doc = concat!("Marker indices for the fields of [`", stringify!($name), "`].")]
+        $vis mod $module {
+            #[allow(unused_imports)]
+            use super::*;
+
+            $crate::impl_at_field!( $name { $($field: $ty),* } );
+        }
+    };
+
+    ( $name:ident { $($field:ident: $ty:ty),* $(,)? } ) => {
+        $(
+            #[doc = concat!("A marker index for the `", stringify!($field), "` field of [`", stringify!($name), "`].")]
+            #[allow(non_camel_case_types)]
+            #[derive(Debug, Copy, Clone)]
+            pub struct $field;
+
+            impl $crate::At<$field> for $name {
+                type View = $ty;
+
+                fn access_at<__R, __F>(&mut self, _: $field, f: __F) -> Option<__R> where
+                    __F: FnOnce(&mut $ty) -> __R
+                {
+                    Some(f(&mut self.$field))
+                }
+            }
+        )*
+    };
+}
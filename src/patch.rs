@@ -0,0 +1,567 @@
+//! A textual patch language. __Requires `patch`.__
+//!
+//! A patch is a single line of the form `path = value` where the path
+//! is a sequence of segments:
+//! * `name` or `.name`: a field name (a string key of a map)
+//! * `["name"]`: the same but the name can contain arbitrary characters
+//!   (`\"` and `\\` are the only escapes)
+//! * `[3]`: an index of a sequence
+//! * `[1..4]`: a (half-open) range of a sequence
+//!
+//! The value is the rest of the line (without surrounding whitespace).
+//!
+//! A parsed [`Patch`](struct.Patch.html) can be applied to any
+//! [`Patchable`](trait.Patchable.html) value. `Patchable` is implemented
+//! for scalars (through `FromStr`), `String`, `Option`, `Vec`,
+//! maps with `String` keys and (with the `serde_json` feature enabled)
+//! for `serde_json::Value`.
+//!
+//! ```
+//! use smart_access::patch::{ parse, Patchable };
+//! use std::collections::BTreeMap;
+//!
+//! let mut scores = BTreeMap::new();
+//! scores.insert("math".to_string(), 90);
+//!
+//! let mut users = BTreeMap::new();
+//! users.insert("alice".to_string(), vec![scores]);
+//!
+//! assert!(parse(r#"alice[0]["math"] = 95"#).unwrap().apply(&mut users));
+//! assert!(users["alice"][0]["math"] == 95);
+//!
+//! // a nonexistent path or an unparsable value
+//! assert!(!parse("bob[0].math = 95").unwrap().apply(&mut users));
+//! assert!(!parse("alice[0].math = many").unwrap().apply(&mut users));
+//!
+//! // a syntax error
+//! assert!(parse("alice[0 = 95").unwrap_err().position == 8);
+//! ```
+//!
+//! A patch is also convertible into a [command](../command/) for
+//! self-similar datatypes implementing `At<Key>` and `FromStr`
+//! (e.g. `serde_json::Value` with the `serde_json` feature enabled).
+//!
+//! A path alone can be parsed by [`parse_path`](fn.parse_path.html).
+//! The `Display` impl of `DynPath<Key>` renders the canonical form,
+//! which is parsed back into the same path (the same holds for patches):
+//!
+//! ```
+//! use smart_access::patch::parse_path;
+//!
+//! let path = parse_path(r#"[3] . name ["a b"][1..4]"#).unwrap();
+//! let canonical = path.to_string();
+//!
+//! assert!(canonical == r#"[3].name["a b"][1..4]"#);
+//! assert!(parse_path(&canonical).unwrap() == path);
+//! ```
+
+use crate::at::Cps;
+use crate::DynPath;
+use crate::command::Command;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
+
+
+/// A single path segment.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature="serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Key {
+    /// A field name or a string key of a map.
+    Name(String),
+
+    /// An index of a sequence.
+    Index(usize),
+
+    /// A half-open range of a sequence.
+    Range { start: usize, end: usize },
+}
+
+/// Renders a non-leading segment: `.name`, `["name"]`, `[3]` or `[1..4]`.
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Key::Name(name) if is_ident(name) => write!(f, ".{}", name),
+            Key::Name(name) => {
+                f.write_str("[\"")?;
+
+                for c in name.chars() {
+                    if c == '"' || c == '\\' { f.write_str("\\")?; }
+                    write!(f, "{}", c)?;
+                }
+
+                f.write_str("\"]")
+            }
+            Key::Index(i) => write!(f, "[{}]", i),
+            Key::Range { start, end } => write!(f, "[{}..{}]", start, end),
+        }
+    }
+}
+
+/// Renders the canonical form of a path (the leading dot is omitted).
+impl fmt::Display for DynPath<Key> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, key) in self.indices().iter().enumerate() {
+            match key {
+                Key::Name(name) if i == 0 && is_ident(name) => f.write_str(name)?,
+                key => write!(f, "{}", key)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn is_ident(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(is_ident_char)
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+
+/// A parsed patch: a runtime path and the textual representation of a value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Patch {
+    pub path: DynPath<Key>,
+    pub value: String,
+}
+
+impl Patch {
+    /// Applies the patch. Returns `false` if the path doesn't resolve
+    /// or the value can't be parsed.
+    pub fn apply<T: Patchable + ?Sized>(&self, root: &mut T) -> bool {
+        root.patch_at(self.path.indices(), &self.value)
+    }
+
+    /// Converts the patch into a replacement command.
+    ///
+    /// Returns `None` if the value can't be parsed.
+    pub fn command<T: FromStr>(&self) -> Option<Command<Key, T>> {
+        let value = self.value.parse().ok()?;
+
+        Some(Command::replace(self.path.clone(), value))
+    }
+}
+
+
+/// A syntax error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The byte offset of the error.
+    pub position: usize,
+
+    /// What was expected at the position.
+    pub expected: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected {} at position {}", self.expected, self.position)
+    }
+}
+
+
+/// Renders the patch as `path = value`.
+impl fmt::Display for Patch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}", self.path, self.value)
+    }
+}
+
+
+/// Parses a patch.
+pub fn parse(src: &str) -> Result<Patch, ParseError> {
+    let mut parser = Parser { src, pos: 0 };
+    let path = parser.path()?;
+
+    parser.expect('=', "`=`, `.` or `[`")?;
+
+    Ok(Patch { path, value: src[parser.pos..].trim().into() })
+}
+
+/// Parses a path.
+///
+/// The whole string (except surrounding whitespace) must be a path.
+pub fn parse_path(src: &str) -> Result<DynPath<Key>, ParseError> {
+    let mut parser = Parser { src, pos: 0 };
+    let path = parser.path()?;
+
+    if parser.peek().is_some() { return parser.error("`.`, `[` or the end"); }
+
+    Ok(path)
+}
+
+
+struct Parser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn error<T>(&self, expected: &'static str) -> Result<T, ParseError> {
+        Err(ParseError { position: self.pos, expected })
+    }
+
+    fn skip_ws(&mut self) {
+        let rest = &self.src[self.pos..];
+
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn expect(&mut self, c: char, expected: &'static str) -> Result<(), ParseError> {
+        if self.peek() != Some(c) { return self.error(expected); }
+
+        self.pos += c.len_utf8();
+
+        Ok(())
+    }
+
+    // the first segment is either a bare name or a bracketed one
+    fn path(&mut self) -> Result<DynPath<Key>, ParseError> {
+        let mut path = DynPath::new();
+
+        self.skip_ws();
+        match self.peek() {
+            Some('[') | None => {}
+            _ => path.push(Key::Name(self.ident()?)),
+        }
+        self.segments(&mut path)?;
+
+        Ok(path)
+    }
+
+    fn segments(&mut self, path: &mut DynPath<Key>) -> Result<(), ParseError> {
+        loop {
+            self.skip_ws();
+
+            match self.peek() {
+                Some('.') => {
+                    self.pos += 1;
+                    self.skip_ws();
+                    path.push(Key::Name(self.ident()?));
+                }
+                Some('[') => {
+                    self.pos += 1;
+                    self.skip_ws();
+                    path.push(self.bracketed()?);
+                    self.skip_ws();
+                    self.expect(']', "`]`")?;
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Option<usize> {
+        let rest = &self.src[self.pos..];
+        let len = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let n = rest[..len].parse().ok()?;
+
+        self.pos += len;
+
+        Some(n)
+    }
+
+    fn ident(&mut self) -> Result<String, ParseError> {
+        let rest = &self.src[self.pos..];
+        let len = rest
+            .find(|c: char| !is_ident_char(c))
+            .unwrap_or(rest.len());
+
+        if len == 0 { return self.error("a name"); }
+
+        self.pos += len;
+
+        Ok(rest[..len].into())
+    }
+
+    fn bracketed(&mut self) -> Result<Key, ParseError> {
+        let rest = &self.src[self.pos..];
+
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let mut name = String::new();
+            let mut chars = quoted.char_indices();
+
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    '"' => {
+                        self.pos += i + 2;
+                        return Ok(Key::Name(name));
+                    }
+                    '\\' => match chars.next() {
+                        Some((_, c)) if c == '"' || c == '\\' => name.push(c),
+                        _ => {
+                            self.pos += i + 1;
+                            return self.error("`\\\"` or `\\\\`");
+                        }
+                    },
+                    c => name.push(c),
+                }
+            }
+
+            self.pos = self.src.len();
+            return self.error("`\"`");
+        }
+
+        let start = match self.number() {
+            Some(start) => start,
+            None => return self.error("an index, a range or a quoted name"),
+        };
+
+        self.skip_ws();
+        if !self.src[self.pos..].starts_with("..") { return Ok(Key::Index(start)); }
+
+        self.pos += 2;
+        self.skip_ws();
+
+        match self.number() {
+            Some(end) => Ok(Key::Range { start, end }),
+            None => self.error("the end of a range"),
+        }
+    }
+}
+
+
+/// A value which can be patched along a path.
+pub trait Patchable {
+    /// Replaces the value at the path by the parsed `value`.
+    ///
+    /// Returns `false` if the path doesn't resolve or the value can't be parsed.
+    fn patch_at(&mut self, path: &[Key], value: &str) -> bool;
+}
+
+macro_rules! patchable_scalar {
+    ( $($t:ty),* ) => { $(
+        impl Patchable for $t {
+            fn patch_at(&mut self, path: &[Key], value: &str) -> bool {
+                if !path.is_empty() { return false; }
+
+                match value.parse() {
+                    Ok(x) => { *self = x; true }
+                    Err(_) => false,
+                }
+            }
+        }
+    )* };
+}
+
+patchable_scalar!(
+    i8, i16, i32, i64, i128, isize,
+    u8, u16, u32, u64, u128, usize,
+    f32, f64, bool, char
+);
+
+/// A quoted value is unquoted (with the same escapes as in quoted names).
+impl Patchable for String {
+    fn patch_at(&mut self, path: &[Key], value: &str) -> bool {
+        if !path.is_empty() { return false; }
+
+        if !value.starts_with('"') {
+            *self = value.into();
+            return true;
+        }
+
+        let mut parser = Parser { src: value, pos: 0 };
+
+        match parser.bracketed() {
+            Ok(Key::Name(name)) if parser.pos == value.len() => { *self = name; true }
+            _ => false,
+        }
+    }
+}
+
+/// The path goes through the `Some` value
+impl<T: Patchable> Patchable for Option<T> {
+    fn patch_at(&mut self, path: &[Key], value: &str) -> bool {
+        self.at(()).access(|x| x.patch_at(path, value)).unwrap_or(false)
+    }
+}
+
+impl<T: Patchable> Patchable for [T] {
+    fn patch_at(&mut self, path: &[Key], value: &str) -> bool {
+        match path.split_first() {
+            Some((Key::Index(i), rest)) => self.at(*i)
+                .access(|x| x.patch_at(rest, value))
+                .unwrap_or(false),
+            Some((Key::Range { start, end }, rest)) => self.get_mut(*start..*end)
+                .map(|xs| xs.patch_at(rest, value))
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+}
+
+impl<T: Patchable> Patchable for Vec<T> {
+    fn patch_at(&mut self, path: &[Key], value: &str) -> bool {
+        self.as_mut_slice().patch_at(path, value)
+    }
+}
+
+impl<T: Patchable> Patchable for BTreeMap<String, T> {
+    fn patch_at(&mut self, path: &[Key], value: &str) -> bool {
+        match path.split_first() {
+            Some((Key::Name(name), rest)) => self.at(name.as_str())
+                .access(|x| x.patch_at(rest, value))
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature="hashbrown")]
+impl<T: Patchable> Patchable for hashbrown::HashMap<String, T> {
+    fn patch_at(&mut self, path: &[Key], value: &str) -> bool {
+        match path.split_first() {
+            Some((Key::Name(name), rest)) => self.at(name.as_str())
+                .access(|x| x.patch_at(rest, value))
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+}
+
+
+#[cfg(feature="serde_json")]
+impl crate::At<Key> for serde_json::Value {
+    type View = serde_json::Value;
+
+    fn access_at<R, F>(&mut self, key: Key, f: F) -> Option<R> where
+        F: FnOnce(&mut serde_json::Value) -> R
+    {
+        match key {
+            Key::Name(name) => self.as_object_mut()?.get_mut(&name).map(f),
+            Key::Index(i) => self.as_array_mut()?.get_mut(i).map(f),
+            Key::Range { .. } => None,
+        }
+    }
+}
+
+#[cfg(feature="serde_json")]
+impl crate::command::Edit<Key> for serde_json::Value {
+    /// Inserts an array element or a missing object member.
+    fn insert_at(&mut self, key: Key, child: serde_json::Value) -> bool {
+        match (self, key) {
+            (serde_json::Value::Array(xs), Key::Index(i)) if i <= xs.len() => {
+                xs.insert(i, child); true
+            }
+            (serde_json::Value::Object(obj), Key::Name(name)) if !obj.contains_key(&name) => {
+                obj.insert(name, child); true
+            }
+            _ => false,
+        }
+    }
+
+    fn remove_at(&mut self, key: Key) -> Option<serde_json::Value> {
+        match (self, key) {
+            (serde_json::Value::Array(xs), Key::Index(i)) if i < xs.len() => Some(xs.remove(i)),
+            (serde_json::Value::Object(obj), Key::Name(name)) => obj.remove(&name),
+            _ => None,
+        }
+    }
+}
+
+/// A value which is not a valid JSON is treated as a string
+#[cfg(feature="serde_json")]
+impl Patchable for serde_json::Value {
+    fn patch_at(&mut self, path: &[Key], value: &str) -> bool {
+        let value = value.parse().unwrap_or_else(|_| serde_json::Value::String(value.into()));
+
+        self.at_dyn(path.iter().cloned().collect()).set(value)
+    }
+}
+
+
+#[test]
+fn test_parse() {
+    let patch = parse(r#" users [3] .scores["m\"a\\th"]=  95 "#).unwrap();
+
+    let path = DynPath::new()
+        .at(Key::Name("users".into()))
+        .at(Key::Index(3))
+        .at(Key::Name("scores".into()))
+        .at(Key::Name("m\"a\\th".into()));
+
+    assert!(patch == Patch { path, value: "95".into() });
+
+    assert!(parse("= 1").unwrap_err().position == 0);
+    assert!(parse("a.[0] = 1").unwrap_err().position == 2);
+    assert!(parse(r#"a["b] = 1"#).unwrap_err().position == 9);
+    assert!(parse(r#"a["\b"] = 1"#).unwrap_err().position == 3);
+    assert!(parse("a b = 1").unwrap_err() == ParseError { position: 2, expected: "`=`, `.` or `[`" });
+}
+
+#[test]
+fn test_paths() {
+    use alloc::string::ToString;
+    use alloc::vec;
+
+    let path = DynPath::new()
+        .at(Key::Name("users".into()))
+        .at(Key::Range { start: 1, end: 4 })
+        .at(Key::Index(0))
+        .at(Key::Name("".into()))
+        .at(Key::Name("m\"a\\th".into()));
+
+    let canonical = path.to_string();
+    assert!(canonical == r#"users[1..4][0][""]["m\"a\\th"]"#);
+    assert!(parse_path(&canonical).unwrap() == path);
+
+    let patch = Patch { path, value: "1".into() };
+    assert!(parse(&patch.to_string()).unwrap() == patch);
+
+    assert!(parse_path("").unwrap().is_empty());
+    assert!(parse_path("[ 2 .. 3 ]").unwrap().indices() == [Key::Range { start: 2, end: 3 }]);
+    assert!(parse_path("[2..]").unwrap_err().position == 4);
+    assert!(parse_path("a b").unwrap_err().position == 2);
+
+    let mut foo = vec![vec![0], vec![1], vec![2]];
+    let patch = parse("[1..3][1][0] = 5").unwrap();
+    assert!(parse(&patch.to_string()).unwrap() == patch);
+    assert!(patch.apply(&mut foo));
+    assert!(!parse("[1..4][0][0] = 5").unwrap().apply(&mut foo));
+    assert!(foo == vec![vec![0], vec![1], vec![5]]);
+}
+
+#[test]
+fn test_patch_values() {
+    let mut foo = BTreeMap::new();
+    foo.insert(String::from("x"), Some(String::from("a")));
+    foo.insert(String::from("y"), None);
+
+    assert!(parse(r#"x = "b \" c""#).unwrap().apply(&mut foo));
+    assert!(foo["x"].as_deref() == Some("b \" c"));
+
+    assert!(parse("x = plain text").unwrap().apply(&mut foo));
+    assert!(foo["x"].as_deref() == Some("plain text"));
+
+    assert!(!parse("y = 1").unwrap().apply(&mut foo));
+    assert!(!parse("x.z = 1").unwrap().apply(&mut foo));
+    assert!(!parse(r#"x = "unterminated"#).unwrap().apply(&mut foo));
+    assert!(foo["x"].as_deref() == Some("plain text"));
+}
+
+#[test]#[cfg(feature="serde_json")]
+fn test_patch_json() {
+    let mut json: serde_json::Value = serde_json::from_str(r#"{"users": [{"name": "a", "age": 1}]}"#).unwrap();
+
+    assert!(parse("users[0].age = 2").unwrap().apply(&mut json));
+    assert!(parse("users[0].name = b").unwrap().apply(&mut json));
+    assert!(!parse(r#"users[0].tags = ["x"]"#).unwrap().apply(&mut json));
+    assert!(json == serde_json::json!({"users": [{"name": "b", "age": 2}]}));
+
+    let command = parse(r#"users[0] = {"name": "c"}"#).unwrap().command::<serde_json::Value>().unwrap();
+    assert!(command.apply(&mut json));
+    assert!(json == serde_json::json!({"users": [{"name": "c"}]}));
+
+    let command = Command::<Key, serde_json::Value>::remove(parse("users[0] = _").unwrap().path);
+    assert!(command.apply(&mut json));
+    assert!(json == serde_json::json!({"users": []}));
+}
@@ -0,0 +1,107 @@
+//! Reusable mutation plans. __Requires `plan`.__
+//!
+//! A [`Plan`](struct.Plan.html) bundles a [detached path](../trait.Attach.html)
+//! with a mutation. Both are type-erased, thus plans of different shapes
+//! can be stored together and applied to many objects:
+//!
+//! ```
+//! use smart_access::{ Cps, detached_at };
+//! use smart_access::plan::Plan;
+//!
+//! let plans: Vec<Plan<Vec<Vec<i32>>, i32>> = vec![
+//!     Plan::new(detached_at(0).at(0), |x: &mut i32| { *x += 1; *x }),
+//!     Plan::new(detached_at(1), |xs: &mut Vec<i32>| { xs.push(0); xs.len() as i32 }),
+//! ];
+//!
+//! let mut objects = vec![
+//!     vec![ vec![1], vec![] ],
+//!     vec![ vec![], vec![2] ],
+//! ];
+//!
+//! let results = plans.iter()
+//!     .map(|plan| plan.apply_all(objects.iter_mut()))
+//!     .collect::<Vec<_>>();
+//!
+//! assert!(results == vec![
+//!     vec![ Some(2), None ],
+//!     vec![ Some(1), Some(2) ],
+//! ]);
+//!
+//! assert!(objects == vec![
+//!     vec![ vec![2], vec![0] ],
+//!     vec![ vec![], vec![2, 0] ],
+//! ]);
+//! ```
+
+use crate::at::Cps;
+use crate::Attach;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+
+
+type FnRc<Root, R> = Rc<dyn Fn(&mut Root) -> Option<R>>;
+
+/// A detached path bundled with a mutation.
+///
+/// Clones share the same path and mutation.
+pub struct Plan<Root: ?Sized, R> {
+    run: FnRc<Root, R>,
+}
+
+impl<Root: ?Sized, R> Clone for Plan<Root, R> {
+    fn clone(&self) -> Self {
+        Plan { run: self.run.clone() }
+    }
+}
+
+impl<Root: ?Sized, R> Plan<Root, R> {
+    /// Creates a plan.
+    pub fn new<P, V, F>(path: P, f: F) -> Self where
+        P: Attach<Root, View=V> + Clone + 'static,
+        V: ?Sized,
+        F: Fn(&mut V) -> R + 'static
+    {
+        Plan { run: Rc::new(move |root: &mut Root| root.attach(path.clone()).access(&f)) }
+    }
+
+    /// Applies the plan to an object.
+    ///
+    /// Returns `None` if the path doesn't resolve.
+    pub fn apply(&self, root: &mut Root) -> Option<R> {
+        (self.run)(root)
+    }
+
+    /// Applies the plan to every object.
+    pub fn apply_all<'a, I>(&self, roots: I) -> Vec<Option<R>> where
+        I: IntoIterator<Item=&'a mut Root>,
+        Root: 'a
+    {
+        roots.into_iter().map(|root| self.apply(root)).collect()
+    }
+
+    /// Combines two plans into a plan applying them in turn.
+    ///
+    /// The second plan is applied even if the first one fails.
+    pub fn then<S: 'static>(self, next: Plan<Root, S>) -> Plan<Root, (Option<R>, Option<S>)> where
+        Root: 'static,
+        R: 'static
+    {
+        Plan { run: Rc::new(move |root: &mut Root| Some((self.apply(root), next.apply(root)))) }
+    }
+}
+
+
+#[test]#[cfg(feature="collections")]
+fn test_plan() {
+    use crate::detached_at;
+    use alloc::vec;
+
+    let inc = Plan::new(detached_at(0), |x: &mut i32| { *x += 1; *x });
+    let both = inc.clone().then(Plan::new(detached_at(1), |x: &mut i32| *x));
+
+    let mut foo = vec![1];
+
+    assert!(inc.apply(&mut foo) == Some(2));
+    assert!(both.apply(&mut foo) == Some((Some(3), None)));
+    assert!(foo == vec![3]);
+}
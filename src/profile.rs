@@ -0,0 +1,245 @@
+//! Access profiling. __Requires `profile`.__
+//!
+//! A [`Profiled`](struct.Profiled.html) value counts accesses and failures
+//! per path. Paths are distinguished by their rendering (a chain of
+//! `.at(..)` calls with `Debug`-formatted indices).
+//!
+//! ```
+//! use smart_access::Cps;
+//! use smart_access::profile::{ Profiled, PathStats };
+//!
+//! let mut state = Profiled::new(vec![vec![1, 2], vec![3]]);
+//!
+//! for i in 0..3 {
+//!     state.at(0).at(i).access(|x| { *x += 1; });
+//! }
+//! state.at(1).at(0).touch();
+//! state.at(1).at(0).touch();
+//!
+//! let report = state.report();
+//!
+//! assert!(report[0] == (".at(1).at(0)", PathStats { accesses: 2, failures: 0 }));
+//! assert!(report.contains(&(".at(0).at(2)", PathStats { accesses: 1, failures: 1 })));
+//!
+//! assert!(state.get()[0] == vec![2, 3]);
+//! ```
+
+use crate::at::{ At, AtView, Cps, RenderPath };
+use core::fmt::Debug;
+use core::cmp::Reverse;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[cfg(feature="dyn_path")]
+use crate::DynPath;
+
+#[cfg(feature="dyn_path")]
+use core::fmt::Write;
+
+
+/// Statistics of a single path.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PathStats {
+    /// The total number of accesses.
+    pub accesses: usize,
+
+    /// The number of accesses which returned `None`.
+    pub failures: usize,
+}
+
+
+/// A value which counts accesses to it. __Requires `profile`.__
+///
+/// Paths are started with the [`at`](#method.at) method.
+///
+/// See the [module-level documentation](index.html) for an example.
+pub struct Profiled<T> {
+    value: T,
+    stats: BTreeMap<String, PathStats>,
+}
+
+impl<T> Profiled<T> {
+    /// Wraps a value.
+    pub fn new(value: T) -> Self {
+        Profiled { value, stats: BTreeMap::new() }
+    }
+
+    /// Starts a profiled path.
+    pub fn at<Index>(&mut self, i: Index) -> ProfiledPath<'_, T, ((), Index)> where
+        T: At<Index>,
+        Index: Debug
+    {
+        ProfiledPath { root: self, list: ((), i) }
+    }
+
+    /// Starts a profiled [runtime path](../struct.DynPath.html). __Requires `dyn_path`.__
+    ///
+    /// The path is rendered in the same way as a static one, thus
+    /// both kinds of paths share the statistics:
+    ///
+    /// ```
+    /// use smart_access::{ Cps, DynPath };
+    /// use smart_access::profile::Profiled;
+    /// use std::collections::BTreeMap;
+    ///
+    /// #[derive(Debug, Clone, PartialEq)]
+    /// struct Config(i32, BTreeMap<&'static str, Config>);
+    ///
+    /// impl smart_access::At<&'static str> for Config {
+    ///     type View = Config;
+    ///
+    ///     fn access_at<R, F>(&mut self, key: &'static str, f: F) -> Option<R> where
+    ///         F: FnOnce(&mut Config) -> R
+    ///     {
+    ///         self.1.get_mut(key).map(f)
+    ///     }
+    /// }
+    ///
+    /// let mut server = BTreeMap::new();
+    /// server.insert("port", Config(80, BTreeMap::new()));
+    ///
+    /// let mut root = BTreeMap::new();
+    /// root.insert("server", Config(0, server));
+    ///
+    /// let mut config = Profiled::new(Config(0, root));
+    ///
+    /// let port = DynPath::new().at("server").at("port");
+    /// let legacy = DynPath::new().at("server").at("legacy_port");
+    ///
+    /// assert!(config.at_dyn(port).access(|c| c.0) == Some(80));
+    /// assert!(config.at("server").at("port").access(|c| c.0) == Some(80));
+    /// assert!(config.at_dyn(legacy).access(|c| c.0) == None);
+    ///
+    /// assert!(config.stats()[".at(\"server\").at(\"port\")"].accesses == 2);
+    /// assert!(config.dead_paths() == vec![".at(\"server\").at(\"legacy_port\")"]);
+    /// ```
+    #[cfg(feature="dyn_path")]
+    pub fn at_dyn<I: Debug>(&mut self, path: DynPath<I>) -> ProfiledDynPath<'_, T, I> where
+        T: At<I, View=T>
+    {
+        ProfiledDynPath { root: self, path }
+    }
+
+    /// The paths accessed at least once which have never been resolved
+    /// (e.g. stale keys), in the order of their rendering.
+    pub fn dead_paths(&self) -> Vec<&str> {
+        self.stats.iter()
+            .filter(|(_, stats)| stats.failures == stats.accesses)
+            .map(|(path, _)| &path[..])
+            .collect()
+    }
+
+    /// Statistics for every path accessed so far, keyed by the path rendering.
+    pub fn stats(&self) -> &BTreeMap<String, PathStats> {
+        &self.stats
+    }
+
+    /// Statistics for every path accessed so far, the hottest paths first.
+    ///
+    /// Paths with the same number of accesses are ordered by their rendering.
+    pub fn report(&self) -> Vec<(&str, PathStats)> {
+        let mut report = self.stats.iter()
+            .map(|(path, stats)| (&path[..], *stats))
+            .collect::<Vec<_>>();
+
+        report.sort_by_key(|&(_, stats)| Reverse(stats.accesses));
+
+        report
+    }
+
+    /// Forgets all the statistics.
+    pub fn reset(&mut self) {
+        self.stats.clear();
+    }
+
+    /// A read-only reference to the wrapped value.
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Unwraps the value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+
+/// A path starting at a [`Profiled`](struct.Profiled.html) value.
+///
+/// Is counted when accessed.
+#[must_use]
+pub struct ProfiledPath<'a, T, List> {
+    root: &'a mut Profiled<T>,
+    list: List,
+}
+
+impl<'a, T, List> ProfiledPath<'a, T, List> {
+    /// Adds a new component to the profiled path.
+    pub fn at<Index, View>(self, i: Index) -> ProfiledPath<'a, T, (List, Index)> where
+        ProfiledPath<'a, T, List>: Cps<View=View>,
+        View: At<Index> + ?Sized,
+        Index: Debug
+    {
+        ProfiledPath { root: self.root, list: (self.list, i) }
+    }
+}
+
+/// `access` returns what the underlying path returns. The access is counted.
+impl<'a, T, List> Cps for ProfiledPath<'a, T, List> where
+    List: AtView<T> + RenderPath
+{
+    type View = List::View;
+
+    fn access<R, F>(self, f: F) -> Option<R> where
+        F: FnOnce(&mut Self::View) -> R
+    {
+        let mut path = String::new();
+        let _ = self.list.render(&mut path);
+
+        let root = self.root;
+        let result = self.list.give_access(&mut root.value, f);
+
+        let stats = root.stats.entry(path).or_default();
+        stats.accesses += 1;
+        if result.is_none() { stats.failures += 1; }
+
+        result
+    }
+}
+
+
+/// A runtime path starting at a [`Profiled`](struct.Profiled.html) value. __Requires `dyn_path`.__
+///
+/// Is counted when accessed.
+#[cfg(feature="dyn_path")]
+#[must_use]
+pub struct ProfiledDynPath<'a, T, I> {
+    root: &'a mut Profiled<T>,
+    path: DynPath<I>,
+}
+
+/// `access` returns what the underlying path returns. The access is counted.
+#[cfg(feature="dyn_path")]
+impl<'a, T, I> Cps for ProfiledDynPath<'a, T, I> where
+    T: At<I, View=T>,
+    I: Debug
+{
+    type View = T;
+
+    fn access<R, F>(self, f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        let mut path = String::new();
+        for i in self.path.indices() { let _ = write!(path, ".at({:?})", i); }
+
+        let root = self.root;
+        let result = root.value.at_dyn(self.path).access(f);
+
+        let stats = root.stats.entry(path).or_default();
+        stats.accesses += 1;
+        if result.is_none() { stats.failures += 1; }
+
+        result
+    }
+}
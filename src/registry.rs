@@ -0,0 +1,100 @@
+//! Named paths. __Requires `registry`.__
+//!
+//! A [`Registry`](struct.Registry.html) maps string names to
+//! [detached paths](../trait.Attach.html) sharing the same root and view types.
+//! The paths are type-erased on registration, thus a registry
+//! can hold paths of different shapes. For example a console
+//! can expose &#8220;settable properties&#8221; by name:
+//!
+//! ```
+//! use smart_access::detached_at;
+//! use smart_access::registry::Registry;
+//!
+//! let mut state = vec![ vec![10, 20], vec![30] ];
+//!
+//! let mut registry = Registry::<Vec<Vec<i32>>, i32>::new();
+//! registry.register("volume.left", detached_at(0).at(0));
+//! registry.register("volume.right", detached_at(0).at(1));
+//! registry.register("brightness", detached_at(1).at(0));
+//!
+//! assert!(registry.access(&mut state, "volume.right", |x| { *x += 1; *x }) == Some(21));
+//! assert!(registry.access(&mut state, "contrast", |x| *x) == None);
+//! assert!(state == vec![ vec![10, 21], vec![30] ]);
+//!
+//! let names = registry.names().collect::<Vec<_>>();
+//! assert!(names == vec!["brightness", "volume.left", "volume.right"]);
+//! ```
+
+use crate::at::Cps;
+use crate::Attach;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+
+type ErasedPath<Root, V> = Box<dyn Fn(&mut Root, &mut dyn FnMut(&mut V)) -> bool>;
+
+
+/// A mapping from names to type-erased detached paths.
+///
+/// See the [module-level documentation](index.html) for an example.
+pub struct Registry<Root: ?Sized, V: ?Sized> {
+    paths: BTreeMap<String, ErasedPath<Root, V>>,
+}
+
+impl<Root: ?Sized, V: ?Sized> Registry<Root, V> {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Registry { paths: BTreeMap::new() }
+    }
+
+    /// Registers a path under the name.
+    ///
+    /// Returns `true` if a path with the same name has been replaced.
+    pub fn register<P>(&mut self, name: &str, path: P) -> bool where
+        P: Attach<Root, View=V> + Clone + 'static
+    {
+        let erased = move |root: &mut Root, f: &mut dyn FnMut(&mut V)| {
+            root.attach(path.clone()).access(|v| f(v)).is_some()
+        };
+
+        self.paths.insert(name.into(), Box::new(erased)).is_some()
+    }
+
+    /// Removes the path with the name. Returns `false` if there is no such path.
+    pub fn unregister(&mut self, name: &str) -> bool {
+        self.paths.remove(name).is_some()
+    }
+
+    /// Checks if a path with the name is registered.
+    pub fn contains(&self, name: &str) -> bool {
+        self.paths.contains_key(name)
+    }
+
+    /// The names of the registered paths (in the lexicographic order).
+    pub fn names(&self) -> impl Iterator<Item=&str> {
+        self.paths.keys().map(|name| name.as_str())
+    }
+
+    /// Accesses the root along the path with the name.
+    ///
+    /// Returns `None` if there is no such path or the path doesn't resolve.
+    pub fn access<R, F>(&self, root: &mut Root, name: &str, f: F) -> Option<R> where
+        F: FnOnce(&mut V) -> R
+    {
+        let path = self.paths.get(name)?;
+
+        let mut f = Some(f);
+        let mut result = None;
+
+        path(root, &mut |v| { result = f.take().map(|f| f(v)); });
+
+        result
+    }
+}
+
+impl<Root: ?Sized, V: ?Sized> Default for Registry<Root, V> {
+    fn default() -> Self {
+        Registry::new()
+    }
+}
@@ -0,0 +1,182 @@
+//! A store with dispatch and middleware. __Requires `store`.__
+//!
+//! A [`Store`](struct.Store.html) owns the root value. Mutations are
+//! submitted as [actions](struct.Action.html) (a named detached path paired
+//! with a mutation) via [`dispatch`](struct.Store.html#method.dispatch).
+//! Every action passes through the middleware (in the order of registration)
+//! before being applied. A middleware can log the action, validate it,
+//! replace it or swallow it by not calling [`Next::run`](struct.Next.html#method.run):
+//!
+//! ```
+//! use smart_access::detached_at;
+//! use smart_access::store::{ Store, Action };
+//! use std::rc::Rc;
+//! use std::cell::RefCell;
+//!
+//! let log = Rc::new(RefCell::new(Vec::new()));
+//! let log_ref = log.clone();
+//!
+//! let mut store = Store::new(vec![10, 20]);
+//!
+//! // logging
+//! store.add_middleware(move |root, action, next| {
+//!     log_ref.borrow_mut().push(action.name().to_string());
+//!     next.run(root, action)
+//! });
+//!
+//! // validation: roll back the actions producing negative values
+//! store.add_middleware(|root: &mut Vec<i32>, action, next| {
+//!     let backup = root.clone();
+//!     let applied = next.run(root, action);
+//!
+//!     if root.iter().any(|&x| x < 0) { *root = backup; false } else { applied }
+//! });
+//!
+//! assert!(store.dispatch(Action::new("inc", detached_at(0), |x: &mut i32| *x += 1)));
+//! assert!(!store.dispatch(Action::new("sub", detached_at(1), |x: &mut i32| *x -= 30)));
+//! assert!(!store.dispatch(Action::new("oob", detached_at(2), |x: &mut i32| *x = 0)));
+//!
+//! assert!(*store.state() == vec![11, 20]);
+//! assert!(*log.borrow() == vec!["inc", "sub", "oob"]);
+//! ```
+
+use crate::at::Cps;
+use crate::Attach;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+
+type MiddlewareFn<T> = Box<dyn FnMut(&mut T, Action<T>, Next<'_, T>) -> bool>;
+
+
+/// A named detached path paired with a mutation.
+pub struct Action<T: ?Sized> {
+    name: String,
+    run: Box<dyn FnOnce(&mut T) -> bool>,
+}
+
+impl<T: ?Sized> Action<T> {
+    /// Creates an action.
+    pub fn new<P, V, F>(name: &str, path: P, f: F) -> Self where
+        P: Attach<T, View=V> + 'static,
+        V: ?Sized,
+        F: FnOnce(&mut V) + 'static
+    {
+        Action { name: name.into(), run: Box::new(move |root: &mut T| root.attach(path).access(f).is_some()) }
+    }
+
+    /// The name of the action.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Applies the action bypassing any middleware.
+    ///
+    /// Returns `false` if the path doesn't resolve.
+    pub fn apply(self, root: &mut T) -> bool {
+        (self.run)(root)
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for Action<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Action").field("name", &self.name).finish()
+    }
+}
+
+
+/// The rest of the middleware chain.
+pub struct Next<'a, T> {
+    rest: &'a mut [MiddlewareFn<T>],
+}
+
+impl<T> Next<'_, T> {
+    /// Passes the action to the next middleware (or applies it if there is none).
+    pub fn run(self, root: &mut T, action: Action<T>) -> bool {
+        match self.rest.split_first_mut() {
+            Some((middleware, rest)) => middleware(root, action, Next { rest }),
+            None                     => action.apply(root),
+        }
+    }
+}
+
+
+/// An owner of the root value applying the dispatched actions.
+///
+/// See the [module-level documentation](index.html) for an example.
+pub struct Store<T> {
+    state: T,
+    middleware: Vec<MiddlewareFn<T>>,
+}
+
+impl<T> Store<T> {
+    /// Creates a store without middleware.
+    pub fn new(state: T) -> Self {
+        Store { state, middleware: Vec::new() }
+    }
+
+    /// Appends a middleware to the chain.
+    pub fn add_middleware<M>(&mut self, middleware: M) where
+        M: FnMut(&mut T, Action<T>, Next<'_, T>) -> bool + 'static
+    {
+        self.middleware.push(Box::new(middleware));
+    }
+
+    /// Passes the action through the middleware chain.
+    ///
+    /// Returns `true` if the action has been applied.
+    pub fn dispatch(&mut self, action: Action<T>) -> bool {
+        Next { rest: &mut self.middleware }.run(&mut self.state, action)
+    }
+
+    /// Dispatches all actions in order.
+    pub fn dispatch_all<I>(&mut self, actions: I) -> Vec<bool> where
+        I: IntoIterator<Item=Action<T>>
+    {
+        actions.into_iter().map(|action| self.dispatch(action)).collect()
+    }
+
+    /// The current state.
+    pub fn state(&self) -> &T {
+        &self.state
+    }
+
+    /// Extracts the state.
+    pub fn into_inner(self) -> T {
+        self.state
+    }
+}
+
+
+#[cfg(feature="collections")]
+#[test]
+fn test_store() {
+    use crate::detached_at;
+    use alloc::vec;
+
+    let mut store = Store::new(vec![vec![1], vec![2, 3]]);
+
+    // doubles every action
+    store.add_middleware(|root, action, next| {
+        let name = String::from(action.name());
+        let again = Action::new(&name, detached_at(0).at(0), |x: &mut i32| *x *= 2);
+
+        next.run(root, action) && again.apply(root)
+    });
+
+    // swallows the actions named "skip"
+    store.add_middleware(|root, action, next| {
+        action.name() != "skip" && next.run(root, action)
+    });
+
+    let results = store.dispatch_all(vec![
+        Action::new("add", detached_at(0).at(0), |x: &mut i32| *x += 1),
+        Action::new("skip", detached_at(1).at(0), |x: &mut i32| *x += 1),
+        Action::new("push", detached_at(1), |xs: &mut Vec<i32>| xs.push(4)),
+    ]);
+
+    assert!(results == vec![true, false, true]);
+    assert!(store.into_inner() == vec![vec![8], vec![2, 3, 4]]);
+}
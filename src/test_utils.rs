@@ -0,0 +1,239 @@
+//! Helpers for testing code built on accessors. __Requires `test_utils`.__
+//!
+//! The main type of this module is [`RecordingCps`](struct.RecordingCps.html):
+//! a wrapper around some value which logs every access performed
+//! through it.
+//!
+//! It allows one to check that a function touched exactly the
+//! locations it was supposed to touch:
+//!
+//! ```
+//! use smart_access::Cps;
+//! use smart_access::test_utils::RecordingCps;
+//!
+//! fn bump_first<CPS: Cps<View=i32>>(first: CPS, second: impl Cps<View=i32>) {
+//!     first.access(|x| { *x += 1; });
+//!     let _ = second;
+//! }
+//!
+//! let mut rec = RecordingCps::tracking(vec![vec![1, 2], vec![3]]);
+//!
+//! bump_first(rec.at(0).at(1), vec![0].at(0));
+//! rec.at(5).touch();
+//!
+//! assert!(rec.paths() == vec![".at(0).at(1)", ".at(5)"]);
+//!
+//! assert!(rec.log()[0].resolved);
+//! assert!(rec.log()[0].changed() == Some(true));
+//! assert!(!rec.log()[1].resolved);
+//! assert!(rec.log()[1].changed() == Some(false));
+//!
+//! assert!(rec.into_inner() == vec![vec![1, 3], vec![3]]);
+//! ```
+
+use crate::at::{ At, AtView, Cps, RenderPath };
+use core::fmt::Debug;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+
+/// A single access logged by a [`RecordingCps`](struct.RecordingCps.html).
+#[derive(Debug, Clone)]
+pub struct Record<T> {
+    /// The rendered path, e.g. `.at(0).at("key")`.
+    pub path: String,
+
+    /// Whether the path resolved (i.e. whether `access` returned `Some`).
+    pub resolved: bool,
+
+    /// Old and new values of the whole recorded value.
+    ///
+    /// Present only if the recorder was created by
+    /// [`tracking`](struct.RecordingCps.html#method.tracking).
+    pub values: Option<(T, T)>,
+}
+
+impl<T: PartialEq> Record<T> {
+    /// Whether the access changed the recorded value.
+    ///
+    /// Returns `None` if values were not tracked.
+    pub fn changed(&self) -> Option<bool> {
+        self.values.as_ref().map(|(old, new)| old != new)
+    }
+}
+
+
+/// A value which records every access to it. __Requires `test_utils`.__
+///
+/// Paths are started with the [`at`](#method.at) method and are rendered
+/// with the `Debug` implementations of their indices.
+///
+/// See the [module-level documentation](index.html) for an example.
+pub struct RecordingCps<T> {
+    value: T,
+    log: Vec<Record<T>>,
+    snapshot: Option<fn(&T) -> T>,
+}
+
+impl<T> RecordingCps<T> {
+    /// Wraps a value. Old and new values are not recorded.
+    pub fn new(value: T) -> Self {
+        RecordingCps { value, log: Vec::new(), snapshot: None }
+    }
+
+    /// Wraps a value. Every record contains the values before and after the access.
+    pub fn tracking(value: T) -> Self where
+        T: Clone
+    {
+        RecordingCps { value, log: Vec::new(), snapshot: Some(T::clone) }
+    }
+
+    /// Starts a recorded path.
+    pub fn at<Index>(&mut self, i: Index) -> Recorded<'_, T, ((), Index)> where
+        T: At<Index>,
+        Index: Debug
+    {
+        Recorded { root: self, list: ((), i) }
+    }
+
+    /// All the accesses performed so far.
+    pub fn log(&self) -> &[Record<T>] {
+        &self.log
+    }
+
+    /// The rendered paths of all the accesses performed so far.
+    pub fn paths(&self) -> Vec<&str> {
+        self.log.iter().map(|record| &record.path[..]).collect()
+    }
+
+    /// Forgets all the accesses performed so far.
+    pub fn clear_log(&mut self) {
+        self.log.clear();
+    }
+
+    /// A read-only reference to the wrapped value.
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Unwraps the value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+
+/// Checks the [`At`](../trait.At.html) contract for the value and the index.
+///
+/// Runs an access (with a closure which doesn't touch the view) on a clone
+/// of the value and panics if
+/// * `access_at` returns `None` but the value has changed
+/// * the closure has been called more than once
+///
+/// Returns whether the index resolved (i.e. whether `access_at` returned `Some`).
+///
+/// See also the [`laws`](../laws/) module (__requires `laws`__) for the
+/// checks of lens-like accessors.
+///
+/// ### Usage example
+///
+/// ```
+/// use smart_access::test_utils::check_at_contract;
+///
+/// assert!(check_at_contract(&vec![1, 2], 1));
+/// assert!(!check_at_contract(&vec![1, 2], 2));
+/// ```
+///
+/// A buggy implementation is caught:
+///
+/// ``` should_panic
+/// use smart_access::At;
+/// use smart_access::test_utils::check_at_contract;
+///
+/// #[derive(Clone, PartialEq)]
+/// struct Stack(Vec<i32>);
+///
+/// // pops the top before checking the index
+/// impl At<usize> for Stack {
+///     type View = i32;
+///
+///     fn access_at<R, F>(&mut self, i: usize, f: F) -> Option<R> where
+///         F: FnOnce(&mut i32) -> R
+///     {
+///         let mut top = self.0.pop()?;
+///         if i != 0 { return None; }
+///
+///         let result = f(&mut top);
+///         self.0.push(top);
+///
+///         Some(result)
+///     }
+/// }
+///
+/// check_at_contract(&Stack(vec![1, 2]), 1);
+/// ```
+pub fn check_at_contract<T, I>(value: &T, i: I) -> bool where
+    T: At<I> + Clone + PartialEq
+{
+    let mut t = value.clone();
+    let mut calls = 0;
+
+    let result = t.access_at(i, |_| { calls += 1; });
+
+    assert!(calls <= 1, "the closure has been called {} times", calls);
+
+    if result.is_none() {
+        assert!(t == *value, "the access failed but the value has changed");
+    }
+
+    result.is_some()
+}
+
+
+/// A path starting at a [`RecordingCps`](struct.RecordingCps.html).
+///
+/// Is logged when accessed.
+#[must_use]
+pub struct Recorded<'a, T, List> {
+    root: &'a mut RecordingCps<T>,
+    list: List,
+}
+
+impl<'a, T, List> Recorded<'a, T, List> {
+    /// Adds a new component to the recorded path.
+    pub fn at<Index, View>(self, i: Index) -> Recorded<'a, T, (List, Index)> where
+        Recorded<'a, T, List>: Cps<View=View>,
+        View: At<Index> + ?Sized,
+        Index: Debug
+    {
+        Recorded { root: self.root, list: (self.list, i) }
+    }
+}
+
+/// `access` returns what the underlying path returns. The access is logged.
+impl<'a, T, List> Cps for Recorded<'a, T, List> where
+    List: AtView<T> + RenderPath
+{
+    type View = List::View;
+
+    fn access<R, F>(self, f: F) -> Option<R> where
+        F: FnOnce(&mut Self::View) -> R
+    {
+        let mut path = String::new();
+        let _ = self.list.render(&mut path);
+
+        let root = self.root;
+        let old = root.snapshot.map(|snapshot| snapshot(&root.value));
+        let result = self.list.give_access(&mut root.value, f);
+
+        let values = match (old, root.snapshot) {
+            (Some(old), Some(snapshot)) => Some((old, snapshot(&root.value))),
+            _ => None,
+        };
+
+        root.log.push(Record { path, resolved: result.is_some(), values });
+
+        result
+    }
+}
+
@@ -0,0 +1,160 @@
+//! Timing of batch steps. __Requires `timing`.__
+//!
+//! The instrumentation is opt-in: a step function wrapped
+//! by [`Timings::step`](struct.Timings.html#method.step) records its
+//! wall time (and an optional label) each time it runs. The records
+//! can be inspected after the batch has been run.
+//!
+//! Works with both [compile-time and runtime batches](../struct.CpsBatch.html):
+//!
+//! ```
+//! use smart_access::Cps;
+//! use smart_access::timing::Timings;
+//!
+//! let mut data = vec![3, 1, 2];
+//! let timings = Timings::new();
+//!
+//! let result = data.batch_rt()
+//!     .add(timings.step("sort", |v: &mut Vec<i32>, _| { v.sort(); v.len() }))
+//!     .add(timings.step("sum", |v: &mut Vec<i32>, _| v.iter().sum::<i32>() as usize))
+//!     .run();
+//!
+//! assert!(result == Some(6));
+//!
+//! let labels = timings.records().iter().map(|r| r.label).collect::<Vec<_>>();
+//! assert!(labels == vec![ Some("sort"), Some("sum") ]);
+//!
+//! // the slowest step takes no less time than any other one
+//! let slowest = timings.slowest().unwrap();
+//! assert!(timings.records().iter().all(|r| r.duration <= slowest.duration));
+//! assert!(slowest.duration <= timings.total());
+//! ```
+//!
+//! __Links to `std`__ (for `std::time::Instant`).
+
+extern crate std;
+
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::time::Duration;
+use std::time::Instant;
+
+
+/// A record of a single run of a step.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StepTiming {
+    /// The label of the step (if present).
+    pub label: Option<&'static str>,
+
+    /// The wall time of the run.
+    pub duration: Duration,
+}
+
+
+/// A shared log of step timings.
+///
+/// Clones share the same log.
+#[derive(Debug, Clone, Default)]
+pub struct Timings {
+    log: Rc<RefCell<Vec<StepTiming>>>,
+}
+
+impl Timings {
+    /// Creates an empty log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wraps a labeled step function.
+    pub fn step<V: ?Sized, P, R, F>(&self, label: &'static str, f: F) -> impl FnOnce(&mut V, P) -> R where
+        F: FnOnce(&mut V, P) -> R
+    {
+        self.wrap(Some(label), f)
+    }
+
+    /// Wraps an unlabeled step function.
+    pub fn unlabeled<V: ?Sized, P, R, F>(&self, f: F) -> impl FnOnce(&mut V, P) -> R where
+        F: FnOnce(&mut V, P) -> R
+    {
+        self.wrap(None, f)
+    }
+
+    /// Wraps a labeled step function of a [looping batch](../struct.CpsBatch.html).
+    pub fn step_mut<V: ?Sized, P, R, F>(&self, label: &'static str, mut f: F) -> impl FnMut(&mut V, P) -> R where
+        F: FnMut(&mut V, P) -> R
+    {
+        let log = self.log.clone();
+
+        move |v, prev| {
+            let start = Instant::now();
+            let result = f(v, prev);
+
+            log.borrow_mut().push(StepTiming { label: Some(label), duration: start.elapsed() });
+
+            result
+        }
+    }
+
+    fn wrap<V: ?Sized, P, R, F>(&self, label: Option<&'static str>, f: F) -> impl FnOnce(&mut V, P) -> R where
+        F: FnOnce(&mut V, P) -> R
+    {
+        let log = self.log.clone();
+
+        move |v, prev| {
+            let start = Instant::now();
+            let result = f(v, prev);
+
+            log.borrow_mut().push(StepTiming { label, duration: start.elapsed() });
+
+            result
+        }
+    }
+
+    /// The records in the order of the runs.
+    pub fn records(&self) -> Vec<StepTiming> {
+        self.log.borrow().clone()
+    }
+
+    /// The slowest run.
+    pub fn slowest(&self) -> Option<StepTiming> {
+        self.log.borrow().iter().max_by_key(|r| r.duration).cloned()
+    }
+
+    /// The total wall time of all runs.
+    pub fn total(&self) -> Duration {
+        self.log.borrow().iter().map(|r| r.duration).sum()
+    }
+
+    /// Clears the log.
+    pub fn reset(&self) {
+        self.log.borrow_mut().clear();
+    }
+}
+
+
+#[test]#[cfg(feature="batch")]
+fn test_timings() {
+    use crate::Cps;
+
+    let mut foo = 0;
+    let timings = Timings::new();
+
+    foo.batch_ct()
+        .add(timings.step("a", |x: &mut i32, _| { *x += 1; }))
+        .add(timings.unlabeled(|x: &mut i32, _| { *x += 1; }))
+        .run();
+
+    foo.batch_loop()
+        .add(timings.step_mut("loop", |x: &mut i32, _| { *x += 1; }))
+        .run_until(3, |_, _| false);
+
+    assert!(foo == 5);
+
+    let labels = timings.records().iter().map(|r| r.label).collect::<Vec<_>>();
+    assert!(labels == alloc::vec![ Some("a"), None, Some("loop"), Some("loop"), Some("loop") ]);
+    assert!(timings.total() >= timings.slowest().unwrap().duration);
+
+    timings.reset();
+    assert!(timings.records().is_empty());
+}
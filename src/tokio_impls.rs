@@ -0,0 +1,329 @@
+//! Support for `tokio` synchronization primitives. __Requires `tokio`.__
+//!
+//! The following traits are implemented:
+//! * `Cps<View=T>` for `MutexGuard<T>`, `OwnedMutexGuard<T>`,
+//!   `RwLockWriteGuard<T>` and `OwnedRwLockWriteGuard<T>`: the lock
+//!   is held until the access ends and is released afterwards
+//! * `At<(), View=T> for Mutex<T>` and `At<(), View=T> for RwLock<T>`:
+//!   access through an exclusive reference (no locking is needed)
+//!
+//! The async entry points (__require `detach`__) are
+//! [`access_async`](fn.access_async.html) and [`write_async`](fn.write_async.html):
+//! they await the lock then access the view at a [detached path](../trait.Attach.html).
+//! Thus the shared state of an async application can be accessed
+//! without any manual guard juggling:
+//!
+//! ```
+//! use smart_access::detached_at;
+//! use smart_access::tokio_impls::access_async;
+//! use tokio::sync::Mutex;
+//! use std::sync::Arc;
+//!
+//! async fn update(state: Arc<Mutex<Vec<Vec<i32>>>>) -> Option<i32> {
+//!     access_async(&state, detached_at(1).at(0), |x| std::mem::replace(x, 5)).await
+//! }
+//! # let _ = update;
+//! ```
+//!
+//! A guard can also be accessed directly (e.g. outside of async code):
+//!
+//! ```
+//! use smart_access::Cps;
+//! use tokio::sync::Mutex;
+//!
+//! let state = Mutex::new(vec![vec![1, 2], vec![3, 4]]);
+//!
+//! assert!(state.try_lock().unwrap().at(1).at(0).replace(5) == Some(3));
+//!
+//! // the lock is released right after the access
+//! assert!(state.try_lock().unwrap().at(1).get_clone() == Some(vec![5, 4]));
+//! ```
+//!
+//! To hold the lock across await points use [`lock_async`](fn.lock_async.html)
+//! or [`write_lock_async`](fn.write_lock_async.html): they run an async closure
+//! on the locked value, thus accessor paths can be used between the await points:
+//!
+//! ```
+//! use smart_access::Cps;
+//! use smart_access::tokio_impls::lock_async;
+//! use tokio::sync::Mutex;
+//!
+//! async fn refresh(state: &Mutex<Vec<Vec<i32>>>) -> Option<i32> {
+//!     lock_async(state, |rows| Box::pin(async move {
+//!         rows.at(0).access(|row| row.push(1));
+//!         // e.g. fetching the new value
+//!         let fresh = async { 2 }.await;
+//!         rows.at(0).at(0).replace(fresh)
+//!     })).await
+//! }
+//! # let _ = refresh;
+//! ```
+//!
+//! ### Note
+//!
+//! The closures passed to `access`, `access_async` and `write_async` are
+//! synchronous, thus the lock is held only during the (blocking) access.
+//! The closure passed to `lock_async` returns a boxed future which
+//! can't borrow anything but the locked value (use owned or `'static`
+//! data, e.g. `Arc` clones, for the rest).
+
+use crate::{ At, Cps };
+use core::future::Future;
+use core::pin::Pin;
+
+extern crate std;
+use std::boxed::Box;
+
+#[cfg(feature="detach")]
+use crate::Attach;
+use tokio::sync::{
+    Mutex, MutexGuard, OwnedMutexGuard,
+    RwLock, RwLockWriteGuard, OwnedRwLockWriteGuard
+};
+
+
+/// `access` is guaranteed to return `Some(f(..))`
+impl<'a, T: ?Sized> Cps for MutexGuard<'a, T> {
+    type View = T;
+
+    fn access<R, F>(mut self, f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        Some(f(&mut *self))
+    }
+}
+
+/// `access` is guaranteed to return `Some(f(..))`
+impl<T: ?Sized> Cps for OwnedMutexGuard<T> {
+    type View = T;
+
+    fn access<R, F>(mut self, f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        Some(f(&mut *self))
+    }
+}
+
+/// `access` is guaranteed to return `Some(f(..))`
+impl<'a, T: ?Sized> Cps for RwLockWriteGuard<'a, T> {
+    type View = T;
+
+    fn access<R, F>(mut self, f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        Some(f(&mut *self))
+    }
+}
+
+/// `access` is guaranteed to return `Some(f(..))`
+impl<T: ?Sized> Cps for OwnedRwLockWriteGuard<T> {
+    type View = T;
+
+    fn access<R, F>(mut self, f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        Some(f(&mut *self))
+    }
+}
+
+
+impl<T: ?Sized> At<()> for Mutex<T> {
+    type View = T;
+
+    fn access_at<R, F>(&mut self, _: (), f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        Some(f(self.get_mut()))
+    }
+}
+
+impl<T: ?Sized> At<()> for RwLock<T> {
+    type View = T;
+
+    fn access_at<R, F>(&mut self, _: (), f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        Some(f(self.get_mut()))
+    }
+}
+
+
+
+/// Locks the mutex then accesses the view at the path. __Requires `detach`.__
+///
+/// The lock is released as soon as the access ends.
+/// Returns `None` if the path doesn't resolve.
+#[cfg(feature="detach")]
+pub async fn access_async<T, P, V, R, F>(mutex: &Mutex<T>, path: P, f: F) -> Option<R> where
+    T: ?Sized,
+    P: Attach<T, View=V>,
+    V: ?Sized,
+    F: FnOnce(&mut V) -> R
+{
+    mutex.lock().await.attach(path).access(f)
+}
+
+/// Write-locks the `RwLock` then accesses the view at the path. __Requires `detach`.__
+///
+/// The lock is released as soon as the access ends.
+/// Returns `None` if the path doesn't resolve.
+#[cfg(feature="detach")]
+pub async fn write_async<T, P, V, R, F>(lock: &RwLock<T>, path: P, f: F) -> Option<R> where
+    T: ?Sized,
+    P: Attach<T, View=V>,
+    V: ?Sized,
+    F: FnOnce(&mut V) -> R
+{
+    lock.write().await.attach(path).access(f)
+}
+
+/// A boxed future borrowing a locked value.
+///
+/// Returned by the closures passed to [`lock_async`](fn.lock_async.html)
+/// and [`write_lock_async`](fn.write_lock_async.html).
+pub type LockedFuture<'a, R> = Pin<Box<dyn Future<Output=R> + Send + 'a>>;
+
+/// Locks the mutex then runs an async closure on the value.
+///
+/// The lock is held across the await points of the closure
+/// and is released when the future returned by the closure completes.
+pub async fn lock_async<T, R, F>(mutex: &Mutex<T>, f: F) -> R where
+    T: ?Sized,
+    F: for<'v> FnOnce(&'v mut T) -> LockedFuture<'v, R>
+{
+    let mut guard = mutex.lock().await;
+
+    f(&mut *guard).await
+}
+
+/// Write-locks the `RwLock` then runs an async closure on the value.
+///
+/// The lock is held across the await points of the closure
+/// and is released when the future returned by the closure completes.
+pub async fn write_lock_async<T, R, F>(lock: &RwLock<T>, f: F) -> R where
+    T: ?Sized,
+    F: for<'v> FnOnce(&'v mut T) -> LockedFuture<'v, R>
+{
+    let mut guard = lock.write().await;
+
+    f(&mut *guard).await
+}
+
+#[test]#[cfg(feature="collections")]
+fn test_tokio_locks() {
+    extern crate std;
+    use std::sync::Arc;
+    use std::vec;
+
+    let mutex = Arc::new(Mutex::new(Some(1)));
+
+    assert!(mutex.clone().try_lock_owned().unwrap().at(()).replace(2) == Some(1));
+    assert!(mutex.try_lock().unwrap().at(()).replace(3) == Some(2));
+
+    let mut rwlock = RwLock::new(vec![1, 2, 3]);
+
+    assert!(rwlock.try_write().unwrap().at(0).replace(4) == Some(1));
+    assert!(rwlock.at(()).at(1).replace(5) == Some(2));
+    assert!(*rwlock.try_read().unwrap() == vec![4, 5, 3]);
+}
+
+#[test]#[cfg(all(feature="collections", feature="detach"))]
+fn test_async_access() {
+    extern crate std;
+    use crate::detached_at;
+    use core::future::Future;
+    use core::task::{ Context, Poll };
+    use std::boxed::Box;
+    use std::sync::Arc;
+    use std::task::Wake;
+    use std::vec;
+
+    struct Noop;
+
+    impl Wake for Noop {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Arc::new(Noop).into();
+        let mut cx = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+
+        loop {
+            if let Poll::Ready(x) = future.as_mut().poll(&mut cx) { return x; }
+        }
+    }
+
+    let mutex = Mutex::new(vec![vec![1], vec![2]]);
+
+    assert!(block_on(access_async(&mutex, detached_at(1).at(0), |x| { *x += 1; *x })) == Some(3));
+    assert!(block_on(access_async(&mutex, detached_at(2), |_| ())).is_none());
+    assert!(mutex.try_lock().is_ok());
+
+    let rwlock = RwLock::new(vec![1, 2]);
+
+    assert!(block_on(write_async(&rwlock, detached_at(0), |x| { *x = 5; })) == Some(()));
+    assert!(*rwlock.try_read().unwrap() == vec![5, 2]);
+}
+
+#[test]#[cfg(feature="collections")]
+fn test_lock_across_await() {
+    use core::task::{ Context, Poll };
+    use std::sync::Arc;
+    use std::task::Wake;
+    use std::vec;
+    use std::vec::Vec;
+
+    struct Noop;
+
+    impl Wake for Noop {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    // pending on the first poll
+    struct YieldOnce(bool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 { return Poll::Ready(()); }
+
+            self.0 = true;
+            cx.waker().wake_by_ref();
+
+            Poll::Pending
+        }
+    }
+
+    let waker = Arc::new(Noop).into();
+    let mut cx = Context::from_waker(&waker);
+
+    let mutex = Mutex::new(vec![1]);
+
+    let mut future = Box::pin(lock_async(&mutex, |xs: &mut Vec<i32>| Box::pin(async move {
+        xs.at(0).replace(2);
+        YieldOnce(false).await;
+        xs.push(3);
+        xs.len()
+    })));
+
+    assert!(future.as_mut().poll(&mut cx).is_pending());
+    assert!(mutex.try_lock().is_err());
+    assert!(future.as_mut().poll(&mut cx) == Poll::Ready(2));
+    drop(future);
+    assert!(*mutex.try_lock().unwrap() == vec![2, 3]);
+
+    let rwlock = RwLock::new(vec![1]);
+
+    let mut future = Box::pin(write_lock_async(&rwlock, |xs: &mut Vec<i32>| Box::pin(async move {
+        YieldOnce(false).await;
+        xs.at(0).replace(4)
+    })));
+
+    assert!(future.as_mut().poll(&mut cx).is_pending());
+    assert!(rwlock.try_read().is_err());
+    assert!(future.as_mut().poll(&mut cx) == Poll::Ready(Some(1)));
+    drop(future);
+    assert!(*rwlock.try_read().unwrap() == vec![4]);
+}
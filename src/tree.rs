@@ -0,0 +1,214 @@
+//! Helpers for self-similar trees. __Requires `tree`.__
+//!
+//! A type is a [`Tree<I>`](trait.Tree.html) if its children are addressable
+//! by an index (`At<I, View=Self>`) and can be enumerated. ASTs, DOM-like
+//! trees and scene graphs are typical examples.
+//!
+//! The module provides:
+//! * [`descend`](fn.descend.html): access a node by a runtime path
+//! * [`Subtree`](struct.Subtree.html): a pre-order traversal of all nodes
+//! * [`find`](fn.find.html) and [`paths`](fn.paths.html): detached
+//!   [runtime paths](../struct.DynPath.html) to the nodes
+//!
+//! ```
+//! use smart_access::{ At, Cps };
+//! use smart_access::traversal::Each;
+//! use smart_access::tree::{ Tree, Subtree, descend, find };
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Node { name: &'static str, children: Vec<Node> }
+//!
+//! impl At<usize> for Node {
+//!     type View = Node;
+//!
+//!     fn access_at<R, F>(&mut self, i: usize, f: F) -> Option<R> where
+//!         F: FnOnce(&mut Node) -> R
+//!     {
+//!         self.children.get_mut(i).map(f)
+//!     }
+//! }
+//!
+//! impl Tree<usize> for Node {
+//!     fn children(&self) -> Vec<usize> { (0..self.children.len()).collect() }
+//! }
+//!
+//! let leaf = |name| Node { name, children: vec![] };
+//!
+//! let mut scene = Node { name: "root", children: vec![
+//!     Node { name: "player", children: vec![ leaf("sword") ] },
+//!     leaf("lamp"),
+//! ]};
+//!
+//! assert!(descend(&mut scene, &[0, 0]).access(|n| n.name) == Some("sword"));
+//!
+//! let mut names = vec![];
+//! scene.of(Subtree::new()).each(|n| { names.push(n.name); true });
+//! assert!(names == vec!["root", "player", "sword", "lamp"]);
+//!
+//! let path = find(&mut scene, |n| n.name == "lamp").unwrap();
+//! assert!(scene.at_dyn(path).access(|n| n.name = "torch").is_some());
+//! assert!(scene.children[1] == leaf("torch"));
+//! ```
+
+use crate::at::{ At, Cps };
+use crate::{ DynPath, DynAT };
+use crate::traversal::Of;
+use alloc::vec::Vec;
+use core::iter::Cloned;
+use core::marker::PhantomData;
+use core::slice;
+
+
+/// A self-similar tree with enumerable children.
+pub trait Tree<I>: At<I, View=Self> {
+    /// The indices of the children in order.
+    fn children(&self) -> Vec<I>;
+}
+
+
+/// Accesses a node by a runtime path.
+///
+/// Equivalent to `root.at_iter(path.iter().cloned())`.
+pub fn descend<'a, T, I>(root: &'a mut T, path: &'a [I]) -> DynAT<&'a mut T, Cloned<slice::Iter<'a, I>>> where
+    T: At<I, View=T> + ?Sized,
+    I: Clone
+{
+    root.at_iter(path.iter().cloned())
+}
+
+
+/// A pre-order traversal of a node and all its descendants.
+///
+/// Stopping the traversal (by returning `false`) stops it for the whole tree.
+///
+/// The index type is usually inferred: `tree.of(Subtree::new())`.
+#[derive(Debug)]
+pub struct Subtree<I>(PhantomData<fn() -> I>);
+
+impl<I> Subtree<I> {
+    /// Creates the traversal.
+    pub fn new() -> Self {
+        Subtree(PhantomData)
+    }
+}
+
+impl<I> Default for Subtree<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// a derived impl would require `I: Clone`
+impl<I> Clone for Subtree<I> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Tree<I>, I> Of<Subtree<I>> for T {
+    type View = T;
+
+    fn each_of<F>(&mut self, _: Subtree<I>, mut f: F) -> bool where
+        F: FnMut(&mut T) -> bool
+    {
+        walk(self, &mut f);
+
+        true
+    }
+}
+
+fn walk<T: Tree<I>, I, F>(node: &mut T, f: &mut F) -> bool where
+    F: FnMut(&mut T) -> bool
+{
+    if !f(node) { return false; }
+
+    for i in node.children() {
+        if node.access_at(i, |child| walk(child, f)) == Some(false) { return false; }
+    }
+
+    true
+}
+
+
+/// Finds the first node (in pre-order) satisfying the predicate.
+///
+/// Returns a detached path from the root to the node.
+pub fn find<T: Tree<I>, I: Clone, P>(root: &mut T, mut pred: P) -> Option<DynPath<I>> where
+    P: FnMut(&T) -> bool
+{
+    let mut path = DynPath::new();
+
+    if find_from(root, &mut pred, &mut path) { Some(path) } else { None }
+}
+
+fn find_from<T: Tree<I>, I: Clone, P>(node: &mut T, pred: &mut P, path: &mut DynPath<I>) -> bool where
+    P: FnMut(&T) -> bool
+{
+    if pred(node) { return true; }
+
+    for i in node.children() {
+        path.push(i.clone());
+
+        if node.access_at(i, |child| find_from(child, pred, path)) == Some(true) { return true; }
+
+        path.pop();
+    }
+
+    false
+}
+
+
+/// Detached paths to all nodes (in pre-order, the root path is empty).
+pub fn paths<T: Tree<I>, I: Clone>(root: &mut T) -> Vec<DynPath<I>> {
+    let mut result = Vec::new();
+
+    collect_paths(root, &mut DynPath::new(), &mut result);
+
+    result
+}
+
+fn collect_paths<T: Tree<I>, I: Clone>(node: &mut T, path: &mut DynPath<I>, result: &mut Vec<DynPath<I>>) {
+    result.push(path.clone());
+
+    for i in node.children() {
+        path.push(i.clone());
+        node.access_at(i, |child| collect_paths(child, path, result));
+        path.pop();
+    }
+}
+
+
+#[test]
+fn test_tree() {
+    use crate::traversal::Each;
+    use alloc::vec;
+
+    #[derive(Debug, PartialEq)]
+    struct Node(i32, Vec<Node>);
+
+    impl At<usize> for Node {
+        type View = Node;
+
+        fn access_at<R, F>(&mut self, i: usize, f: F) -> Option<R> where
+            F: FnOnce(&mut Node) -> R
+        {
+            self.1.get_mut(i).map(f)
+        }
+    }
+
+    impl Tree<usize> for Node {
+        fn children(&self) -> Vec<usize> { (0..self.1.len()).collect() }
+    }
+
+    let mut tree = Node(1, vec![ Node(2, vec![ Node(3, vec![]) ]), Node(4, vec![]) ]);
+
+    let all = paths(&mut tree).into_iter().map(|p| p.into_vec()).collect::<Vec<_>>();
+    assert!(all == vec![ vec![], vec![0], vec![0, 0], vec![1] ]);
+
+    tree.of(Subtree::new()).each(|n| { n.0 *= 10; n.0 < 30 });
+    assert!(tree == Node(10, vec![ Node(20, vec![ Node(30, vec![]) ]), Node(4, vec![]) ]));
+
+    assert!(find(&mut tree, |n| n.0 == 4).unwrap().into_vec() == vec![1]);
+    assert!(find(&mut tree, |n| n.0 == 5).is_none());
+    assert!(descend(&mut tree, &[1, 0]).touch().is_none());
+}
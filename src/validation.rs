@@ -0,0 +1,150 @@
+//! Validators attached to paths. __Requires `validation`.__
+//!
+//! A [`ValidationSet`](struct.ValidationSet.html) holds validators registered
+//! per [detached path](../type.DetachedPath.html). A mutation performed by
+//! [`access_validated`](struct.ValidationSet.html#method.access_validated)
+//! is checked by all validators whose paths are prefixes of the accessed path
+//! (including the path itself). If any of them fails the mutation is rolled back.
+//!
+//! Paths are compared componentwise by their `Debug`-formatted indices.
+//!
+//! ```
+//! use smart_access::detached_at;
+//! use smart_access::validation::ValidationSet;
+//!
+//! // a form of two sections with numeric fields
+//! let mut form = vec![ vec![10, 20], vec![30] ];
+//!
+//! let mut rules = ValidationSet::new();
+//! rules.add(detached_at(0), |section: &Vec<i32>| section.iter().sum::<i32>() <= 100);
+//! rules.add(detached_at(0).at(1), |field: &i32| *field >= 0);
+//!
+//! // both rules are checked
+//! assert!(rules.access_validated(&mut form, detached_at(0).at(1), |x| *x = 50) == Some(()));
+//! assert!(rules.access_validated(&mut form, detached_at(0).at(1), |x| *x = -1) == None);
+//! assert!(rules.access_validated(&mut form, detached_at(0).at(1), |x| *x = 95) == None);
+//!
+//! // only the first rule is checked
+//! assert!(rules.access_validated(&mut form, detached_at(0).at(0), |x| *x = -5) == Some(()));
+//!
+//! // no rules are checked
+//! assert!(rules.access_validated(&mut form, detached_at(1).at(0), |x| *x = -1) == Some(()));
+//!
+//! assert!(form == vec![ vec![-5, 50], vec![-1] ]);
+//! ```
+
+use crate::at::{ AtView, Cps, VisitPath };
+use crate::DetachedPath;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+
+type ErasedValidator<Root> = Box<dyn Fn(&mut Root) -> bool>;
+
+
+/// A set of validators attached to paths.
+///
+/// See the [module-level documentation](index.html) for an example.
+pub struct ValidationSet<Root: ?Sized> {
+    validators: Vec<(Vec<String>, ErasedValidator<Root>)>,
+}
+
+impl<Root: ?Sized> ValidationSet<Root> {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        ValidationSet { validators: Vec::new() }
+    }
+
+    /// Registers a validator of the view at the path.
+    ///
+    /// If the path doesn't resolve the validator fails.
+    pub fn add<List, V, F>(&mut self, path: DetachedPath<Root, List>, validator: F) where
+        Root: 'static,
+        List: AtView<Root, View=V> + VisitPath + Clone + 'static,
+        V: ?Sized,
+        F: Fn(&V) -> bool + 'static
+    {
+        let components = components(&path);
+
+        let erased = move |root: &mut Root| {
+            root.attach(path.clone()).access(|v| validator(v)).unwrap_or(false)
+        };
+
+        self.validators.push((components, Box::new(erased)));
+    }
+
+    /// The number of registered validators.
+    pub fn len(&self) -> usize {
+        self.validators.len()
+    }
+
+    /// Checks if there are no validators.
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_empty()
+    }
+
+    /// Runs the mutation then the validators whose paths are prefixes of the path.
+    ///
+    /// Returns `None` if the path doesn't resolve or a validator fails.
+    /// In the latter case the view is restored from a copy.
+    pub fn access_validated<List, V, R, F>(&self, root: &mut Root, path: DetachedPath<Root, List>, f: F) -> Option<R> where
+        List: AtView<Root, View=V> + VisitPath + Clone,
+        V: Clone,
+        F: FnOnce(&mut V) -> R
+    {
+        let components = components(&path);
+
+        let (backup, result) = root.attach(path.clone()).access(|v| (v.clone(), f(v)))?;
+
+        let valid = self.validators.iter()
+            .filter(|(prefix, _)| components.starts_with(prefix))
+            .all(|(_, validator)| validator(root));
+
+        if valid { return Some(result); }
+
+        root.attach(path).access(|v| *v = backup);
+
+        None
+    }
+}
+
+impl<Root: ?Sized> Default for ValidationSet<Root> {
+    fn default() -> Self {
+        ValidationSet::new()
+    }
+}
+
+fn components<Root: ?Sized, List: VisitPath>(path: &DetachedPath<Root, List>) -> Vec<String> {
+    let mut components = Vec::new();
+
+    path.visit_path(|_, index| components.push(format!("{:?}", index)));
+
+    components
+}
+
+
+#[test]
+fn test_validation() {
+    use crate::{ detached_at, EmptyPath };
+
+    let mut foo = [1, 2, 3];
+    let mut rules = ValidationSet::<[i32]>::new();
+
+    rules.add(detached_at(0), |x: &i32| *x > 0);
+    rules.add(detached_at(1), |x: &i32| *x < 10);
+    rules.add(detached_at(10), |_: &i32| true);
+
+    assert!(rules.len() == 3);
+
+    assert!(rules.access_validated(&mut foo[..], detached_at(0), |x| *x = 5) == Some(()));
+    assert!(rules.access_validated(&mut foo[..], detached_at(1), |x| *x = 10).is_none());
+    assert!(rules.access_validated(&mut foo[..], detached_at(2), |x| *x = -1) == Some(()));
+    assert!(rules.access_validated(&mut foo[..], detached_at(3), |x| *x = 0).is_none());
+    assert!(foo == [5, 2, -1]);
+
+    let mut empty = ValidationSet::<[i32]>::new();
+    empty.add(EmptyPath::new(), |xs: &[i32]| xs.len() == 3);
+    assert!(empty.access_validated(&mut foo[..], detached_at(0), |x| *x = 0) == Some(()));
+}
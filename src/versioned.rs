@@ -0,0 +1,138 @@
+//! Versioned values. __Requires `versioned`.__
+//!
+//! A [`Versioned`](struct.Versioned.html) wrapper snapshots the root
+//! before each mutation made through it. Any earlier version can be restored
+//! by [`checkout`](struct.Versioned.html#method.checkout):
+//!
+//! ```
+//! use smart_access::{ Cps, detached_at };
+//! use smart_access::versioned::Versioned;
+//!
+//! let mut doc = Versioned::new(vec![ vec![1, 2], vec![3] ]);
+//!
+//! assert!(doc.update(detached_at(0).at(1), |x| *x = 4) == Some(()));
+//! assert!(doc.update(detached_at(5).at(0), |x| *x = 5) == None); // no snapshot
+//! assert!(doc.at(()).at(1).access(|xs| xs.push(6)) == Some(()));
+//!
+//! assert!(doc.version() == 2);
+//! assert!(*doc.current() == vec![ vec![1, 4], vec![3, 6] ]);
+//! assert!(doc.history() == &[ vec![ vec![1, 2], vec![3] ], vec![ vec![1, 4], vec![3] ] ]);
+//!
+//! assert!(doc.checkout(1));
+//! assert!(*doc.current() == vec![ vec![1, 4], vec![3] ]);
+//! assert!(doc.version() == 1);
+//! assert!(!doc.checkout(2));
+//! ```
+
+use crate::at::{ At, Cps };
+use crate::Attach;
+use alloc::vec::Vec;
+
+
+/// A value with the history of its previous states.
+///
+/// The versions are numbered from zero (the initial value). Every mutation
+/// increments the version. Restoring a version discards all later ones.
+#[derive(Debug, Clone)]
+pub struct Versioned<T> {
+    current: T,
+    history: Vec<T>,
+}
+
+impl<T: Clone> Versioned<T> {
+    /// Creates a value with the empty history.
+    pub fn new(value: T) -> Self {
+        Versioned { current: value, history: Vec::new() }
+    }
+
+    /// The current value.
+    pub fn current(&self) -> &T {
+        &self.current
+    }
+
+    /// The number of the current version.
+    pub fn version(&self) -> usize {
+        self.history.len()
+    }
+
+    /// The previous states (the oldest first).
+    pub fn history(&self) -> &[T] {
+        &self.history
+    }
+
+    /// Mutates the value along a detached path.
+    ///
+    /// The snapshot is recorded only if the path resolves.
+    pub fn update<P, V, R, F>(&mut self, path: P, f: F) -> Option<R> where
+        P: Attach<T, View=V>,
+        V: ?Sized,
+        F: FnOnce(&mut V) -> R
+    {
+        let snapshot = self.current.clone();
+        let result = self.current.attach(path).access(f)?;
+
+        self.history.push(snapshot);
+
+        Some(result)
+    }
+
+    /// Restores the value of the version.
+    ///
+    /// Returns `false` (and does nothing) if the version is not older than the current one.
+    pub fn checkout(&mut self, version: usize) -> bool {
+        if version >= self.history.len() { return false; }
+
+        self.history.truncate(version + 1);
+        self.current = self.history.pop().unwrap();
+
+        true
+    }
+
+    /// Discards the history.
+    pub fn into_inner(self) -> T {
+        self.current
+    }
+}
+
+/// `access_at` is guaranteed to return `Some(f(..))`
+///
+/// The snapshot is recorded only if the value has changed: `f` can't report
+/// whether a path continuing from the value has resolved, thus the value
+/// is compared with the snapshot. A failed or read-only access adds nothing.
+impl<T: Clone + PartialEq> At<()> for Versioned<T> {
+    type View = T;
+
+    fn access_at<R, F>(&mut self, _: (), f: F) -> Option<R> where
+        F: FnOnce(&mut T) -> R
+    {
+        let snapshot = self.current.clone();
+        let result = f(&mut self.current);
+
+        if self.current != snapshot { self.history.push(snapshot); }
+
+        Some(result)
+    }
+}
+
+
+#[test]#[cfg(feature="collections")]
+fn test_versioned() {
+    use crate::detached_at;
+    use alloc::vec;
+
+    let mut foo = Versioned::new(vec![1, 2, 3]);
+
+    for i in 0..3 {
+        foo.at(()).at(i).access(|x| *x *= 10);
+    }
+
+    assert!(foo.at(()).at(5).access(|x| *x = 0).is_none());
+    assert!(foo.at(()).at(0).get_clone() == Some(10));
+
+    assert!(foo.update(detached_at(3), |x: &mut i32| *x = 0).is_none());
+    assert!(foo.version() == 3);
+
+    assert!(foo.checkout(0));
+    assert!(foo.history().is_empty());
+    assert!(foo.into_inner() == vec![1, 2, 3]);
+}
@@ -0,0 +1,220 @@
+//! A network patch format. __Requires `wire`.__
+//!
+//! A [`Patch`](struct.Patch.html) is a runtime path of [keys](../patch/enum.Key.html)
+//! paired with either a serialized new value or a delete marker. Patches are
+//! serializable, thus two processes can synchronize deep edits of the same
+//! data model: the sender serializes the edits, the receiver
+//! [applies](struct.Patch.html#method.apply) them to its copy.
+//!
+//! The new values are carried as `serde_json::Value` (thus the transport
+//! format must be self-describing) and deserialized at the target location.
+//! The receiving side must implement [`Apply`](trait.Apply.html). It is
+//! implemented for scalars, `String`, `Option`, `Vec`, `BTreeMap<String, T>`
+//! and `serde_json::Value`.
+//!
+//! ```
+//! use smart_access::patch::parse_path;
+//! use smart_access::wire::Patch;
+//! use std::collections::BTreeMap;
+//!
+//! let mut sender = BTreeMap::new();
+//! sender.insert("alice".to_string(), vec![1, 2]);
+//! let mut receiver = sender.clone();
+//!
+//! let edits = vec![
+//!     Patch::set(parse_path("alice[1]").unwrap(), &5).unwrap(),
+//!     Patch::set(parse_path("bob").unwrap(), &[3]).unwrap(),
+//!     Patch::delete(parse_path("alice[0]").unwrap()),
+//! ];
+//!
+//! for edit in &edits { assert!(edit.apply(&mut sender)); }
+//!
+//! let message = serde_json::to_string(&edits).unwrap();
+//!
+//! // ... on the receiving side
+//! let received: Vec<Patch> = serde_json::from_str(&message).unwrap();
+//! for edit in &received { assert!(edit.apply(&mut receiver)); }
+//!
+//! assert!(receiver == sender);
+//! assert!(receiver["alice"] == vec![5]);
+//!
+//! // a value of a wrong type is rejected
+//! assert!(!Patch::set(parse_path("bob[0]").unwrap(), "x").unwrap().apply(&mut receiver));
+//! ```
+
+use crate::at::Cps;
+use crate::DynPath;
+use crate::command::Command;
+use crate::patch::Key;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{ Serialize, Deserialize };
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+
+/// A serializable edit: a runtime path and a new value (`None` means deletion).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Patch {
+    pub path: DynPath<Key>,
+    pub value: Option<Value>,
+}
+
+impl Patch {
+    /// A patch replacing (or inserting) the value at the path.
+    ///
+    /// Returns `None` if the value can't be serialized.
+    pub fn set<T: Serialize + ?Sized>(path: DynPath<Key>, value: &T) -> Option<Self> {
+        Some(Patch { path, value: Some(serde_json::to_value(value).ok()?) })
+    }
+
+    /// A patch deleting the value at the path.
+    pub fn delete(path: DynPath<Key>) -> Self {
+        Patch { path, value: None }
+    }
+
+    /// Applies the patch.
+    ///
+    /// Returns `false` if the path doesn't resolve or the value
+    /// can't be deserialized. In such a case the root is left untouched.
+    pub fn apply<T: Apply + ?Sized>(&self, root: &mut T) -> bool {
+        root.apply_at(self.path.indices(), self.value.as_ref())
+    }
+}
+
+
+/// A value which can receive [patches](struct.Patch.html).
+///
+/// The conventions are:
+/// * an empty path with `Some(value)` replaces `self`
+/// * a single key with `Some(value)` inserts the value if the key is missing
+///   (for sequences: if the index equals the length)
+/// * a single key with `None` removes the item (`None` with an empty path
+///   is accepted only by `Option`)
+pub trait Apply {
+    /// Replaces (`Some`) or deletes (`None`) the value at the path.
+    fn apply_at(&mut self, path: &[Key], value: Option<&Value>) -> bool;
+}
+
+fn replace<T: DeserializeOwned>(target: &mut T, value: Option<&Value>) -> bool {
+    match value.map(T::deserialize) {
+        Some(Ok(x)) => { *target = x; true }
+        _ => false,
+    }
+}
+
+macro_rules! apply_scalar {
+    ( $($t:ty),* ) => { $(
+        impl Apply for $t {
+            fn apply_at(&mut self, path: &[Key], value: Option<&Value>) -> bool {
+                path.is_empty() && replace(self, value)
+            }
+        }
+    )* };
+}
+
+apply_scalar!(
+    i8, i16, i32, i64, i128, isize,
+    u8, u16, u32, u64, u128, usize,
+    f32, f64, bool, char, String
+);
+
+/// The path goes through the `Some` value
+impl<T: Apply + DeserializeOwned> Apply for Option<T> {
+    fn apply_at(&mut self, path: &[Key], value: Option<&Value>) -> bool {
+        match (path.is_empty(), value) {
+            (true, None) => { *self = None; true }
+            (true, _)    => replace(self, value),
+            (false, _)   => self.at(()).access(|x| x.apply_at(path, value)).unwrap_or(false),
+        }
+    }
+}
+
+/// A range with a single key and `None` removes the range
+impl<T: Apply + DeserializeOwned> Apply for Vec<T> {
+    fn apply_at(&mut self, path: &[Key], value: Option<&Value>) -> bool {
+        match (path.split_first(), value) {
+            (None, _) => replace(self, value),
+
+            (Some((&Key::Index(i), [])), None) if i < self.len() => { self.remove(i); true }
+
+            (Some((&Key::Index(i), [])), Some(v)) if i == self.len() => match T::deserialize(v) {
+                Ok(x) => { self.push(x); true }
+                Err(_) => false,
+            },
+
+            (Some((&Key::Index(i), rest)), _) => self.at(i)
+                .access(|x| x.apply_at(rest, value))
+                .unwrap_or(false),
+
+            (Some((&Key::Range { start, end }, [])), None) if start <= end && end <= self.len() => {
+                self.drain(start..end); true
+            }
+
+            (Some((&Key::Range { start, end }, rest)), _) => self.at(start..end)
+                .access(|xs| xs.apply_at(rest, value))
+                .unwrap_or(false),
+
+            _ => false,
+        }
+    }
+}
+
+impl<T: Apply + DeserializeOwned> Apply for BTreeMap<String, T> {
+    fn apply_at(&mut self, path: &[Key], value: Option<&Value>) -> bool {
+        match (path.split_first(), value) {
+            (None, _) => replace(self, value),
+
+            (Some((Key::Name(name), [])), None) => self.remove(name).is_some(),
+
+            (Some((Key::Name(name), [])), Some(v)) if !self.contains_key(name) => match T::deserialize(v) {
+                Ok(x) => { self.insert(name.clone(), x); true }
+                Err(_) => false,
+            },
+
+            (Some((Key::Name(name), rest)), _) => self.at(name.as_str())
+                .access(|x| x.apply_at(rest, value))
+                .unwrap_or(false),
+
+            _ => false,
+        }
+    }
+}
+
+/// Any JSON value is accepted
+impl Apply for Value {
+    fn apply_at(&mut self, path: &[Key], value: Option<&Value>) -> bool {
+        let path: DynPath<Key> = path.iter().cloned().collect();
+
+        match value {
+            Some(v) => Command::replace(path.clone(), v.clone()).apply(self)
+                || (!path.is_empty() && Command::insert(path, v.clone()).apply(self)),
+            None => Command::<Key, Value>::remove(path).apply(self),
+        }
+    }
+}
+
+
+#[test]
+fn test_wire() {
+    use crate::patch::parse_path;
+    use alloc::vec;
+
+    let mut foo = vec![Some(vec![1, 2, 3]), None];
+
+    assert!(Patch::set(parse_path("[0][1..3]").unwrap(), &[5]).unwrap().apply(&mut foo));
+    assert!(Patch::set(parse_path("[1]").unwrap(), &[4]).unwrap().apply(&mut foo));
+    assert!(Patch::delete(parse_path("[1]").unwrap()).apply(&mut foo));
+    assert!(!Patch::delete(parse_path("[1]").unwrap()).apply(&mut foo));
+    assert!(!Patch::set(parse_path("[0][0]").unwrap(), &"a").unwrap().apply(&mut foo));
+    assert!(foo == vec![Some(vec![1, 5])]);
+
+    let mut json = serde_json::json!({"a": [1]});
+
+    assert!(Patch::set(parse_path("a[1]").unwrap(), &2).unwrap().apply(&mut json));
+    assert!(Patch::set(parse_path("b").unwrap(), &true).unwrap().apply(&mut json));
+    assert!(Patch::delete(parse_path("a[0]").unwrap()).apply(&mut json));
+    assert!(!Patch::set(parse_path("c.d").unwrap(), &0).unwrap().apply(&mut json));
+    assert!(json == serde_json::json!({"a": [2], "b": true}));
+}
@@ -0,0 +1,75 @@
+use smart_access::{ AtDelegate, Cps, Fields, EmptyPath };
+
+#[cfg(feature="traversal")]
+use smart_access::traversal::{ Each, AllFields };
+
+#[derive(Fields, Debug, PartialEq)]
+pub struct Pair<'a, T> where T: Clone + PartialEq {
+    pub first: T,
+    second: &'a str,
+}
+
+#[derive(Fields)]
+struct Wrapper<T: Clone + PartialEq> { pairs: Vec<Pair<'static, T>> }
+
+
+#[test]
+fn test() {
+    let mut foo = Wrapper { pairs: vec![ Pair { first: 1, second: "a" } ] };
+
+    let first = EmptyPath::<Wrapper<i32>>::new().pairs().at(0).first();
+
+    assert!(foo.attach(first.clone()).replace(2) == Some(1));
+    assert!(foo.attach(first).get_clone() == Some(2));
+
+    assert!(foo.cut().pairs().at(0).second().replace("b") == Some("a"));
+    assert!(foo.cut().pairs().at(1).second().touch().is_none());
+
+    assert!(foo.pairs == vec![ Pair { first: 2, second: "b" } ]);
+}
+
+
+#[cfg(feature="traversal")]
+#[derive(AllFields, Clone, PartialEq)]
+struct Triple<T>(T, T, T);
+
+#[cfg(feature="traversal")]
+#[test]
+fn test_all_fields() {
+    let mut foo = Wrapper { pairs: vec![ Pair { first: Triple(1, 2, 3), second: "a" } ] };
+
+    foo.cut().pairs().at(0).first().access(|t| t.of(AllFields).each(|x| { *x *= 10; *x < 20 }));
+
+    assert!(foo.pairs[0].first == Triple(10, 20, 3));
+}
+
+
+#[derive(AtDelegate)]
+#[at_delegate(usize)]
+struct Pairs<T: Clone + PartialEq>(Vec<Pair<'static, T>>);
+
+
+#[test]
+fn test_at_delegate() {
+    let mut foo = Pairs(vec![ Pair { first: 1, second: "a" } ]);
+
+    assert!(foo.at(0).cut().first().replace(2) == Some(1));
+    assert!(foo.at(1).touch().is_none());
+    assert!(foo.at(()).access(|v| v.len()) == Some(1));
+    assert!(foo.0[0].first == 2);
+}
+
+
+#[derive(Fields)]
+struct Named { pairs: usize }
+
+
+#[test]
+fn test_shared_field_names() {
+    let mut foo = Named { pairs: 1 };
+    let mut bar = Wrapper { pairs: vec![ Pair { first: 1, second: "a" } ] };
+
+    assert!(foo.cut().pairs().replace(2) == Some(1));
+    assert!(bar.cut().pairs().at(0).first().replace(2) == Some(1));
+    assert!(foo.attach(EmptyPath::<Named>::new().pairs()).get_clone() == Some(2));
+}